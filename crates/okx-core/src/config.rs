@@ -1,8 +1,8 @@
 //! Configuration management.
 
 use crate::{
-    Credentials, REST_API_URL, WS_PRIVATE_URL, WS_PRIVATE_URL_SIMULATED, WS_PUBLIC_URL,
-    WS_PUBLIC_URL_SIMULATED,
+    Credentials, RetryPolicy, REST_API_URL, WS_BUSINESS_URL, WS_BUSINESS_URL_SIMULATED,
+    WS_PRIVATE_URL, WS_PRIVATE_URL_SIMULATED, WS_PUBLIC_URL, WS_PUBLIC_URL_SIMULATED,
 };
 
 /// OKX client configuration.
@@ -16,12 +16,20 @@ pub struct Config {
     ws_public_url: String,
     /// WebSocket private URL
     ws_private_url: String,
+    /// WebSocket business URL
+    ws_business_url: String,
     /// Whether to use simulated (demo) trading
     simulated: bool,
     /// Request timeout in seconds
     timeout_secs: u64,
     /// HTTP/HTTPS proxy URL
     proxy_url: Option<String>,
+    /// Retry policy for transient REST failures
+    retry_policy: Option<RetryPolicy>,
+    /// WebSocket idle time in seconds before sending a ping (0 = disabled)
+    ws_ping_interval_secs: u64,
+    /// WebSocket pong deadline in seconds after a ping
+    ws_pong_timeout_secs: u64,
 }
 
 impl Config {
@@ -35,9 +43,13 @@ impl Config {
             rest_url: REST_API_URL.to_string(),
             ws_public_url: WS_PUBLIC_URL.to_string(),
             ws_private_url: WS_PRIVATE_URL.to_string(),
+            ws_business_url: WS_BUSINESS_URL.to_string(),
             simulated: false,
             timeout_secs: 30,
             proxy_url: None,
+            retry_policy: None,
+            ws_ping_interval_secs: 20,
+            ws_pong_timeout_secs: 10,
         }
     }
 
@@ -50,9 +62,11 @@ impl Config {
         if simulated {
             self.ws_public_url = WS_PUBLIC_URL_SIMULATED.to_string();
             self.ws_private_url = WS_PRIVATE_URL_SIMULATED.to_string();
+            self.ws_business_url = WS_BUSINESS_URL_SIMULATED.to_string();
         } else {
             self.ws_public_url = WS_PUBLIC_URL.to_string();
             self.ws_private_url = WS_PRIVATE_URL.to_string();
+            self.ws_business_url = WS_BUSINESS_URL.to_string();
         }
         self
     }
@@ -78,6 +92,13 @@ impl Config {
         self
     }
 
+    /// Set a custom WebSocket business URL.
+    #[must_use]
+    pub fn with_ws_business_url(mut self, url: impl Into<String>) -> Self {
+        self.ws_business_url = url.into();
+        self
+    }
+
     /// Set the request timeout in seconds.
     #[must_use]
     pub fn with_timeout_secs(mut self, secs: u64) -> Self {
@@ -100,6 +121,31 @@ impl Config {
         self
     }
 
+    /// Set the retry policy for transient REST failures.
+    ///
+    /// Retries are disabled unless a policy is set.
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Set the WebSocket idle time in seconds before a `ping` is sent.
+    ///
+    /// OKX drops connections idle for 30 seconds; `0` disables the heartbeat.
+    #[must_use]
+    pub fn with_ws_ping_interval_secs(mut self, secs: u64) -> Self {
+        self.ws_ping_interval_secs = secs;
+        self
+    }
+
+    /// Set how long to wait for a `pong` before treating the connection as dead.
+    #[must_use]
+    pub fn with_ws_pong_timeout_secs(mut self, secs: u64) -> Self {
+        self.ws_pong_timeout_secs = secs;
+        self
+    }
+
     /// Get the credentials.
     #[must_use]
     pub fn credentials(&self) -> &Credentials {
@@ -124,6 +170,12 @@ impl Config {
         &self.ws_private_url
     }
 
+    /// Get the WebSocket business URL.
+    #[must_use]
+    pub fn ws_business_url(&self) -> &str {
+        &self.ws_business_url
+    }
+
     /// Check if simulated trading is enabled.
     #[must_use]
     pub fn is_simulated(&self) -> bool {
@@ -141,14 +193,32 @@ impl Config {
     pub fn proxy_url(&self) -> Option<&str> {
         self.proxy_url.as_deref()
     }
+
+    /// Get the WebSocket idle time in seconds before a `ping` is sent.
+    #[must_use]
+    pub fn ws_ping_interval_secs(&self) -> u64 {
+        self.ws_ping_interval_secs
+    }
+
+    /// Get the WebSocket pong deadline in seconds.
+    #[must_use]
+    pub fn ws_pong_timeout_secs(&self) -> u64 {
+        self.ws_pong_timeout_secs
+    }
+
+    /// Get the retry policy.
+    #[must_use]
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        REST_API_URL, WS_PRIVATE_URL, WS_PRIVATE_URL_SIMULATED, WS_PUBLIC_URL,
-        WS_PUBLIC_URL_SIMULATED,
+        REST_API_URL, WS_BUSINESS_URL, WS_BUSINESS_URL_SIMULATED, WS_PRIVATE_URL,
+        WS_PRIVATE_URL_SIMULATED, WS_PUBLIC_URL, WS_PUBLIC_URL_SIMULATED,
     };
 
     #[test]
@@ -157,9 +227,13 @@ mod tests {
         assert_eq!(cfg.rest_url(), REST_API_URL);
         assert_eq!(cfg.ws_public_url(), WS_PUBLIC_URL);
         assert_eq!(cfg.ws_private_url(), WS_PRIVATE_URL);
+        assert_eq!(cfg.ws_business_url(), WS_BUSINESS_URL);
         assert!(!cfg.is_simulated());
         assert_eq!(cfg.timeout_secs(), 30);
         assert!(cfg.proxy_url().is_none());
+        assert!(cfg.retry_policy().is_none());
+        assert_eq!(cfg.ws_ping_interval_secs(), 20);
+        assert_eq!(cfg.ws_pong_timeout_secs(), 10);
     }
 
     #[test]
@@ -168,6 +242,7 @@ mod tests {
         assert!(cfg.is_simulated());
         assert_eq!(cfg.ws_public_url(), WS_PUBLIC_URL_SIMULATED);
         assert_eq!(cfg.ws_private_url(), WS_PRIVATE_URL_SIMULATED);
+        assert_eq!(cfg.ws_business_url(), WS_BUSINESS_URL_SIMULATED);
     }
 
     #[test]
@@ -176,13 +251,21 @@ mod tests {
             .with_rest_url("https://alt.com")
             .with_ws_public_url("wss://pub")
             .with_ws_private_url("wss://pri")
+            .with_ws_business_url("wss://biz")
             .with_timeout_secs(5)
-            .with_proxy("http://127.0.0.1:7890");
+            .with_proxy("http://127.0.0.1:7890")
+            .with_retry_policy(RetryPolicy::new(4))
+            .with_ws_ping_interval_secs(0)
+            .with_ws_pong_timeout_secs(3);
 
         assert_eq!(cfg.rest_url(), "https://alt.com");
         assert_eq!(cfg.ws_public_url(), "wss://pub");
         assert_eq!(cfg.ws_private_url(), "wss://pri");
+        assert_eq!(cfg.ws_business_url(), "wss://biz");
         assert_eq!(cfg.timeout_secs(), 5);
         assert_eq!(cfg.proxy_url(), Some("http://127.0.0.1:7890"));
+        assert_eq!(cfg.retry_policy().map(RetryPolicy::max_attempts), Some(4));
+        assert_eq!(cfg.ws_ping_interval_secs(), 0);
+        assert_eq!(cfg.ws_pong_timeout_secs(), 3);
     }
 }
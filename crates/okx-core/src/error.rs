@@ -2,6 +2,8 @@
 
 use thiserror::Error;
 
+use crate::OkxErrorCode;
+
 /// Result type alias for OKX operations.
 pub type Result<T> = std::result::Result<T, OkxError>;
 
@@ -29,7 +31,7 @@ pub enum OkxError {
     #[error("API error: code={code}, msg={msg}")]
     Api {
         /// Error code from OKX
-        code: String,
+        code: OkxErrorCode,
         /// Error message from OKX
         msg: String,
     },
@@ -54,6 +56,25 @@ pub enum OkxError {
     #[error("Connection closed")]
     ConnectionClosed,
 
+    /// Request blocked by the client-side rate limiter
+    #[error("Rate limit exceeded for {endpoint}, retry after {retry_after_ms}ms")]
+    RateLimitExceeded {
+        /// Endpoint path
+        endpoint: String,
+        /// Time until a permit frees up, in milliseconds
+        retry_after_ms: u64,
+    },
+
+    /// Request failed after being retried
+    #[error("{source} (after {attempts} attempts)")]
+    Retried {
+        /// Number of attempts made, including the first one
+        attempts: u32,
+        /// Error of the last attempt
+        #[source]
+        source: Box<OkxError>,
+    },
+
     /// Other errors
     #[error("{0}")]
     Other(String),
@@ -62,7 +83,7 @@ pub enum OkxError {
 impl OkxError {
     /// Create an API error from code and message.
     #[must_use]
-    pub fn api(code: impl Into<String>, msg: impl Into<String>) -> Self {
+    pub fn api(code: impl Into<OkxErrorCode>, msg: impl Into<String>) -> Self {
         Self::Api {
             code: code.into(),
             msg: msg.into(),
@@ -72,7 +93,16 @@ impl OkxError {
     /// Check if this is an API error with a specific code.
     #[must_use]
     pub fn is_api_error(&self, code: &str) -> bool {
-        matches!(self, Self::Api { code: c, .. } if c == code)
+        matches!(self.last_attempt(), Self::Api { code: c, .. } if c == code)
+    }
+
+    /// Get the OKX error code if this is an API error.
+    #[must_use]
+    pub fn api_code(&self) -> Option<&OkxErrorCode> {
+        match self.last_attempt() {
+            Self::Api { code, .. } => Some(code),
+            _ => None,
+        }
     }
 
     /// Create an HTTP status error.
@@ -87,13 +117,46 @@ impl OkxError {
     /// Check if this is an HTTP status error with a specific status code.
     #[must_use]
     pub fn is_http_status(&self, status: u16) -> bool {
-        matches!(self, Self::HttpStatus { status: s, .. } if *s == status)
+        matches!(self.last_attempt(), Self::HttpStatus { status: s, .. } if *s == status)
     }
 
-    /// Check if this is a rate limit error (HTTP 429).
+    /// Check if this is a rate limit error (HTTP 429 or client-side limiter).
     #[must_use]
     pub fn is_rate_limited(&self) -> bool {
-        self.is_http_status(429)
+        self.is_http_status(429) || matches!(self.last_attempt(), Self::RateLimitExceeded { .. })
+    }
+
+    /// Record the number of attempts made before this error.
+    ///
+    /// Single attempts are returned unchanged.
+    #[must_use]
+    pub fn with_attempts(self, attempts: u32) -> Self {
+        match self {
+            Self::Retried { source, .. } => source.with_attempts(attempts),
+            err if attempts > 1 => Self::Retried {
+                attempts,
+                source: Box::new(err),
+            },
+            err => err,
+        }
+    }
+
+    /// Get the number of attempts made before this error.
+    #[must_use]
+    pub fn attempts(&self) -> u32 {
+        match self {
+            Self::Retried { attempts, .. } => *attempts,
+            _ => 1,
+        }
+    }
+
+    /// Get the error of the last attempt, looking through [`OkxError::Retried`].
+    #[must_use]
+    pub fn last_attempt(&self) -> &Self {
+        match self {
+            Self::Retried { source, .. } => source.last_attempt(),
+            err => err,
+        }
     }
 }
 
@@ -107,6 +170,7 @@ mod tests {
         assert!(matches!(err, OkxError::Api { .. }));
         assert!(err.is_api_error("51000"));
         assert!(!err.is_api_error("400"));
+        assert_eq!(err.api_code(), Some(&OkxErrorCode::ParameterError));
         assert_eq!(err.to_string(), "API error: code=51000, msg=failure");
     }
 
@@ -120,10 +184,37 @@ mod tests {
         assert_eq!(err.to_string(), "HTTP status 429: rate limited");
     }
 
+    #[test]
+    fn local_rate_limit_error_is_rate_limited() {
+        let err = OkxError::RateLimitExceeded {
+            endpoint: "/api/v5/trade/order".into(),
+            retry_after_ms: 150,
+        };
+        assert!(err.is_rate_limited());
+        assert!(!err.is_http_status(429));
+        assert_eq!(
+            err.to_string(),
+            "Rate limit exceeded for /api/v5/trade/order, retry after 150ms"
+        );
+    }
+
     #[test]
     fn http_status_500_is_not_rate_limited() {
         let err = OkxError::http_status(500, "internal error");
         assert!(!err.is_rate_limited());
         assert!(err.is_http_status(500));
     }
+
+    #[test]
+    fn retried_error_carries_attempts_and_keeps_helpers() {
+        let err = OkxError::http_status(503, "busy").with_attempts(3);
+        assert_eq!(err.attempts(), 3);
+        assert!(err.is_http_status(503));
+        assert!(matches!(err.last_attempt(), OkxError::HttpStatus { .. }));
+        assert_eq!(err.to_string(), "HTTP status 503: busy (after 3 attempts)");
+
+        let single = OkxError::api("51000", "failure").with_attempts(1);
+        assert_eq!(single.attempts(), 1);
+        assert!(matches!(single, OkxError::Api { .. }));
+    }
 }
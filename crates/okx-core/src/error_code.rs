@@ -0,0 +1,397 @@
+//! OKX v5 error code catalog.
+//!
+//! Source: OKX API v5 Error Codes
+//! - <https://www.okx.com/docs-v5/en/#error-code>
+
+use std::borrow::Cow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// Family of an OKX error code, derived from its numeric prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OkxErrorFamily {
+    /// 50xxx general errors
+    General,
+    /// 51xxx trade errors
+    Trade,
+    /// 58xxx funding errors
+    Funding,
+    /// 59xxx account errors
+    Account,
+    /// 60xxx / 64xxx WebSocket errors
+    WebSocket,
+    /// Any other code
+    Other,
+}
+
+macro_rules! error_codes {
+    ($($(#[$doc:meta])* $variant:ident = $code:literal,)+) => {
+        /// Documented OKX v5 error code.
+        ///
+        /// Codes not in the catalog are kept verbatim in [`OkxErrorCode::Other`].
+        /// Equality and hashing compare the code string, so `Other("51008")`
+        /// equals [`OkxErrorCode::InsufficientBalance`].
+        #[derive(Debug, Clone, Eq)]
+        #[non_exhaustive]
+        pub enum OkxErrorCode {
+            $($(#[$doc])* $variant,)+
+            /// Code not in the catalog
+            Other(String),
+        }
+
+        impl OkxErrorCode {
+            /// Look up a code string.
+            #[must_use]
+            pub fn from_code(code: &str) -> Self {
+                match code {
+                    $($code => Self::$variant,)+
+                    other => Self::Other(other.to_string()),
+                }
+            }
+
+            /// Get the code string.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)+
+                    Self::Other(code) => code,
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // 50xxx 通用错误
+    /// 50000: Body cannot be empty
+    EmptyBody = "50000",
+    /// 50001: Service temporarily unavailable
+    ServiceUnavailable = "50001",
+    /// 50002: JSON syntax error
+    JsonSyntaxError = "50002",
+    /// 50004: API endpoint request timeout
+    EndpointTimeout = "50004",
+    /// 50005: API is offline or unavailable
+    ApiOffline = "50005",
+    /// 50006: Invalid Content-Type
+    InvalidContentType = "50006",
+    /// 50007: Account blocked
+    AccountBlocked = "50007",
+    /// 50008: User does not exist
+    UserNotFound = "50008",
+    /// 50009: Account suspended due to ongoing liquidation
+    AccountInLiquidation = "50009",
+    /// 50011: Rate limit reached
+    RateLimitReached = "50011",
+    /// 50013: Systems are busy
+    SystemBusy = "50013",
+    /// 50014: Parameter cannot be empty
+    ParameterRequired = "50014",
+    /// 50026: System error
+    SystemError = "50026",
+    /// 50061: Sub-account rate limit exceeded
+    SubAccountRateLimitReached = "50061",
+    /// 50100: API frozen
+    ApiFrozen = "50100",
+    /// 50101: API key does not match current environment
+    ApiKeyEnvironmentMismatch = "50101",
+    /// 50102: Timestamp request expired
+    TimestampExpired = "50102",
+    /// 50103: `OK-ACCESS-KEY` cannot be empty
+    AccessKeyRequired = "50103",
+    /// 50104: `OK-ACCESS-PASSPHRASE` cannot be empty
+    PassphraseRequired = "50104",
+    /// 50105: `OK-ACCESS-PASSPHRASE` incorrect
+    PassphraseIncorrect = "50105",
+    /// 50106: `OK-ACCESS-SIGN` cannot be empty
+    SignRequired = "50106",
+    /// 50107: `OK-ACCESS-TIMESTAMP` cannot be empty
+    TimestampRequired = "50107",
+    /// 50110: IP not in the API key whitelist
+    InvalidIp = "50110",
+    /// 50111: Invalid `OK-ACCESS-KEY`
+    InvalidAccessKey = "50111",
+    /// 50112: Invalid `OK-ACCESS-TIMESTAMP`
+    InvalidTimestamp = "50112",
+    /// 50113: Invalid signature
+    InvalidSign = "50113",
+    /// 50114: Invalid authorization
+    InvalidAuthorization = "50114",
+    /// 50115: Invalid request method
+    InvalidRequestMethod = "50115",
+
+    // 51xxx 交易错误
+    /// 51000: Parameter error
+    ParameterError = "51000",
+    /// 51001: Instrument ID does not exist
+    InstrumentNotFound = "51001",
+    /// 51004: Order amount exceeds the current tier limit
+    TierLimitExceeded = "51004",
+    /// 51006: Order price is not within the price limit
+    PriceOutOfLimit = "51006",
+    /// 51008: Order failed, insufficient balance
+    InsufficientBalance = "51008",
+    /// 51016: Duplicated `clOrdId`
+    DuplicateClOrdId = "51016",
+    /// 51020: Order amount below the minimum
+    OrderSizeTooSmall = "51020",
+    /// 51119: Order failed, insufficient margin
+    InsufficientMargin = "51119",
+    /// 51131: Insufficient balance
+    InsufficientAvailBalance = "51131",
+    /// 51149: Order timed out, please try again later
+    ///
+    /// The order may still have been placed; query it before resubmitting.
+    OrderTimeout = "51149",
+    /// 51400: Cancellation failed, order filled, canceled or not found
+    CancelFailed = "51400",
+    /// 51401: Cancellation failed, order already canceled
+    OrderAlreadyCanceled = "51401",
+    /// 51402: Cancellation failed, order already completed
+    OrderAlreadyCompleted = "51402",
+    /// 51503: Amendment failed, order filled, canceled or not found
+    AmendFailed = "51503",
+    /// 51603: Order does not exist
+    OrderNotFound = "51603",
+
+    // 58xxx 资金错误
+    /// 58350: Insufficient balance in the funding account
+    FundingInsufficientBalance = "58350",
+
+    // 59xxx 账户错误
+    /// 59000: Close positions and cancel orders before changing settings
+    SettingsBlockedByPositions = "59000",
+    /// 59102: Leverage exceeds the maximum
+    LeverageTooHigh = "59102",
+    /// 59200: Insufficient account balance
+    AccountInsufficientBalance = "59200",
+    /// 59201: Negative account balance
+    NegativeBalance = "59201",
+
+    // 60xxx / 64xxx WebSocket 错误
+    /// 60004: Invalid timestamp
+    WsInvalidTimestamp = "60004",
+    /// 60005: Invalid API key
+    WsInvalidApiKey = "60005",
+    /// 60006: Timestamp request expired
+    WsTimestampExpired = "60006",
+    /// 60007: Invalid sign
+    WsInvalidSign = "60007",
+    /// 60008: Private channels are not supported on this endpoint
+    WsPrivateNotSupported = "60008",
+    /// 60009: Login failed
+    WsLoginFailed = "60009",
+    /// 60011: Please log in
+    WsNotLoggedIn = "60011",
+    /// 60012: Invalid request
+    WsInvalidRequest = "60012",
+    /// 60013: Invalid args
+    WsInvalidArgs = "60013",
+    /// 60014: Requests too frequent
+    WsRequestsTooFrequent = "60014",
+    /// 60018: Wrong URL or channel does not exist
+    WsChannelNotFound = "60018",
+    /// 60019: Invalid op
+    WsInvalidOp = "60019",
+    /// 60024: Wrong passphrase
+    WsWrongPassphrase = "60024",
+    /// 60032: API key does not exist
+    WsApiKeyNotFound = "60032",
+    /// 64008: Connection will be closed soon for a service upgrade
+    WsServiceUpgrade = "64008",
+}
+
+impl OkxErrorCode {
+    /// Get the family of this code.
+    #[must_use]
+    pub fn family(&self) -> OkxErrorFamily {
+        let code = self.as_str();
+        if code.len() != 5 || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return OkxErrorFamily::Other;
+        }
+        match &code[..2] {
+            "50" => OkxErrorFamily::General,
+            "51" => OkxErrorFamily::Trade,
+            "58" => OkxErrorFamily::Funding,
+            "59" => OkxErrorFamily::Account,
+            "60" | "64" => OkxErrorFamily::WebSocket,
+            _ => OkxErrorFamily::Other,
+        }
+    }
+
+    /// 手动构造的 `Other` 若属于目录中的错误码，按对应变体分类。
+    fn canonical(&self) -> Cow<'_, Self> {
+        match self {
+            Self::Other(code) => Cow::Owned(Self::from_code(code)),
+            known => Cow::Borrowed(known),
+        }
+    }
+
+    /// Check if the request may succeed when retried later (busy, timeout, rate limit).
+    ///
+    /// [`OkxErrorCode::OrderTimeout`] is not included: the order may already
+    /// exist, so resubmitting it blindly can place a duplicate.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            *self.canonical(),
+            Self::ServiceUnavailable
+                | Self::EndpointTimeout
+                | Self::RateLimitReached
+                | Self::SystemBusy
+                | Self::SystemError
+                | Self::SubAccountRateLimitReached
+                | Self::WsRequestsTooFrequent
+        )
+    }
+
+    /// Check if this is an insufficient balance / margin error.
+    #[must_use]
+    pub fn is_insufficient_balance(&self) -> bool {
+        matches!(
+            *self.canonical(),
+            Self::InsufficientBalance
+                | Self::InsufficientMargin
+                | Self::InsufficientAvailBalance
+                | Self::FundingInsufficientBalance
+                | Self::AccountInsufficientBalance
+        )
+    }
+
+    /// Check if this is an authentication failure (key, passphrase, signature, IP).
+    #[must_use]
+    pub fn is_auth_failure(&self) -> bool {
+        matches!(
+            *self.canonical(),
+            Self::ApiFrozen
+                | Self::ApiKeyEnvironmentMismatch
+                | Self::AccessKeyRequired
+                | Self::PassphraseRequired
+                | Self::PassphraseIncorrect
+                | Self::SignRequired
+                | Self::InvalidIp
+                | Self::InvalidAccessKey
+                | Self::InvalidSign
+                | Self::InvalidAuthorization
+                | Self::WsInvalidApiKey
+                | Self::WsInvalidSign
+                | Self::WsLoginFailed
+                | Self::WsWrongPassphrase
+                | Self::WsApiKeyNotFound
+        )
+    }
+
+    /// Check if the request was rejected because the local clock is off.
+    #[must_use]
+    pub fn is_clock_skew(&self) -> bool {
+        matches!(
+            *self.canonical(),
+            Self::TimestampExpired
+                | Self::InvalidTimestamp
+                | Self::WsInvalidTimestamp
+                | Self::WsTimestampExpired
+        )
+    }
+}
+
+impl fmt::Display for OkxErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for OkxErrorCode {
+    fn from(code: &str) -> Self {
+        Self::from_code(code)
+    }
+}
+
+impl From<String> for OkxErrorCode {
+    fn from(code: String) -> Self {
+        Self::from_code(&code)
+    }
+}
+
+impl PartialEq for OkxErrorCode {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Hash for OkxErrorCode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialEq<str> for OkxErrorCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for OkxErrorCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_and_unknown_codes_are_kept() {
+        let code = OkxErrorCode::from_code("51008");
+        assert_eq!(code, OkxErrorCode::InsufficientBalance);
+        assert_eq!(code.as_str(), "51008");
+        assert_eq!(code, "51008");
+
+        let other = OkxErrorCode::from("51999");
+        assert_eq!(other, OkxErrorCode::Other("51999".into()));
+        assert_eq!(other.to_string(), "51999");
+        assert_eq!(other.family(), OkxErrorFamily::Trade);
+
+        // 手动构造的 Other 与目录中的变体按错误码比较
+        let manual = OkxErrorCode::Other("51008".into());
+        assert_eq!(manual, OkxErrorCode::InsufficientBalance);
+        let set: std::collections::HashSet<_> = [manual, OkxErrorCode::InsufficientBalance].into();
+        assert_eq!(set.len(), 1);
+        assert!(OkxErrorCode::Other("51008".into()).is_insufficient_balance());
+        assert!(OkxErrorCode::Other("50011".into()).is_retryable());
+        assert!(OkxErrorCode::Other("50113".into()).is_auth_failure());
+        assert!(OkxErrorCode::Other("50102".into()).is_clock_skew());
+    }
+
+    #[test]
+    fn family_follows_numeric_prefix() {
+        assert_eq!(OkxErrorCode::SystemBusy.family(), OkxErrorFamily::General);
+        assert_eq!(
+            OkxErrorCode::FundingInsufficientBalance.family(),
+            OkxErrorFamily::Funding
+        );
+        assert_eq!(
+            OkxErrorCode::LeverageTooHigh.family(),
+            OkxErrorFamily::Account
+        );
+        assert_eq!(
+            OkxErrorCode::WsServiceUpgrade.family(),
+            OkxErrorFamily::WebSocket
+        );
+        assert_eq!(OkxErrorCode::from("1").family(), OkxErrorFamily::Other);
+    }
+
+    #[test]
+    fn classification_helpers() {
+        assert!(OkxErrorCode::from("50011").is_retryable());
+        assert!(!OkxErrorCode::from("51000").is_retryable());
+        assert!(!OkxErrorCode::from("51149").is_retryable());
+        assert!(OkxErrorCode::from("51008").is_insufficient_balance());
+        assert!(OkxErrorCode::from("59200").is_insufficient_balance());
+        assert!(OkxErrorCode::from("50113").is_auth_failure());
+        assert!(OkxErrorCode::from("60009").is_auth_failure());
+        assert!(OkxErrorCode::from("50102").is_clock_skew());
+        assert!(OkxErrorCode::from("60006").is_clock_skew());
+        assert!(!OkxErrorCode::from("60006").is_auth_failure());
+    }
+}
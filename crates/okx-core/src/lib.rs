@@ -26,6 +26,8 @@
 mod config;
 mod credentials;
 mod error;
+mod error_code;
+mod retry;
 mod time_provider;
 
 pub mod signer;
@@ -36,8 +38,14 @@ pub use time_provider::{LocalTimeProvider, OffsetTimeProvider, TimestampProvider
 pub use config::Config;
 pub use credentials::Credentials;
 pub use error::{OkxError, Result};
+pub use error_code::{OkxErrorCode, OkxErrorFamily};
+pub use retry::RetryPolicy;
 pub use signer::Signer;
 
+/// Fixed-point decimal used by the `*_decimal()` accessors (feature `decimal`).
+#[cfg(feature = "decimal")]
+pub use rust_decimal::Decimal;
+
 /// OKX API version
 pub const API_VERSION: &str = "v5";
 
@@ -53,8 +61,16 @@ pub const WS_PUBLIC_URL: &str = "wss://ws.okx.com:8443/ws/v5/public";
 /// WebSocket private URL (production)
 pub const WS_PRIVATE_URL: &str = "wss://ws.okx.com:8443/ws/v5/private";
 
+/// WebSocket business URL (production)
+///
+/// Serves candles, algo/grid, recurring-buy, RFQ and block-trade channels.
+pub const WS_BUSINESS_URL: &str = "wss://ws.okx.com:8443/ws/v5/business";
+
 /// WebSocket public URL (simulated/demo)
 pub const WS_PUBLIC_URL_SIMULATED: &str = "wss://wspap.okx.com:8443/ws/v5/public?brokerId=9999";
 
 /// WebSocket private URL (simulated/demo)
 pub const WS_PRIVATE_URL_SIMULATED: &str = "wss://wspap.okx.com:8443/ws/v5/private?brokerId=9999";
+
+/// WebSocket business URL (simulated/demo)
+pub const WS_BUSINESS_URL_SIMULATED: &str = "wss://wspap.okx.com:8443/ws/v5/business?brokerId=9999";
@@ -0,0 +1,160 @@
+//! Retry policy for transient REST failures.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// Retry policy for transient REST failures.
+///
+/// Connection errors, HTTP 5xx and HTTP 429 responses are retried up to
+/// `max_attempts` times (including the first attempt) with exponential backoff.
+/// A `Retry-After` / rate limit reset header on the response takes precedence
+/// over the computed backoff; both are capped at `max_delay`.
+///
+/// Idempotent GET requests are always eligible. POST requests are only retried
+/// when every order in the body carries a `clOrdId`, so a repeated request cannot
+/// place a duplicate order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one
+    max_attempts: u32,
+    /// Backoff before the first retry
+    base_delay: Duration,
+    /// Upper bound of any retry delay, server-provided ones included
+    max_delay: Duration,
+    /// Whether to randomize the backoff
+    jitter: bool,
+}
+
+impl RetryPolicy {
+    /// Create a policy with the given maximum number of attempts.
+    ///
+    /// Defaults to a 200ms base delay, a 5s cap and jitter enabled.
+    #[must_use]
+    pub const fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            base_delay: Duration::from_millis(200),
+            max_delay: Duration::from_secs(5),
+            jitter: true,
+        }
+    }
+
+    /// Set the backoff before the first retry.
+    #[must_use]
+    pub const fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the upper bound of the backoff and of server-provided delays.
+    #[must_use]
+    pub const fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Enable or disable jitter.
+    ///
+    /// With jitter enabled the backoff is drawn uniformly from `[delay / 2, delay]`.
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Get the maximum number of attempts (at least 1).
+    #[must_use]
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts.max(1)
+    }
+
+    /// Get the backoff before the first retry.
+    #[must_use]
+    pub const fn base_delay(&self) -> Duration {
+        self.base_delay
+    }
+
+    /// Get the upper bound of the backoff and of server-provided delays.
+    #[must_use]
+    pub const fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Check if jitter is enabled.
+    #[must_use]
+    pub const fn jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Compute the backoff before the given retry (1-based).
+    #[must_use]
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Random value in `[0, 1)`, seeded from the std hasher keys.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+    // 取高 32 位构造 [0, 1) 浮点数
+    let high = u32::try_from(hasher.finish() >> 32).unwrap_or_default();
+    f64::from(high) / (f64::from(u32::MAX) + 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        let policy = RetryPolicy::new(5)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(350))
+            .with_jitter(false);
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_and_full_delay() {
+        let policy = RetryPolicy::new(3).with_base_delay(Duration::from_millis(400));
+
+        for _ in 0..100 {
+            let delay = policy.backoff(1);
+            assert!(delay >= Duration::from_millis(200));
+            assert!(delay <= Duration::from_millis(400));
+        }
+    }
+
+    #[test]
+    fn max_attempts_is_at_least_one() {
+        assert_eq!(RetryPolicy::new(0).max_attempts(), 1);
+        assert_eq!(RetryPolicy::default().max_attempts(), 3);
+    }
+}
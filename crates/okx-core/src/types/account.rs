@@ -4,9 +4,13 @@
 //! - GET /api/v5/account/balance
 //! - GET /api/v5/account/positions
 //! - GET /api/v5/account/config
+//! - GET /api/v5/account/greeks
+//! - WS adl-warning channel
 
 use serde::{Deserialize, Serialize};
 
+use super::MgnMode;
+
 /// Account balance information.
 ///
 /// Source: GET /api/v5/account/balance response
@@ -164,7 +168,7 @@ pub struct Position {
     pub inst_type: String,
     /// Margin mode (cross/isolated)
     #[serde(default)]
-    pub mgn_mode: String,
+    pub mgn_mode: MgnMode,
     /// Position ID
     pub pos_id: String,
     /// Position side (long/short/net)
@@ -423,3 +427,82 @@ pub struct AccountConfig {
     #[serde(default)]
     pub spot_borrow_auto_repay: bool,
 }
+
+/// Account greeks of one currency.
+///
+/// Source: GET /api/v5/account/greeks response and `account-greeks` WS channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountGreeks {
+    /// Currency
+    pub ccy: String,
+    /// Delta in USD (Black-Scholes)
+    #[serde(rename = "deltaBS", default)]
+    pub delta_bs: String,
+    /// Delta in coin (price adjusted)
+    #[serde(rename = "deltaPA", default)]
+    pub delta_pa: String,
+    /// Gamma in USD (Black-Scholes)
+    #[serde(rename = "gammaBS", default)]
+    pub gamma_bs: String,
+    /// Gamma in coin (price adjusted)
+    #[serde(rename = "gammaPA", default)]
+    pub gamma_pa: String,
+    /// Theta in USD (Black-Scholes)
+    #[serde(rename = "thetaBS", default)]
+    pub theta_bs: String,
+    /// Theta in coin (price adjusted)
+    #[serde(rename = "thetaPA", default)]
+    pub theta_pa: String,
+    /// Vega in USD (Black-Scholes)
+    #[serde(rename = "vegaBS", default)]
+    pub vega_bs: String,
+    /// Vega in coin (price adjusted)
+    #[serde(rename = "vegaPA", default)]
+    pub vega_pa: String,
+    /// Update time (Unix timestamp in milliseconds)
+    #[serde(default)]
+    pub ts: String,
+}
+
+/// Auto-deleveraging warning of an insurance fund.
+///
+/// Source: `adl-warning` WS channel push
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdlWarning {
+    /// Instrument type
+    pub inst_type: String,
+    /// Instrument family
+    #[serde(default)]
+    pub inst_family: String,
+    /// ADL state: `normal`, `warning`, `adl`
+    pub state: String,
+    /// Insurance fund balance
+    #[serde(default)]
+    pub bal: String,
+    /// Maximum insurance fund balance in the past eight hours
+    #[serde(default)]
+    pub max_bal: String,
+    /// Timestamp of the maximum balance
+    #[serde(default)]
+    pub max_bal_ts: String,
+    /// Balance that triggers the `warning` state
+    #[serde(default)]
+    pub adl_rec_bal: String,
+    /// Balance that triggers ADL
+    #[serde(default)]
+    pub adl_bal: String,
+    /// Real-time insurance fund decline rate
+    #[serde(default)]
+    pub dec_rate: String,
+    /// Decline rate that triggers ADL
+    #[serde(default)]
+    pub adl_rate: String,
+    /// Decline rate that triggers the `warning` state
+    #[serde(default)]
+    pub adl_rec_rate: String,
+    /// Push time (Unix timestamp in milliseconds)
+    #[serde(default)]
+    pub ts: String,
+}
@@ -2,7 +2,9 @@
 //!
 //! Source: OKX API v5 documentation
 
-use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Standard API response wrapper.
 ///
@@ -28,189 +30,211 @@ impl<T> ApiResponse<T> {
     }
 }
 
-/// Instrument type.
+/// 生成带 `Unknown(String)` 兜底的 API 字符串枚举。
 ///
-/// Source: OKX API v5 - instType parameter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum InstType {
-    /// Spot trading
-    Spot,
-    /// Margin trading
-    Margin,
-    /// Perpetual swap
-    Swap,
-    /// Futures
-    Futures,
-    /// Options
-    Option,
-}
+/// 未收录的取值保留在 `Unknown` 中，新上线的枚举值不会导致反序列化失败。
+macro_rules! api_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$vdoc:meta])* $variant:ident = $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$vdoc])* $variant,)+
+            /// Value not known to this version of the SDK, kept verbatim
+            Unknown(String),
+        }
 
-impl InstType {
-    /// Convert to API string representation.
-    #[must_use]
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Spot => "SPOT",
-            Self::Margin => "MARGIN",
-            Self::Swap => "SWAP",
-            Self::Futures => "FUTURES",
-            Self::Option => "OPTION",
+        impl $name {
+            /// Convert to API string representation.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $value,)+
+                    Self::Unknown(value) => value,
+                }
+            }
+
+            /// Check if the value is not known to this version of the SDK.
+            #[must_use]
+            pub fn is_unknown(&self) -> bool {
+                matches!(self, Self::Unknown(_))
+            }
         }
-    }
-}
 
-/// Trading mode.
-///
-/// Source: OKX API v5 - tdMode parameter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum TdMode {
-    /// Cash (spot)
-    Cash,
-    /// Cross margin
-    Cross,
-    /// Isolated margin
-    Isolated,
-}
+        impl Default for $name {
+            /// Empty value, as returned by OKX for absent fields.
+            fn default() -> Self {
+                Self::Unknown(String::new())
+            }
+        }
 
-impl TdMode {
-    /// Convert to API string representation.
-    #[must_use]
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Cash => "cash",
-            Self::Cross => "cross",
-            Self::Isolated => "isolated",
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
         }
-    }
-}
 
-/// Order side.
-///
-/// Source: OKX API v5 - side parameter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Side {
-    /// Buy order
-    Buy,
-    /// Sell order
-    Sell,
-}
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                match value {
+                    $($value => Self::$variant,)+
+                    other => Self::Unknown(other.to_string()),
+                }
+            }
+        }
 
-impl Side {
-    /// Convert to API string representation.
-    #[must_use]
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Buy => "buy",
-            Self::Sell => "sell",
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self::from(value.as_str())
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
         }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+                Ok(Self::from(String::deserialize(deserializer)?))
+            }
+        }
+    };
+}
+
+api_enum! {
+    /// Instrument type.
+    ///
+    /// Source: OKX API v5 - instType parameter
+    pub enum InstType {
+        /// Spot trading
+        Spot = "SPOT",
+        /// Margin trading
+        Margin = "MARGIN",
+        /// Perpetual swap
+        Swap = "SWAP",
+        /// Futures
+        Futures = "FUTURES",
+        /// Options
+        Option = "OPTION",
     }
 }
 
-/// Position side for derivatives.
-///
-/// Source: OKX API v5 - posSide parameter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum PosSide {
-    /// Long position
-    Long,
-    /// Short position
-    Short,
-    /// Net position (for one-way mode)
-    Net,
+api_enum! {
+    /// Trading mode.
+    ///
+    /// Source: OKX API v5 - tdMode parameter
+    pub enum TdMode {
+        /// Cash (spot)
+        Cash = "cash",
+        /// Cross margin
+        Cross = "cross",
+        /// Isolated margin
+        Isolated = "isolated",
+        /// Spot isolated (lead trading only)
+        SpotIsolated = "spot_isolated",
+    }
 }
 
-impl PosSide {
-    /// Convert to API string representation.
-    #[must_use]
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Long => "long",
-            Self::Short => "short",
-            Self::Net => "net",
-        }
+api_enum! {
+    /// Order side.
+    ///
+    /// Source: OKX API v5 - side parameter
+    pub enum Side {
+        /// Buy order
+        Buy = "buy",
+        /// Sell order
+        Sell = "sell",
     }
 }
 
-/// Order type.
-///
-/// Source: OKX API v5 - ordType parameter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum OrdType {
-    /// Market order
-    Market,
-    /// Limit order
-    Limit,
-    /// Post-only order
-    #[serde(rename = "post_only")]
-    PostOnly,
-    /// Fill or kill
-    Fok,
-    /// Immediate or cancel
-    Ioc,
-    /// Optimal limit order (market order with price protection)
-    #[serde(rename = "optimal_limit_ioc")]
-    OptimalLimitIoc,
-    /// Market-maker protection (MMP)
-    Mmp,
-    /// Market-maker protection and target
-    #[serde(rename = "mmp_and_target")]
-    MmpAndTarget,
+api_enum! {
+    /// Position side for derivatives.
+    ///
+    /// Source: OKX API v5 - posSide parameter
+    pub enum PosSide {
+        /// Long position
+        Long = "long",
+        /// Short position
+        Short = "short",
+        /// Net position (for one-way mode)
+        Net = "net",
+    }
 }
 
-impl OrdType {
-    /// Convert to API string representation.
-    #[must_use]
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Market => "market",
-            Self::Limit => "limit",
-            Self::PostOnly => "post_only",
-            Self::Fok => "fok",
-            Self::Ioc => "ioc",
-            Self::OptimalLimitIoc => "optimal_limit_ioc",
-            Self::Mmp => "mmp",
-            Self::MmpAndTarget => "mmp_and_target",
-        }
+api_enum! {
+    /// Order type.
+    ///
+    /// Source: OKX API v5 - ordType parameter
+    pub enum OrdType {
+        /// Market order
+        Market = "market",
+        /// Limit order
+        Limit = "limit",
+        /// Post-only order
+        PostOnly = "post_only",
+        /// Fill or kill
+        Fok = "fok",
+        /// Immediate or cancel
+        Ioc = "ioc",
+        /// Optimal limit order (market order with price protection)
+        OptimalLimitIoc = "optimal_limit_ioc",
+        /// Market-maker protection (MMP)
+        Mmp = "mmp",
+        /// Market-maker protection and target
+        MmpAndTarget = "mmp_and_target",
+        /// Fill or kill order for options, priced in implied volatility
+        OpFok = "op_fok",
     }
 }
 
-/// Order state.
-///
-/// Source: OKX API v5 - state parameter in order responses
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum OrdState {
-    /// Order created but not yet in order book
-    Created,
-    /// Order is live/open
-    Live,
-    /// Order partially filled
-    #[serde(rename = "partially_filled")]
-    PartiallyFilled,
-    /// Order canceled
-    Canceled,
-    /// Order filled completely
-    Filled,
-    /// Market-maker protection canceled
-    #[serde(rename = "mmp_canceled")]
-    MmpCanceled,
+api_enum! {
+    /// Order state.
+    ///
+    /// Source: OKX API v5 - state parameter in order responses
+    pub enum OrdState {
+        /// Order created but not yet in order book
+        Created = "created",
+        /// Order is live/open
+        Live = "live",
+        /// Order partially filled
+        PartiallyFilled = "partially_filled",
+        /// Order canceled
+        Canceled = "canceled",
+        /// Order filled completely
+        Filled = "filled",
+        /// Market-maker protection canceled
+        MmpCanceled = "mmp_canceled",
+    }
 }
 
-/// Margin mode.
-///
-/// Source: OKX API v5 - mgnMode parameter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum MgnMode {
-    /// Cross margin
-    Cross,
-    /// Isolated margin
-    Isolated,
+api_enum! {
+    /// Margin mode.
+    ///
+    /// Source: OKX API v5 - mgnMode parameter
+    pub enum MgnMode {
+        /// Cross margin
+        Cross = "cross",
+        /// Isolated margin
+        Isolated = "isolated",
+    }
 }
 
 /// Contract type.
@@ -227,8 +251,11 @@ pub enum CtType {
 
 /// Candlestick/K-line bar interval.
 ///
+/// Bars of 6H and longer open at UTC+8 (Hong Kong time); the `*Utc`
+/// variants open at UTC+0.
+///
 /// Source: OKX API v5 - bar parameter
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Bar {
     /// 1 minute
     #[serde(rename = "1m")]
@@ -263,6 +290,12 @@ pub enum Bar {
     /// 1 day
     #[serde(rename = "1D")]
     D1,
+    /// 2 days
+    #[serde(rename = "2D")]
+    D2,
+    /// 3 days
+    #[serde(rename = "3D")]
+    D3,
     /// 1 week
     #[serde(rename = "1W")]
     W1,
@@ -272,9 +305,61 @@ pub enum Bar {
     /// 3 months
     #[serde(rename = "3M")]
     Mo3,
+    /// 6 hours (UTC)
+    #[serde(rename = "6Hutc")]
+    H6Utc,
+    /// 12 hours (UTC)
+    #[serde(rename = "12Hutc")]
+    H12Utc,
+    /// 1 day (UTC)
+    #[serde(rename = "1Dutc")]
+    D1Utc,
+    /// 2 days (UTC)
+    #[serde(rename = "2Dutc")]
+    D2Utc,
+    /// 3 days (UTC)
+    #[serde(rename = "3Dutc")]
+    D3Utc,
+    /// 1 week (UTC)
+    #[serde(rename = "1Wutc")]
+    W1Utc,
+    /// 1 month (UTC)
+    #[serde(rename = "1Mutc")]
+    Mo1Utc,
+    /// 3 months (UTC)
+    #[serde(rename = "3Mutc")]
+    Mo3Utc,
 }
 
 impl Bar {
+    /// All bar intervals, shortest first within each time zone.
+    pub const ALL: [Self; 24] = [
+        Self::M1,
+        Self::M3,
+        Self::M5,
+        Self::M15,
+        Self::M30,
+        Self::H1,
+        Self::H2,
+        Self::H4,
+        Self::H6,
+        Self::H12,
+        Self::D1,
+        Self::D2,
+        Self::D3,
+        Self::W1,
+        Self::Mo1,
+        Self::Mo3,
+        Self::H6Utc,
+        Self::H12Utc,
+        Self::D1Utc,
+        Self::D2Utc,
+        Self::D3Utc,
+        Self::W1Utc,
+        Self::Mo1Utc,
+        Self::Mo3Utc,
+    ];
+
     /// Convert to API string representation.
     #[must_use]
     pub fn as_str(&self) -> &'static str {
@@ -290,9 +375,25 @@ impl Bar {
             Self::H6 => "6H",
             Self::H12 => "12H",
             Self::D1 => "1D",
+            Self::D2 => "2D",
+            Self::D3 => "3D",
             Self::W1 => "1W",
             Self::Mo1 => "1M",
             Self::Mo3 => "3M",
+            Self::H6Utc => "6Hutc",
+            Self::H12Utc => "12Hutc",
+            Self::D1Utc => "1Dutc",
+            Self::D2Utc => "2Dutc",
+            Self::D3Utc => "3Dutc",
+            Self::W1Utc => "1Wutc",
+            Self::Mo1Utc => "1Mutc",
+            Self::Mo3Utc => "3Mutc",
         }
     }
+
+    /// Parse the API string representation (e.g. `"1H"`, `"1Dutc"`).
+    #[must_use]
+    pub fn from_api_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|bar| bar.as_str() == s)
+    }
 }
@@ -0,0 +1,212 @@
+//! `rust_decimal` accessors for numeric string fields (feature `decimal`).
+//!
+//! OKX encodes every price/size as a string and uses an empty string for
+//! "absent". Accessors return `None` for empty or unparsable fields; setters
+//! write `Decimal::to_string()`, which keeps the exact scale (e.g. `0.010`).
+
+use rust_decimal::Decimal;
+
+use super::{Balance, BalanceDetail, Fill, Instrument, Order, PlaceOrderRequest, Position, Ticker};
+
+/// Parse an OKX numeric string; empty strings mean absent.
+#[must_use]
+pub fn parse_decimal(value: &str) -> Option<Decimal> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    value
+        .parse()
+        .or_else(|_| Decimal::from_scientific(value))
+        .ok()
+}
+
+/// String-like field that can hold an OKX numeric value.
+trait NumericField {
+    fn as_numeric_str(&self) -> &str;
+}
+
+impl NumericField for String {
+    fn as_numeric_str(&self) -> &str {
+        self
+    }
+}
+
+impl NumericField for Option<String> {
+    fn as_numeric_str(&self) -> &str {
+        self.as_deref().unwrap_or_default()
+    }
+}
+
+/// 为数值字符串字段生成 `<field>_decimal()` 访问器。
+macro_rules! decimal_getters {
+    ($ty:ty { $($field:ident => $getter:ident),+ $(,)? }) => {
+        impl $ty {
+            $(
+                #[doc = concat!("`", stringify!($field), "` as a `Decimal` (`None` if empty).")]
+                #[must_use]
+                pub fn $getter(&self) -> Option<Decimal> {
+                    parse_decimal(NumericField::as_numeric_str(&self.$field))
+                }
+            )+
+        }
+    };
+}
+
+decimal_getters!(Ticker {
+    last => last_decimal,
+    last_sz => last_sz_decimal,
+    ask_px => ask_px_decimal,
+    ask_sz => ask_sz_decimal,
+    bid_px => bid_px_decimal,
+    bid_sz => bid_sz_decimal,
+    open_24h => open_24h_decimal,
+    high_24h => high_24h_decimal,
+    low_24h => low_24h_decimal,
+    vol_ccy_24h => vol_ccy_24h_decimal,
+    vol_24h => vol_24h_decimal,
+    sod_utc_0 => sod_utc_0_decimal,
+    sod_utc_8 => sod_utc_8_decimal,
+});
+
+decimal_getters!(Order {
+    px => px_decimal,
+    sz => sz_decimal,
+    pnl => pnl_decimal,
+    acc_fill_sz => acc_fill_sz_decimal,
+    fill_px => fill_px_decimal,
+    fill_sz => fill_sz_decimal,
+    avg_px => avg_px_decimal,
+    lever => lever_decimal,
+    tp_trigger_px => tp_trigger_px_decimal,
+    tp_ord_px => tp_ord_px_decimal,
+    sl_trigger_px => sl_trigger_px_decimal,
+    sl_ord_px => sl_ord_px_decimal,
+    fee => fee_decimal,
+    rebate => rebate_decimal,
+});
+
+decimal_getters!(Fill {
+    fill_px => fill_px_decimal,
+    fill_sz => fill_sz_decimal,
+    fill_pnl => fill_pnl_decimal,
+    fee => fee_decimal,
+    fill_idx_px => fill_idx_px_decimal,
+    fill_mark_px => fill_mark_px_decimal,
+    fill_mark_vol => fill_mark_vol_decimal,
+    fill_fwd_px => fill_fwd_px_decimal,
+});
+
+decimal_getters!(Position {
+    pos => pos_decimal,
+    avail_pos => avail_pos_decimal,
+    avg_px => avg_px_decimal,
+    upl => upl_decimal,
+    upl_ratio => upl_ratio_decimal,
+    lever => lever_decimal,
+    liq_px => liq_px_decimal,
+    mark_px => mark_px_decimal,
+    imr => imr_decimal,
+    margin => margin_decimal,
+    mgn_ratio => mgn_ratio_decimal,
+    mmr => mmr_decimal,
+    liab => liab_decimal,
+    interest => interest_decimal,
+    notional_usd => notional_usd_decimal,
+    last => last_decimal,
+    idx_px => idx_px_decimal,
+    be_px => be_px_decimal,
+    realized_pnl => realized_pnl_decimal,
+    pnl => pnl_decimal,
+    fee => fee_decimal,
+    funding_fee => funding_fee_decimal,
+});
+
+decimal_getters!(Balance {
+    total_eq => total_eq_decimal,
+    iso_eq => iso_eq_decimal,
+    adj_eq => adj_eq_decimal,
+    ord_froz => ord_froz_decimal,
+    imr => imr_decimal,
+    mmr => mmr_decimal,
+    mgn_ratio => mgn_ratio_decimal,
+    notional_usd => notional_usd_decimal,
+});
+
+decimal_getters!(BalanceDetail {
+    eq => eq_decimal,
+    cash_bal => cash_bal_decimal,
+    avail_eq => avail_eq_decimal,
+    avail_bal => avail_bal_decimal,
+    frozen_bal => frozen_bal_decimal,
+    ord_frozen => ord_frozen_decimal,
+    liab => liab_decimal,
+    upl => upl_decimal,
+    eq_usd => eq_usd_decimal,
+    max_loan => max_loan_decimal,
+});
+
+decimal_getters!(Instrument {
+    ct_val => ct_val_decimal,
+    ct_mult => ct_mult_decimal,
+    stk => stk_decimal,
+    lever => lever_decimal,
+    tick_sz => tick_sz_decimal,
+    lot_sz => lot_sz_decimal,
+    min_sz => min_sz_decimal,
+    max_lmt_sz => max_lmt_sz_decimal,
+    max_mkt_sz => max_mkt_sz_decimal,
+});
+
+decimal_getters!(PlaceOrderRequest {
+    sz => sz_decimal,
+    px => px_decimal,
+    tp_trigger_px => tp_trigger_px_decimal,
+    tp_ord_px => tp_ord_px_decimal,
+    sl_trigger_px => sl_trigger_px_decimal,
+    sl_ord_px => sl_ord_px_decimal,
+});
+
+impl PlaceOrderRequest {
+    /// Set the order size from a `Decimal`, keeping its exact scale.
+    #[must_use]
+    pub fn with_sz_decimal(mut self, sz: Decimal) -> Self {
+        self.sz = sz.to_string();
+        self
+    }
+
+    /// Set the order price from a `Decimal`, keeping its exact scale.
+    #[must_use]
+    pub fn with_px_decimal(mut self, px: Decimal) -> Self {
+        self.px = Some(px.to_string());
+        self
+    }
+
+    /// Set the take-profit trigger price from a `Decimal`.
+    #[must_use]
+    pub fn with_tp_trigger_px_decimal(mut self, px: Decimal) -> Self {
+        self.tp_trigger_px = Some(px.to_string());
+        self
+    }
+
+    /// Set the take-profit order price from a `Decimal`.
+    #[must_use]
+    pub fn with_tp_ord_px_decimal(mut self, px: Decimal) -> Self {
+        self.tp_ord_px = Some(px.to_string());
+        self
+    }
+
+    /// Set the stop-loss trigger price from a `Decimal`.
+    #[must_use]
+    pub fn with_sl_trigger_px_decimal(mut self, px: Decimal) -> Self {
+        self.sl_trigger_px = Some(px.to_string());
+        self
+    }
+
+    /// Set the stop-loss order price from a `Decimal`.
+    #[must_use]
+    pub fn with_sl_ord_px_decimal(mut self, px: Decimal) -> Self {
+        self.sl_ord_px = Some(px.to_string());
+        self
+    }
+}
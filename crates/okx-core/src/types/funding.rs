@@ -7,6 +7,7 @@
 //! - GET /api/v5/asset/deposit-address
 //! - GET /api/v5/asset/deposit-history
 //! - GET /api/v5/asset/withdrawal-history
+//! - WS deposit-info / withdrawal-info channels
 
 use serde::{Deserialize, Serialize};
 
@@ -89,6 +90,12 @@ pub struct DepositRecord {
     /// Actual deposit amount after fee
     #[serde(default)]
     pub actual_dep_blk_confirm: String,
+    /// User ID (`deposit-info` WS push only)
+    #[serde(default)]
+    pub uid: String,
+    /// Sub-account name, empty for the master account (`deposit-info` WS push only)
+    #[serde(default)]
+    pub sub_acct: String,
 }
 
 /// Withdrawal history record.
@@ -122,6 +129,12 @@ pub struct WithdrawalRecord {
     pub state: String,
     /// Withdrawal time (Unix timestamp in milliseconds)
     pub ts: String,
+    /// User ID (`withdrawal-info` WS push only)
+    #[serde(default)]
+    pub uid: String,
+    /// Sub-account name, empty for the master account (`withdrawal-info` WS push only)
+    #[serde(default)]
+    pub sub_acct: String,
 }
 
 /// Funds transfer request.
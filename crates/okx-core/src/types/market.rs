@@ -159,10 +159,24 @@ impl Candle {
     /// Parse a candle from the raw API response array.
     ///
     /// API returns: [ts, o, h, l, c, vol, volCcy, volCcyQuote, confirm]
+    ///
+    /// Mark price and index candles return [ts, o, h, l, c, confirm]; their
+    /// volume fields are left empty.
     #[must_use]
     pub fn from_array(arr: &[String]) -> Option<Self> {
-        if arr.len() >= 9 {
-            Some(Self {
+        match arr.len() {
+            6 => Some(Self {
+                ts: arr[0].clone(),
+                open: arr[1].clone(),
+                high: arr[2].clone(),
+                low: arr[3].clone(),
+                close: arr[4].clone(),
+                vol: String::new(),
+                vol_ccy: String::new(),
+                vol_ccy_quote: String::new(),
+                confirm: arr[5].clone(),
+            }),
+            n if n >= 9 => Some(Self {
                 ts: arr[0].clone(),
                 open: arr[1].clone(),
                 high: arr[2].clone(),
@@ -172,9 +186,8 @@ impl Candle {
                 vol_ccy: arr[6].clone(),
                 vol_ccy_quote: arr[7].clone(),
                 confirm: arr[8].clone(),
-            })
-        } else {
-            None
+            }),
+            _ => None,
         }
     }
 
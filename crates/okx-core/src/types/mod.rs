@@ -6,13 +6,18 @@
 mod account;
 mod common;
 mod convert;
+#[cfg(feature = "decimal")]
+mod decimal;
 mod funding;
 mod market;
+mod order_builder;
 mod trade;
 
 pub use account::*;
 pub use common::*;
 pub use convert::*;
+#[cfg(feature = "decimal")]
+pub use decimal::parse_decimal;
 pub use funding::*;
 pub use market::*;
 pub use trade::*;
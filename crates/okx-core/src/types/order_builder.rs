@@ -0,0 +1,520 @@
+//! Fluent constructors and pre-flight validation for [`PlaceOrderRequest`].
+//!
+//! Source: OKX API v5 - POST /api/v5/trade/order
+//! - <https://www.okx.com/docs-v5/en/#order-book-trading-trade-post-place-order>
+
+use std::cmp::Ordering;
+
+use super::{
+    AccountConfig, AttachAlgoOrdRequest, Instrument, OrdType, PlaceOrderRequest, PosSide, Side,
+    TdMode,
+};
+use crate::{OkxError, Result};
+
+impl PlaceOrderRequest {
+    /// Create an order with the given type; trade mode defaults to `cash`.
+    #[must_use]
+    pub fn new(
+        inst_id: impl Into<String>,
+        side: Side,
+        ord_type: OrdType,
+        sz: impl Into<String>,
+    ) -> Self {
+        Self {
+            inst_id: inst_id.into(),
+            td_mode: TdMode::Cash,
+            side,
+            ord_type,
+            sz: sz.into(),
+            ccy: None,
+            cl_ord_id: None,
+            tag: None,
+            pos_side: None,
+            px: None,
+            reduce_only: None,
+            tgt_ccy: None,
+            tp_trigger_px: None,
+            tp_ord_px: None,
+            sl_trigger_px: None,
+            sl_ord_px: None,
+            tp_trigger_px_type: None,
+            sl_trigger_px_type: None,
+            quick_mgn_type: None,
+            stp_id: None,
+            stp_mode: None,
+            attach_algo_ords: None,
+        }
+    }
+
+    /// Create a limit order.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// use okx_core::types::{PlaceOrderRequest, Side};
+    ///
+    /// let request = PlaceOrderRequest::limit("BTC-USDT-SWAP", Side::Buy, "43000", "1")
+    ///     .cross()
+    ///     .post_only()
+    ///     .with_tp_sl("45000", "41000");
+    /// assert!(request.validate().is_ok());
+    /// ```
+    #[must_use]
+    pub fn limit(
+        inst_id: impl Into<String>,
+        side: Side,
+        px: impl Into<String>,
+        sz: impl Into<String>,
+    ) -> Self {
+        Self::new(inst_id, side, OrdType::Limit, sz).with_px(px)
+    }
+
+    /// Create a market order.
+    #[must_use]
+    pub fn market(inst_id: impl Into<String>, side: Side, sz: impl Into<String>) -> Self {
+        Self::new(inst_id, side, OrdType::Market, sz)
+    }
+
+    /// Use cash (non-margin) trade mode.
+    #[must_use]
+    pub fn cash(mut self) -> Self {
+        self.td_mode = TdMode::Cash;
+        self
+    }
+
+    /// Use cross margin trade mode.
+    #[must_use]
+    pub fn cross(mut self) -> Self {
+        self.td_mode = TdMode::Cross;
+        self
+    }
+
+    /// Use isolated margin trade mode.
+    #[must_use]
+    pub fn isolated(mut self) -> Self {
+        self.td_mode = TdMode::Isolated;
+        self
+    }
+
+    /// Make the order post-only (maker only).
+    #[must_use]
+    pub fn post_only(mut self) -> Self {
+        self.ord_type = OrdType::PostOnly;
+        self
+    }
+
+    /// Make the order immediate-or-cancel.
+    #[must_use]
+    pub fn ioc(mut self) -> Self {
+        self.ord_type = OrdType::Ioc;
+        self
+    }
+
+    /// Make the order fill-or-kill.
+    #[must_use]
+    pub fn fok(mut self) -> Self {
+        self.ord_type = OrdType::Fok;
+        self
+    }
+
+    /// Only reduce an existing position.
+    #[must_use]
+    pub fn reduce_only(mut self) -> Self {
+        self.reduce_only = Some(true);
+        self
+    }
+
+    /// Set the order price.
+    #[must_use]
+    pub fn with_px(mut self, px: impl Into<String>) -> Self {
+        self.px = Some(px.into());
+        self
+    }
+
+    /// Set the position side (long/short mode only).
+    #[must_use]
+    pub fn with_pos_side(mut self, pos_side: PosSide) -> Self {
+        self.pos_side = Some(pos_side);
+        self
+    }
+
+    /// Set the client order ID.
+    #[must_use]
+    pub fn with_cl_ord_id(mut self, cl_ord_id: impl Into<String>) -> Self {
+        self.cl_ord_id = Some(cl_ord_id.into());
+        self
+    }
+
+    /// Set the order tag.
+    #[must_use]
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Set the margin currency.
+    #[must_use]
+    pub fn with_ccy(mut self, ccy: impl Into<String>) -> Self {
+        self.ccy = Some(ccy.into());
+        self
+    }
+
+    /// Set the size unit for SPOT market orders (`base_ccy` or `quote_ccy`).
+    #[must_use]
+    pub fn with_tgt_ccy(mut self, tgt_ccy: impl Into<String>) -> Self {
+        self.tgt_ccy = Some(tgt_ccy.into());
+        self
+    }
+
+    /// Attach take-profit and stop-loss orders executed at market price.
+    #[must_use]
+    pub fn with_tp_sl(
+        self,
+        tp_trigger_px: impl Into<String>,
+        sl_trigger_px: impl Into<String>,
+    ) -> Self {
+        self.with_take_profit(tp_trigger_px, "-1")
+            .with_stop_loss(sl_trigger_px, "-1")
+    }
+
+    /// Attach a take-profit order; `ord_px` of `-1` executes at market price.
+    #[must_use]
+    pub fn with_take_profit(
+        mut self,
+        trigger_px: impl Into<String>,
+        ord_px: impl Into<String>,
+    ) -> Self {
+        let algo = self.attached_algo();
+        algo.tp_trigger_px = Some(trigger_px.into());
+        algo.tp_ord_px = Some(ord_px.into());
+        self
+    }
+
+    /// Attach a stop-loss order; `ord_px` of `-1` executes at market price.
+    #[must_use]
+    pub fn with_stop_loss(
+        mut self,
+        trigger_px: impl Into<String>,
+        ord_px: impl Into<String>,
+    ) -> Self {
+        let algo = self.attached_algo();
+        algo.sl_trigger_px = Some(trigger_px.into());
+        algo.sl_ord_px = Some(ord_px.into());
+        self
+    }
+
+    /// 取第一个附带止盈止损单，不存在时新建。
+    fn attached_algo(&mut self) -> &mut AttachAlgoOrdRequest {
+        let algos = self.attach_algo_ords.get_or_insert_with(Vec::new);
+        if algos.is_empty() {
+            algos.push(AttachAlgoOrdRequest {
+                attach_algo_cl_ord_id: None,
+                tp_trigger_px: None,
+                tp_trigger_px_type: None,
+                tp_ord_px: None,
+                sl_trigger_px: None,
+                sl_trigger_px_type: None,
+                sl_ord_px: None,
+                sz: None,
+            });
+        }
+        &mut algos[0]
+    }
+
+    /// Check parameter combinations that OKX would reject.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OkxError::InvalidParameter`] when required fields are missing,
+    /// a market order carries `px`, a priced order lacks `px`, or a cash order
+    /// sets `pos_side`.
+    pub fn validate(&self) -> Result<()> {
+        if self.inst_id.is_empty() {
+            return Err(invalid("instId is required"));
+        }
+        if !parse_fixed(&self.sz).is_some_and(|sz| sz.mantissa > 0) {
+            return Err(invalid(format!(
+                "sz must be a positive number, got {:?}",
+                self.sz
+            )));
+        }
+
+        let has_px = self.px.as_deref().is_some_and(|px| !px.is_empty());
+        match self.ord_type {
+            OrdType::Market | OrdType::OptimalLimitIoc if has_px => {
+                return Err(invalid(format!(
+                    "px must not be set for {} orders",
+                    self.ord_type
+                )));
+            }
+            OrdType::Limit
+            | OrdType::PostOnly
+            | OrdType::Fok
+            | OrdType::Ioc
+            | OrdType::Mmp
+            | OrdType::MmpAndTarget
+            | OrdType::OpFok
+                if !has_px =>
+            {
+                return Err(invalid(format!(
+                    "px is required for {} orders",
+                    self.ord_type
+                )));
+            }
+            _ => {}
+        }
+
+        if self.td_mode == TdMode::Cash
+            && matches!(self.pos_side, Some(PosSide::Long | PosSide::Short))
+        {
+            return Err(invalid("posSide long/short is not allowed in cash mode"));
+        }
+
+        Ok(())
+    }
+
+    /// Check the position side against the account position mode.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OkxError::InvalidParameter`] when `pos_side` is long/short in
+    /// `net_mode`, or `net` in `long_short_mode`.
+    pub fn validate_pos_mode(&self, config: &AccountConfig) -> Result<()> {
+        match (config.pos_mode.as_str(), &self.pos_side) {
+            ("net_mode", Some(PosSide::Long | PosSide::Short)) => {
+                Err(invalid("posSide long/short is not allowed in net_mode"))
+            }
+            ("long_short_mode", Some(PosSide::Net)) => {
+                Err(invalid("posSide net is not allowed in long_short_mode"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate the order and adapt it to the instrument's trading rules.
+    ///
+    /// Prices (order, attached TP/SL) are rounded to the nearest `tick_sz` and the
+    /// size is rounded down to `lot_sz`, then checked against `min_sz` and
+    /// `max_lmt_sz` / `max_mkt_sz`. SPOT market orders sized in the quote currency
+    /// are not rounded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OkxError::InvalidParameter`] if [`validate`](Self::validate)
+    /// fails, the instrument does not match, a number cannot be parsed, or the
+    /// rounded size is out of range.
+    pub fn prepare(mut self, instrument: &Instrument) -> Result<Self> {
+        self.validate()?;
+        if self.inst_id != instrument.inst_id {
+            return Err(invalid(format!(
+                "order instId {} does not match instrument {}",
+                self.inst_id, instrument.inst_id
+            )));
+        }
+
+        let tick = parse_step("tickSz", &instrument.tick_sz)?;
+        if let Some(px) = self.px.take() {
+            self.px = Some(round_price(&px, tick)?);
+        }
+        for algo in self.attach_algo_ords.iter_mut().flatten() {
+            for px in [
+                &mut algo.tp_trigger_px,
+                &mut algo.tp_ord_px,
+                &mut algo.sl_trigger_px,
+                &mut algo.sl_ord_px,
+            ] {
+                if let Some(value) = px.take() {
+                    *px = Some(round_price(&value, tick)?);
+                }
+            }
+        }
+
+        if self.sz_in_quote_ccy(instrument) {
+            return Ok(self);
+        }
+
+        let lot = parse_step("lotSz", &instrument.lot_sz)?;
+        let sz = parse_number("sz", &self.sz)?
+            .floor_to(lot)
+            .ok_or_else(|| out_of_range("sz", &self.sz))?;
+        if let Some(min) = parse_fixed(&instrument.min_sz) {
+            let ordering = sz
+                .try_cmp(&min)
+                .ok_or_else(|| out_of_range("sz", &self.sz))?;
+            if ordering == Ordering::Less {
+                return Err(invalid(format!(
+                    "sz {} is below minSz {}",
+                    self.sz, instrument.min_sz
+                )));
+            }
+        }
+        let (max_name, max_sz) = if self.is_market_like() {
+            ("maxMktSz", &instrument.max_mkt_sz)
+        } else {
+            ("maxLmtSz", &instrument.max_lmt_sz)
+        };
+        if let Some(max) = parse_fixed(max_sz) {
+            let ordering = sz
+                .try_cmp(&max)
+                .ok_or_else(|| out_of_range("sz", &self.sz))?;
+            if ordering == Ordering::Greater {
+                return Err(invalid(format!(
+                    "sz {} exceeds {max_name} {max_sz}",
+                    self.sz
+                )));
+            }
+        }
+        self.sz = sz.to_string();
+
+        Ok(self)
+    }
+
+    fn is_market_like(&self) -> bool {
+        matches!(self.ord_type, OrdType::Market | OrdType::OptimalLimitIoc)
+    }
+
+    /// SPOT 市价买单默认以计价货币为数量单位。
+    fn sz_in_quote_ccy(&self, instrument: &Instrument) -> bool {
+        if !self.is_market_like() || instrument.inst_type != "SPOT" {
+            return false;
+        }
+        match self.tgt_ccy.as_deref() {
+            Some(tgt_ccy) => tgt_ccy == "quote_ccy",
+            None => self.side == Side::Buy,
+        }
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> OkxError {
+    OkxError::InvalidParameter(msg.into())
+}
+
+fn out_of_range(name: &str, value: &str) -> OkxError {
+    invalid(format!("{name} is out of range: {value:?}"))
+}
+
+fn parse_number(name: &str, value: &str) -> Result<Fixed> {
+    parse_fixed(value).ok_or_else(|| invalid(format!("{name} is not a valid number: {value:?}")))
+}
+
+fn parse_step(name: &str, value: &str) -> Result<Fixed> {
+    parse_number(name, value).and_then(|step| {
+        if step.mantissa > 0 {
+            Ok(step)
+        } else {
+            Err(invalid(format!("{name} must be positive, got {value:?}")))
+        }
+    })
+}
+
+/// 价格取最近的 tick 整数倍；`-1`（市价）保持不变。
+fn round_price(px: &str, tick: Fixed) -> Result<String> {
+    if px == "-1" {
+        return Ok(px.to_string());
+    }
+    parse_number("px", px)?
+        .round_to(tick)
+        .map(|px| px.to_string())
+        .ok_or_else(|| out_of_range("px", px))
+}
+
+/// 定点小数：`mantissa × 10^-scale`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fixed {
+    mantissa: i128,
+    scale: u32,
+}
+
+/// 超过该精度的数值视为非法，避免 `i128` 溢出。
+const MAX_SCALE: u32 = 18;
+
+fn parse_fixed(value: &str) -> Option<Fixed> {
+    let value = value.trim();
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if int_part.is_empty() && frac_part.is_empty() {
+        return None;
+    }
+    if !int_part
+        .bytes()
+        .chain(frac_part.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let scale = u32::try_from(frac_part.len())
+        .ok()
+        .filter(|s| *s <= MAX_SCALE)?;
+    let mut mantissa: i128 = 0;
+    for b in int_part.bytes().chain(frac_part.bytes()) {
+        mantissa = mantissa
+            .checked_mul(10)?
+            .checked_add(i128::from(b - b'0'))?;
+    }
+    if negative {
+        mantissa = -mantissa;
+    }
+    Some(Fixed { mantissa, scale })
+}
+
+impl Fixed {
+    /// 转换到更高精度下的尾数；超出 `i128` 范围时返回 `None`。
+    fn mantissa_at(self, scale: u32) -> Option<i128> {
+        10_i128
+            .checked_pow(scale - self.scale)
+            .and_then(|factor| self.mantissa.checked_mul(factor))
+    }
+
+    /// 向下取整到 `step` 的整数倍。
+    fn floor_to(self, step: Fixed) -> Option<Fixed> {
+        let scale = self.scale.max(step.scale);
+        let (value, step_m) = (self.mantissa_at(scale)?, step.mantissa_at(scale)?);
+        let mantissa = value.div_euclid(step_m).checked_mul(step_m)?;
+        Some(Fixed { mantissa, scale }.with_scale(step.scale))
+    }
+
+    /// 四舍五入到 `step` 的整数倍。
+    fn round_to(self, step: Fixed) -> Option<Fixed> {
+        let scale = self.scale.max(step.scale);
+        let (value, step_m) = (self.mantissa_at(scale)?, step.mantissa_at(scale)?);
+        let mantissa = value
+            .checked_add(step_m / 2)?
+            .div_euclid(step_m)
+            .checked_mul(step_m)?;
+        Some(Fixed { mantissa, scale }.with_scale(step.scale))
+    }
+
+    /// 降低精度（调用方保证不丢失有效数字）。
+    fn with_scale(self, scale: u32) -> Fixed {
+        if scale >= self.scale {
+            return self;
+        }
+        Fixed {
+            mantissa: self.mantissa / 10_i128.pow(self.scale - scale),
+            scale,
+        }
+    }
+
+    /// 比较大小；对齐精度时溢出返回 `None`。
+    fn try_cmp(&self, other: &Fixed) -> Option<Ordering> {
+        let scale = self.scale.max(other.scale);
+        Some(self.mantissa_at(scale)?.cmp(&other.mantissa_at(scale)?))
+    }
+}
+
+impl std::fmt::Display for Fixed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let abs = self.mantissa.unsigned_abs();
+        if self.scale == 0 {
+            return write!(f, "{sign}{abs}");
+        }
+        let divisor = 10_u128.pow(self.scale);
+        let width = self.scale as usize;
+        write!(f, "{sign}{}.{:0width$}", abs / divisor, abs % divisor)
+    }
+}
@@ -5,9 +5,15 @@
 //! - GET /api/v5/trade/order
 //! - GET /api/v5/trade/orders-pending
 //! - GET /api/v5/trade/fills
+//! - POST /api/v5/trade/batch-orders
+
+use std::ops::Range;
 
 use serde::{Deserialize, Serialize};
 
+use super::{ApiResponse, OrdState, OrdType, PosSide, Side, TdMode};
+use crate::{OkxError, Result};
+
 /// Order information.
 ///
 /// Source: GET /api/v5/trade/order response
@@ -41,9 +47,9 @@ pub struct Order {
     #[serde(default)]
     pub pnl: String,
     /// Order type (market, limit, `post_only`, fok, ioc, etc.)
-    pub ord_type: String,
+    pub ord_type: OrdType,
     /// Order side (buy, sell)
-    pub side: String,
+    pub side: Side,
     /// Position side (long, short, net)
     #[serde(default)]
     pub pos_side: String,
@@ -68,7 +74,7 @@ pub struct Order {
     #[serde(default)]
     pub avg_px: String,
     /// Order state (created, live, `partially_filled`, canceled, filled)
-    pub state: String,
+    pub state: OrdState,
     /// Leverage
     #[serde(default)]
     pub lever: String,
@@ -222,7 +228,7 @@ pub struct Fill {
     #[serde(default)]
     pub fill_pnl: String,
     /// Order side (buy, sell)
-    pub side: String,
+    pub side: Side,
     /// Position side (long, short, net)
     #[serde(default)]
     pub pos_side: String,
@@ -263,11 +269,11 @@ pub struct PlaceOrderRequest {
     /// Instrument ID (e.g., "BTC-USDT")
     pub inst_id: String,
     /// Trade mode: cash, cross, isolated
-    pub td_mode: String,
+    pub td_mode: TdMode,
     /// Order side: buy, sell
-    pub side: String,
+    pub side: Side,
     /// Order type: market, limit, `post_only`, fok, ioc
-    pub ord_type: String,
+    pub ord_type: OrdType,
     /// Quantity to buy or sell
     pub sz: String,
     /// Currency (for SPOT/MARGIN orders in `tgtCcy=quote_ccy` mode)
@@ -281,7 +287,7 @@ pub struct PlaceOrderRequest {
     pub tag: Option<String>,
     /// Position side: long, short, net
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pos_side: Option<String>,
+    pub pos_side: Option<PosSide>,
     /// Order price (required for limit orders)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub px: Option<String>,
@@ -475,6 +481,247 @@ pub struct AmendOrderResponse {
     pub s_msg: String,
 }
 
+/// Mass cancel request parameters (MMP orders of an instrument family).
+///
+/// Source: POST /api/v5/trade/mass-cancel request body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MassCancelRequest {
+    /// Instrument type (`OPTION`)
+    pub inst_type: String,
+    /// Instrument family (e.g., "BTC-USD")
+    pub inst_family: String,
+    /// Lock interval in milliseconds (0-10000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_interval: Option<String>,
+}
+
+/// Mass cancel response.
+///
+/// Source: POST /api/v5/trade/mass-cancel response data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MassCancelResponse {
+    /// Whether the request was accepted
+    pub result: bool,
+}
+
+/// Item of a batch order response carrying its own `sCode`/`sMsg`.
+pub trait BatchItem {
+    /// Per-item response code ("0" = success)
+    fn s_code(&self) -> &str;
+    /// Per-item response message
+    fn s_msg(&self) -> &str;
+    /// Client-supplied order ID
+    fn cl_ord_id(&self) -> &str;
+    /// Order ID
+    fn ord_id(&self) -> &str;
+
+    /// Check if this item succeeded.
+    fn is_success(&self) -> bool {
+        self.s_code() == "0"
+    }
+
+    /// Get the per-item error, if this item failed.
+    fn error(&self) -> Option<OkxError> {
+        (!self.is_success()).then(|| OkxError::api(self.s_code(), self.s_msg()))
+    }
+}
+
+macro_rules! impl_batch_item {
+    ($($ty:ty),+) => {
+        $(impl BatchItem for $ty {
+            fn s_code(&self) -> &str {
+                &self.s_code
+            }
+            fn s_msg(&self) -> &str {
+                &self.s_msg
+            }
+            fn cl_ord_id(&self) -> &str {
+                &self.cl_ord_id
+            }
+            fn ord_id(&self) -> &str {
+                &self.ord_id
+            }
+        })+
+    };
+}
+
+impl_batch_item!(PlaceOrderResponse, CancelOrderResponse, AmendOrderResponse);
+
+/// Result of a batch place/cancel/amend request.
+///
+/// OKX answers batch requests with top-level `code` "0" (all succeeded),
+/// "1" (all failed) or "2" (partial success); every item carries its own
+/// `sCode`/`sMsg`. All items are kept regardless of the top-level code.
+///
+/// Source: POST /api/v5/trade/batch-orders response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchResult<T> {
+    /// Top-level response code ("0", "1" or "2")
+    pub code: String,
+    /// Top-level response message
+    pub msg: String,
+    /// Per-item results, in request order
+    pub items: Vec<T>,
+}
+
+impl<T: BatchItem> BatchResult<T> {
+    /// Build from an API response, keeping items on partial or total failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OkxError::Api`] when the request itself was rejected
+    /// (any code other than "0"/"1"/"2", or a failure without items).
+    pub fn from_response(response: ApiResponse<T>) -> Result<Self> {
+        let per_item = match response.code.as_str() {
+            "0" => true,
+            "1" | "2" => !response.data.is_empty(),
+            _ => false,
+        };
+        if !per_item {
+            return Err(OkxError::api(response.code, response.msg));
+        }
+
+        Ok(Self {
+            code: response.code,
+            msg: response.msg,
+            items: response.data,
+        })
+    }
+
+    /// Check if every item succeeded.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.items.iter().all(BatchItem::is_success)
+    }
+
+    /// Check if some, but not all, items succeeded.
+    #[must_use]
+    pub fn is_partial(&self) -> bool {
+        self.items.iter().any(BatchItem::is_success) && !self.is_success()
+    }
+
+    /// Iterate over items that succeeded.
+    pub fn succeeded(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().filter(|item| item.is_success())
+    }
+
+    /// Iterate over items that failed.
+    pub fn failed(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().filter(|item| !item.is_success())
+    }
+
+    /// Client order IDs of items that succeeded.
+    #[must_use]
+    pub fn succeeded_cl_ord_ids(&self) -> Vec<&str> {
+        self.succeeded().map(BatchItem::cl_ord_id).collect()
+    }
+
+    /// Client order IDs of items that failed.
+    #[must_use]
+    pub fn failed_cl_ord_ids(&self) -> Vec<&str> {
+        self.failed().map(BatchItem::cl_ord_id).collect()
+    }
+
+    /// Per-item errors of failed items, paired with the item.
+    #[must_use]
+    pub fn errors(&self) -> Vec<(&T, OkxError)> {
+        self.items
+            .iter()
+            .filter_map(|item| item.error().map(|err| (item, err)))
+            .collect()
+    }
+}
+
+/// Batch request that failed as a whole (HTTP error, rate limit, ...).
+#[derive(Debug)]
+pub struct FailedChunk {
+    /// Indices of the affected requests in the original input
+    pub range: Range<usize>,
+    /// Error returned for the whole chunk
+    pub error: OkxError,
+}
+
+/// Result of a batch operation split into several batch requests.
+///
+/// Items of chunks that reached OKX are merged in input order; chunks that
+/// failed as a whole are reported in `failed_chunks` so they can be resubmitted.
+/// A chunk answered with a different number of items than it sent is also
+/// reported as failed, since its items cannot be matched to the requests.
+///
+/// `items` skips the requests of failed chunks, so `items[i]` is only the
+/// `i`-th request when every chunk was answered. Use
+/// [`SplitBatchResult::by_input`] to get one result per input request.
+#[derive(Debug)]
+pub struct SplitBatchResult<T> {
+    /// Per-item results of all answered chunks, in input order, without the
+    /// requests of `failed_chunks`
+    pub items: Vec<T>,
+    /// Chunks that failed as a whole, in input order
+    pub failed_chunks: Vec<FailedChunk>,
+}
+
+impl<T: BatchItem> SplitBatchResult<T> {
+    /// Check if every chunk was answered and every item succeeded.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failed_chunks.is_empty() && self.items.iter().all(BatchItem::is_success)
+    }
+
+    /// Iterate over items that succeeded.
+    pub fn succeeded(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().filter(|item| item.is_success())
+    }
+
+    /// Iterate over items that were answered with a non-zero `sCode`.
+    pub fn failed(&self) -> impl Iterator<Item = &T> {
+        self.items.iter().filter(|item| !item.is_success())
+    }
+
+    /// Client order IDs of items that succeeded.
+    #[must_use]
+    pub fn succeeded_cl_ord_ids(&self) -> Vec<&str> {
+        self.succeeded().map(BatchItem::cl_ord_id).collect()
+    }
+
+    /// Client order IDs of items that were answered with a non-zero `sCode`.
+    #[must_use]
+    pub fn failed_cl_ord_ids(&self) -> Vec<&str> {
+        self.failed().map(BatchItem::cl_ord_id).collect()
+    }
+
+    /// One result per input request, aligned with the input index.
+    ///
+    /// Requests of a failed chunk map to the chunk error; the others map to
+    /// their item (which may still carry a non-zero `sCode`).
+    #[must_use]
+    pub fn by_input(&self) -> Vec<std::result::Result<&T, &OkxError>> {
+        let total = self.items.len()
+            + self
+                .failed_chunks
+                .iter()
+                .map(|c| c.range.len())
+                .sum::<usize>();
+        let mut items = self.items.iter();
+        let mut chunks = self.failed_chunks.iter().peekable();
+        let mut results = Vec::with_capacity(total);
+        for index in 0..total {
+            while chunks.peek().is_some_and(|c| c.range.end <= index) {
+                chunks.next();
+            }
+            match chunks.peek() {
+                Some(chunk) if chunk.range.contains(&index) => results.push(Err(&chunk.error)),
+                _ => match items.next() {
+                    Some(item) => results.push(Ok(item)),
+                    None => break,
+                },
+            }
+        }
+        results
+    }
+}
+
 /// Place algo order request.
 ///
 /// Source: POST /api/v5/trade/order-algo request body
@@ -70,12 +70,29 @@ fn test_bar_as_str() {
         (Bar::H6, "6H"),
         (Bar::H12, "12H"),
         (Bar::D1, "1D"),
+        (Bar::D2, "2D"),
+        (Bar::D3, "3D"),
         (Bar::W1, "1W"),
         (Bar::Mo1, "1M"),
         (Bar::Mo3, "3M"),
+        (Bar::H6Utc, "6Hutc"),
+        (Bar::H12Utc, "12Hutc"),
+        (Bar::D1Utc, "1Dutc"),
+        (Bar::D2Utc, "2Dutc"),
+        (Bar::D3Utc, "3Dutc"),
+        (Bar::W1Utc, "1Wutc"),
+        (Bar::Mo1Utc, "1Mutc"),
+        (Bar::Mo3Utc, "3Mutc"),
     ];
 
     for (bar, expected) in cases {
         assert_eq!(bar.as_str(), expected);
+        assert_eq!(
+            serde_json::to_value(bar).expect("应可序列化"),
+            serde_json::json!(expected)
+        );
+        assert_eq!(Bar::from_api_str(expected), Some(bar));
     }
+    assert_eq!(Bar::ALL.len(), 24);
+    assert_eq!(Bar::from_api_str("7m"), None);
 }
@@ -1,7 +1,10 @@
 //! Unit tests for okx-core.
 
 use okx_core::{
-    types::{ApiResponse, InstType, OrdType, Side, TdMode},
+    types::{
+        ApiResponse, BatchItem, BatchResult, InstType, MgnMode, OrdState, OrdType, Order,
+        PlaceOrderRequest, PlaceOrderResponse, PosSide, Position, Side, TdMode,
+    },
     Config, Credentials, OkxError, Signer,
 };
 
@@ -341,4 +344,101 @@ mod types_tests {
         let parsed: Side = serde_json::from_str(r#""sell""#).unwrap();
         assert_eq!(parsed, Side::Sell);
     }
+
+    #[test]
+    fn test_enum_unknown_fallback() {
+        let parsed: OrdType = serde_json::from_str(r#""twap""#).expect("未知取值不应失败");
+        assert_eq!(parsed, OrdType::Unknown("twap".to_string()));
+        assert!(parsed.is_unknown());
+        assert_eq!(serde_json::to_string(&parsed).unwrap(), r#""twap""#);
+
+        assert_eq!(Side::from("buy"), Side::Buy);
+        assert_eq!(OrdState::default().as_str(), "");
+        assert_eq!(MgnMode::Isolated, "isolated");
+    }
+
+    #[test]
+    fn test_order_fields_are_typed() {
+        let order: Order = serde_json::from_value(serde_json::json!({
+            "instType": "SWAP", "instId": "BTC-USDT-SWAP", "ordId": "1", "sz": "1",
+            "ordType": "post_only", "side": "sell", "tdMode": "cross",
+            "state": "partially_filled"
+        }))
+        .expect("订单应可反序列化");
+        assert_eq!(order.ord_type, OrdType::PostOnly);
+        assert_eq!(order.side, Side::Sell);
+        assert_eq!(order.state, OrdState::PartiallyFilled);
+
+        let position: Position = serde_json::from_value(serde_json::json!({
+            "instType": "SWAP", "instId": "BTC-USDT-SWAP", "posId": "1", "pos": "1"
+        }))
+        .expect("持仓应可反序列化");
+        assert_eq!(position.mgn_mode, "");
+
+        let request: PlaceOrderRequest = serde_json::from_value(serde_json::json!({
+            "instId": "BTC-USDT-SWAP", "tdMode": "isolated", "side": "buy",
+            "ordType": "limit", "sz": "1", "posSide": "long"
+        }))
+        .expect("下单请求应可反序列化");
+        assert_eq!(request.td_mode, TdMode::Isolated);
+        assert_eq!(request.pos_side, Some(PosSide::Long));
+        let body = serde_json::to_value(&request).unwrap();
+        assert_eq!(body["ordType"], "limit");
+        assert_eq!(body["posSide"], "long");
+    }
+
+    fn batch_response(code: &str, items: &[(&str, &str)]) -> ApiResponse<PlaceOrderResponse> {
+        ApiResponse {
+            code: code.to_string(),
+            msg: String::new(),
+            data: items
+                .iter()
+                .map(|(cl_ord_id, s_code)| PlaceOrderResponse {
+                    ord_id: String::new(),
+                    cl_ord_id: (*cl_ord_id).to_string(),
+                    tag: String::new(),
+                    s_code: (*s_code).to_string(),
+                    s_msg: if *s_code == "0" {
+                        String::new()
+                    } else {
+                        "failed".to_string()
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_batch_result_keeps_partial_successes() {
+        let result = BatchResult::from_response(batch_response(
+            "2",
+            &[("a", "0"), ("b", "51008"), ("c", "0")],
+        ))
+        .expect("部分成功不应返回错误");
+
+        assert!(result.is_partial());
+        assert!(!result.is_success());
+        assert_eq!(result.succeeded_cl_ord_ids(), ["a", "c"]);
+        assert_eq!(result.failed_cl_ord_ids(), ["b"]);
+
+        let errors = result.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0.cl_ord_id(), "b");
+        assert!(errors[0].1.is_api_error("51008"));
+    }
+
+    #[test]
+    fn test_batch_result_all_failed_and_request_errors() {
+        let result = BatchResult::from_response(batch_response("1", &[("a", "51000")]))
+            .expect("全部失败时仍应返回逐项结果");
+        assert!(!result.is_partial());
+        assert_eq!(result.failed_cl_ord_ids(), ["a"]);
+
+        let err = BatchResult::from_response(batch_response("50011", &[]))
+            .expect_err("请求级错误应返回 Err");
+        assert!(err.is_api_error("50011"));
+
+        let ok = BatchResult::from_response(batch_response("0", &[("a", "0")])).expect("应成功");
+        assert!(ok.is_success());
+    }
 }
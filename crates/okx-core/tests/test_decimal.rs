@@ -0,0 +1,68 @@
+//! `decimal` feature：数值字符串字段的 `Decimal` 访问器。
+
+#![cfg(feature = "decimal")]
+
+use std::str::FromStr;
+
+use okx_core::types::{parse_decimal, Instrument, PlaceOrderRequest, Ticker};
+use okx_core::Decimal;
+use serde_json::json;
+
+fn dec(value: &str) -> Decimal {
+    Decimal::from_str(value).expect("应为合法小数")
+}
+
+#[test]
+fn parse_decimal_treats_empty_as_absent() {
+    assert_eq!(parse_decimal(""), None);
+    assert_eq!(parse_decimal("  "), None);
+    assert_eq!(parse_decimal("abc"), None);
+    assert_eq!(parse_decimal("0.00012300"), Some(dec("0.00012300")));
+    assert_eq!(parse_decimal("1e-8"), Some(dec("0.00000001")));
+}
+
+#[test]
+fn ticker_and_instrument_accessors() {
+    let ticker: Ticker = serde_json::from_value(json!({
+        "instType": "SPOT", "instId": "BTC-USDT",
+        "last": "43250.1", "lastSz": "0.001",
+        "askPx": "43250.2", "askSz": "", "bidPx": "43250.1", "bidSz": "1.5",
+        "open24h": "42000", "high24h": "44000", "low24h": "41000",
+        "volCcy24h": "100", "vol24h": "2", "sodUtc0": "", "sodUtc8": "",
+        "ts": "1700000000000"
+    }))
+    .expect("Ticker 应可反序列化");
+
+    assert_eq!(ticker.last_decimal(), Some(dec("43250.1")));
+    assert_eq!(ticker.ask_sz_decimal(), None);
+    assert_eq!(ticker.sod_utc_0_decimal(), None);
+
+    let instrument: Instrument = serde_json::from_value(json!({
+        "instType": "SPOT", "instId": "BTC-USDT", "tickSz": "0.1", "lotSz": "0.00000001",
+        "minSz": "0.00001", "state": "live"
+    }))
+    .expect("Instrument 应可反序列化");
+    assert_eq!(instrument.tick_sz_decimal(), Some(dec("0.1")));
+    assert_eq!(instrument.lot_sz_decimal(), Some(dec("0.00000001")));
+    assert_eq!(instrument.ct_val_decimal(), None);
+}
+
+#[test]
+fn place_order_request_round_trips_exact_precision() {
+    let request: PlaceOrderRequest = serde_json::from_value(json!({
+        "instId": "BTC-USDT", "tdMode": "cash", "side": "buy", "ordType": "limit", "sz": "1"
+    }))
+    .expect("下单请求应可反序列化");
+
+    let request = request
+        .with_sz_decimal(dec("0.010"))
+        .with_px_decimal(dec("43250.10"));
+    assert_eq!(request.sz, "0.010");
+    assert_eq!(request.px.as_deref(), Some("43250.10"));
+    assert_eq!(request.px_decimal(), Some(dec("43250.10")));
+    assert_eq!(request.tp_trigger_px_decimal(), None);
+
+    let body = serde_json::to_value(&request).expect("应可序列化");
+    assert_eq!(body["sz"], "0.010");
+    assert_eq!(body["px"], "43250.10");
+}
@@ -51,6 +51,19 @@ fn test_candle_from_array_and_confirm_flag() {
     assert!(Candle::from_array(&incomplete).is_none());
 }
 
+#[test]
+fn test_price_candle_from_array_has_no_volume() {
+    let raw: Vec<String> = ["1700000000000", "10", "12", "9", "11", "0"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    let candle = Candle::from_array(&raw).expect("标记价格 K 线应解析成功");
+    assert_eq!(candle.close, "11");
+    assert!(candle.vol.is_empty());
+    assert!(!candle.is_confirmed());
+}
+
 #[test]
 fn test_order_book_parsed_bids_filters_incomplete_levels() {
     let book = OrderBook {
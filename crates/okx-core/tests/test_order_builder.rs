@@ -0,0 +1,180 @@
+//! `PlaceOrderRequest` 构造器与下单前校验。
+
+use okx_core::{
+    types::{AccountConfig, Instrument, OrdType, PlaceOrderRequest, PosSide, Side, TdMode},
+    OkxError,
+};
+use serde_json::json;
+
+fn swap_instrument() -> Instrument {
+    serde_json::from_value(json!({
+        "instType": "SWAP", "instId": "BTC-USDT-SWAP",
+        "tickSz": "0.1", "lotSz": "0.01", "minSz": "0.01",
+        "maxLmtSz": "100", "maxMktSz": "10", "state": "live"
+    }))
+    .expect("Instrument 应可反序列化")
+}
+
+fn spot_instrument() -> Instrument {
+    serde_json::from_value(json!({
+        "instType": "SPOT", "instId": "BTC-USDT",
+        "tickSz": "0.1", "lotSz": "0.00001", "minSz": "0.0001",
+        "maxLmtSz": "1000", "maxMktSz": "1000000", "state": "live"
+    }))
+    .expect("Instrument 应可反序列化")
+}
+
+fn account_config(pos_mode: &str) -> AccountConfig {
+    serde_json::from_value(json!({ "uid": "1", "acctLv": "2", "posMode": pos_mode }))
+        .expect("AccountConfig 应可反序列化")
+}
+
+fn expect_invalid(result: okx_core::Result<impl std::fmt::Debug>, needle: &str) {
+    match result {
+        Err(OkxError::InvalidParameter(msg)) => {
+            assert!(msg.contains(needle), "错误信息应包含 {needle}: {msg}");
+        }
+        other => panic!("应返回 InvalidParameter，实际为 {other:?}"),
+    }
+}
+
+#[test]
+fn builder_sets_fields_and_serializes() {
+    let request = PlaceOrderRequest::limit("BTC-USDT-SWAP", Side::Buy, "43000.1", "1")
+        .cross()
+        .post_only()
+        .reduce_only()
+        .with_pos_side(PosSide::Long)
+        .with_cl_ord_id("c1")
+        .with_tp_sl("45000", "41000");
+
+    assert_eq!(request.td_mode, TdMode::Cross);
+    assert_eq!(request.ord_type, OrdType::PostOnly);
+    assert_eq!(request.reduce_only, Some(true));
+
+    let body = serde_json::to_value(&request).expect("应可序列化");
+    assert_eq!(body["px"], "43000.1");
+    assert_eq!(body["posSide"], "long");
+    assert_eq!(body["attachAlgoOrds"][0]["tpTriggerPx"], "45000");
+    assert_eq!(body["attachAlgoOrds"][0]["tpOrdPx"], "-1");
+    assert_eq!(body["attachAlgoOrds"][0]["slTriggerPx"], "41000");
+    assert!(body.get("tgtCcy").is_none());
+}
+
+#[test]
+fn validate_rejects_invalid_combinations() {
+    expect_invalid(
+        PlaceOrderRequest::market("BTC-USDT", Side::Buy, "1")
+            .with_px("100")
+            .validate(),
+        "px must not be set",
+    );
+    expect_invalid(
+        PlaceOrderRequest::new("BTC-USDT", Side::Buy, OrdType::Limit, "1").validate(),
+        "px is required",
+    );
+    expect_invalid(
+        PlaceOrderRequest::limit("BTC-USDT", Side::Buy, "100", "0").validate(),
+        "sz must be a positive number",
+    );
+    expect_invalid(
+        PlaceOrderRequest::limit("BTC-USDT", Side::Buy, "100", "1")
+            .with_pos_side(PosSide::Long)
+            .validate(),
+        "cash mode",
+    );
+
+    let hedged = PlaceOrderRequest::limit("BTC-USDT-SWAP", Side::Buy, "100", "1")
+        .cross()
+        .with_pos_side(PosSide::Short);
+    expect_invalid(
+        hedged.validate_pos_mode(&account_config("net_mode")),
+        "net_mode",
+    );
+    assert!(hedged
+        .validate_pos_mode(&account_config("long_short_mode"))
+        .is_ok());
+}
+
+#[test]
+fn prepare_rounds_to_tick_and_lot() {
+    let request = PlaceOrderRequest::limit("BTC-USDT-SWAP", Side::Sell, "43000.16", "1.239")
+        .cross()
+        .with_take_profit("42000.04", "42000.05")
+        .prepare(&swap_instrument())
+        .expect("应通过校验");
+
+    assert_eq!(request.px.as_deref(), Some("43000.2"));
+    assert_eq!(request.sz, "1.23");
+    let algo = &request.attach_algo_ords.as_ref().expect("应有止盈单")[0];
+    assert_eq!(algo.tp_trigger_px.as_deref(), Some("42000.0"));
+    assert_eq!(algo.tp_ord_px.as_deref(), Some("42000.1"));
+}
+
+#[test]
+fn prepare_checks_size_limits() {
+    let instrument = swap_instrument();
+
+    expect_invalid(
+        PlaceOrderRequest::limit("BTC-USDT-SWAP", Side::Buy, "100", "0.009")
+            .cross()
+            .prepare(&instrument),
+        "minSz",
+    );
+    expect_invalid(
+        PlaceOrderRequest::market("BTC-USDT-SWAP", Side::Buy, "11")
+            .cross()
+            .prepare(&instrument),
+        "maxMktSz",
+    );
+    assert!(
+        PlaceOrderRequest::limit("BTC-USDT-SWAP", Side::Buy, "100", "11")
+            .cross()
+            .prepare(&instrument)
+            .is_ok()
+    );
+    expect_invalid(
+        PlaceOrderRequest::limit("ETH-USDT-SWAP", Side::Buy, "100", "1").prepare(&instrument),
+        "does not match",
+    );
+}
+
+#[test]
+fn prepare_rejects_numbers_that_overflow_at_step_precision() {
+    // 18 位小数的 tickSz/lotSz 与 38 位整数相乘会超出 i128
+    let instrument: Instrument = serde_json::from_value(json!({
+        "instType": "SPOT", "instId": "BTC-USDT",
+        "tickSz": "0.000000000000000001", "lotSz": "0.000000000000000001",
+        "minSz": "0.000000000000000001", "maxLmtSz": "1000", "state": "live"
+    }))
+    .expect("Instrument 应可反序列化");
+    let huge = "1".repeat(38);
+
+    expect_invalid(
+        PlaceOrderRequest::limit("BTC-USDT", Side::Buy, "100", &huge)
+            .cash()
+            .prepare(&instrument),
+        "sz is out of range",
+    );
+    expect_invalid(
+        PlaceOrderRequest::limit("BTC-USDT", Side::Buy, &huge, "1")
+            .cash()
+            .prepare(&instrument),
+        "px is out of range",
+    );
+}
+
+#[test]
+fn prepare_keeps_quote_sized_spot_market_orders() {
+    let instrument = spot_instrument();
+
+    let buy = PlaceOrderRequest::market("BTC-USDT", Side::Buy, "25.123456")
+        .prepare(&instrument)
+        .expect("市价买单以计价货币计量");
+    assert_eq!(buy.sz, "25.123456");
+
+    let sell = PlaceOrderRequest::market("BTC-USDT", Side::Sell, "0.1234567")
+        .prepare(&instrument)
+        .expect("市价卖单以交易货币计量");
+    assert_eq!(sell.sz, "0.12345");
+}
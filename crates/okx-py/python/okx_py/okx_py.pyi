@@ -512,6 +512,8 @@ class WsClient:
     async def __anext__(self, /) -> Any: ...
     async def close(self, /) -> Any: ...
     @staticmethod
+    async def connect_business(config, max_reconnect_attempts=None) -> Any: ...
+    @staticmethod
     async def connect_private(config, max_reconnect_attempts=None) -> Any: ...
     @staticmethod
     async def connect_public(config, max_reconnect_attempts=None) -> Any: ...
@@ -519,19 +521,31 @@ class WsClient:
     async def reconnect(self, /) -> Any: ...
     async def recv(self, /) -> Any: ...
     async def subscribe_account(self, /, ccy=None) -> Any: ...
+    async def subscribe_account_greeks(self, /, ccy=None) -> Any: ...
+    async def subscribe_adl_warning(self, /, inst_type, inst_family=None) -> Any: ...
     async def subscribe_algo_advance(self, /, inst_type=None, inst_family=None, inst_id=None) -> Any: ...
     async def subscribe_algo_recurring_buy(self, /, algo_id=None) -> Any: ...
     async def subscribe_balance_and_position(self, /) -> Any: ...
     async def subscribe_block_tickers(self, /, inst_family=None) -> Any: ...
     async def subscribe_candles(self, /, inst_id, interval='1m') -> Any: ...
+    async def subscribe_deposit_info(self, /, ccy=None) -> Any: ...
+    async def subscribe_economic_calendar(self, /) -> Any: ...
+    async def subscribe_estimated_price(self, /, inst_type, inst_family=None, inst_id=None) -> Any: ...
     async def subscribe_funding_rate(self, /, inst_id) -> Any: ...
     async def subscribe_grid_orders_contract(self, /, algo_id=None, inst_id=None) -> Any: ...
     async def subscribe_grid_orders_moon(self, /, algo_id=None, inst_id=None) -> Any: ...
     async def subscribe_grid_orders_spot(self, /, algo_id=None, inst_id=None) -> Any: ...
     async def subscribe_grid_positions(self, /, algo_id=None, inst_type=None, inst_id=None) -> Any: ...
     async def subscribe_grid_sub_orders(self, /, algo_id=None, inst_id=None) -> Any: ...
+    async def subscribe_index_candles(self, /, inst_id, interval='1m') -> Any: ...
     async def subscribe_index_tickers(self, /, inst_id) -> Any: ...
+    async def subscribe_instruments(self, /, inst_type) -> Any: ...
+    async def subscribe_liquidation_orders(self, /, inst_type) -> Any: ...
+    async def subscribe_liquidation_warning(self, /, inst_type, inst_family=None, inst_id=None) -> Any: ...
     async def subscribe_mark_price(self, /, inst_id) -> Any: ...
+    async def subscribe_mark_price_candles(self, /, inst_id, interval='1m') -> Any: ...
+    async def subscribe_open_interest(self, /, inst_id) -> Any: ...
+    async def subscribe_opt_summary(self, /, inst_family) -> Any: ...
     async def subscribe_orderbook(self, /, inst_id) -> Any: ...
     async def subscribe_orderbook5(self, /, inst_id) -> Any: ...
     async def subscribe_orderbook50_l2_tbt(self, /, inst_id) -> Any: ...
@@ -539,13 +553,16 @@ class WsClient:
     async def subscribe_orders(self, /, inst_type, inst_id=None) -> Any: ...
     async def subscribe_orders_algo(self, /, inst_type, inst_family=None, inst_id=None) -> Any: ...
     async def subscribe_positions(self, /, inst_type, inst_id=None) -> Any: ...
+    async def subscribe_price_limit(self, /, inst_id) -> Any: ...
     async def subscribe_public_block_trades(self, /, inst_family=None) -> Any: ...
     async def subscribe_public_struc_block_trades(self, /, inst_family=None) -> Any: ...
     async def subscribe_quotes(self, /, inst_family=None) -> Any: ...
     async def subscribe_rfqs(self, /, inst_family=None) -> Any: ...
+    async def subscribe_status(self, /) -> Any: ...
     async def subscribe_struc_block_trades(self, /, inst_family=None) -> Any: ...
     async def subscribe_tickers(self, /, inst_id) -> Any: ...
     async def subscribe_trades(self, /, inst_id) -> Any: ...
+    async def subscribe_withdrawal_info(self, /, ccy=None) -> Any: ...
     async def subscription_count(self, /) -> Any: ...
 
 @final
@@ -63,9 +63,9 @@ impl PyAsyncOkxClient {
     ) -> PyResult<Bound<'py, PyAny>> {
         let request = PlaceOrderRequest {
             inst_id,
-            td_mode,
-            side,
-            ord_type,
+            td_mode: td_mode.into(),
+            side: side.into(),
+            ord_type: ord_type.into(),
             sz,
             px,
             cl_ord_id,
@@ -106,9 +106,9 @@ impl PyAsyncOkxClient {
             .map(
                 |(inst_id, td_mode, side, ord_type, sz, px, cl_ord_id)| PlaceOrderRequest {
                     inst_id,
-                    td_mode,
-                    side,
-                    ord_type,
+                    td_mode: td_mode.into(),
+                    side: side.into(),
+                    ord_type: ord_type.into(),
                     sz,
                     px,
                     cl_ord_id,
@@ -135,7 +135,8 @@ impl PyAsyncOkxClient {
             rest.place_batch_orders(requests)
                 .await
                 .map(|v| {
-                    v.into_iter()
+                    v.items
+                        .into_iter()
                         .map(PyPlaceOrderResult::from)
                         .collect::<Vec<_>>()
                 })
@@ -186,7 +187,8 @@ impl PyAsyncOkxClient {
             rest.cancel_batch_orders(requests)
                 .await
                 .map(|v| {
-                    v.into_iter()
+                    v.items
+                        .into_iter()
                         .map(PyCancelOrderResult::from)
                         .collect::<Vec<_>>()
                 })
@@ -730,7 +732,8 @@ impl PyAsyncOkxClient {
             rest.amend_batch_orders(requests)
                 .await
                 .map(|v| {
-                    v.into_iter()
+                    v.items
+                        .into_iter()
                         .map(PyAmendOrderResult::from)
                         .collect::<Vec<_>>()
                 })
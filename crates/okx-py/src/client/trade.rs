@@ -64,9 +64,9 @@ impl PyOkxClient {
     ) -> PyResult<String> {
         let request = PlaceOrderRequest {
             inst_id: inst_id.to_string(),
-            td_mode: td_mode.to_string(),
-            side: side.to_string(),
-            ord_type: ord_type.to_string(),
+            td_mode: td_mode.into(),
+            side: side.into(),
+            ord_type: ord_type.into(),
             sz: sz.to_string(),
             px: px.map(String::from),
             cl_ord_id: cl_ord_id.map(String::from),
@@ -107,9 +107,9 @@ impl PyOkxClient {
             .map(
                 |(inst_id, td_mode, side, ord_type, sz, px, cl_ord_id)| PlaceOrderRequest {
                     inst_id,
-                    td_mode,
-                    side,
-                    ord_type,
+                    td_mode: td_mode.into(),
+                    side: side.into(),
+                    ord_type: ord_type.into(),
                     sz,
                     px,
                     cl_ord_id,
@@ -136,7 +136,7 @@ impl PyOkxClient {
             self.client
                 .place_batch_orders(requests)
                 .await
-                .map(|v| v.into_iter().map(PyPlaceOrderResult::from).collect())
+                .map(|v| v.items.into_iter().map(PyPlaceOrderResult::from).collect())
                 .map_err(to_py_err)
         })
     }
@@ -183,7 +183,7 @@ impl PyOkxClient {
             self.client
                 .cancel_batch_orders(requests)
                 .await
-                .map(|v| v.into_iter().map(PyCancelOrderResult::from).collect())
+                .map(|v| v.items.into_iter().map(PyCancelOrderResult::from).collect())
                 .map_err(to_py_err)
         })
     }
@@ -274,7 +274,7 @@ impl PyOkxClient {
             self.client
                 .amend_batch_orders(requests)
                 .await
-                .map(|v| v.into_iter().map(PyAmendOrderResult::from).collect())
+                .map(|v| v.items.into_iter().map(PyAmendOrderResult::from).collect())
                 .map_err(to_py_err)
         })
     }
@@ -131,12 +131,13 @@ fn okx_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
 /// Convert OKX error to Python exception.
 ///
 /// 根据错误类型返回对应的 Python 异常：
-/// - `HttpStatus` 429 -> `OkxRateLimitError`
+/// - `HttpStatus` 429 / `RateLimitExceeded` -> `OkxRateLimitError`
 /// - `HttpStatus` 其他 -> `OkxHttpError`
 /// - `Api` -> `OkxApiError`
 /// - `Auth` -> `OkxAuthError`
 /// - `WebSocket` / `ConnectionClosed` -> `OkxWebSocketError`
 /// - `Timeout` -> `OkxTimeoutError`
+/// - `Retried` -> 最后一次尝试对应的异常类型（消息附带重试次数）
 /// - 其他 -> `OkxError`
 fn to_py_err(e: okx_core::OkxError) -> PyErr {
     use okx_core::OkxError as E;
@@ -149,11 +150,20 @@ fn to_py_err(e: okx_core::OkxError) -> PyErr {
                 OkxHttpError::new_err(msg)
             }
         }
+        E::RateLimitExceeded { .. } => OkxRateLimitError::new_err(e.to_string()),
         E::Api { ref code, ref msg } => OkxApiError::new_err(format!("API error [{code}]: {msg}")),
         E::Auth(ref msg) => OkxAuthError::new_err(msg.clone()),
         E::WebSocket(ref msg) => OkxWebSocketError::new_err(msg.clone()),
         E::ConnectionClosed => OkxWebSocketError::new_err("Connection closed"),
         E::Timeout => OkxTimeoutError::new_err("Request timeout"),
+        E::Retried { attempts, source } => {
+            // 保留原始异常类型，消息中附带重试次数
+            let err = to_py_err(*source);
+            Python::attach(|py| {
+                let msg = format!("{} (after {attempts} attempts)", err.value(py));
+                PyErr::from_type(err.get_type(py), msg)
+            })
+        }
         _ => OkxError::new_err(e.to_string()),
     }
 }
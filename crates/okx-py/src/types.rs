@@ -316,19 +316,19 @@ impl PyOrder {
     /// Order side.
     #[getter]
     fn side(&self) -> &str {
-        &self.inner.side
+        self.inner.side.as_str()
     }
 
     /// Order type.
     #[getter]
     fn ord_type(&self) -> &str {
-        &self.inner.ord_type
+        self.inner.ord_type.as_str()
     }
 
     /// Order state.
     #[getter]
     fn state(&self) -> &str {
-        &self.inner.state
+        self.inner.state.as_str()
     }
 
     /// Price.
@@ -892,7 +892,7 @@ impl From<Fill> for PyFill {
             fill_px: v.fill_px,
             fill_sz: v.fill_sz,
             fill_pnl: v.fill_pnl,
-            side: v.side,
+            side: v.side.to_string(),
             pos_side: v.pos_side,
             exec_type: v.exec_type,
             fee_ccy: v.fee_ccy,
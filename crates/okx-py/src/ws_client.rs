@@ -7,6 +7,7 @@ use pyo3::prelude::*;
 use pyo3::types::PyDict;
 use tokio::sync::Mutex;
 
+use okx_core::types::Bar;
 use okx_core::TimestampProvider;
 use okx_ws::{Channel, ConnectionType, ReconnectConfig, ReconnectingWsClient, WsMessage};
 
@@ -42,7 +43,7 @@ use crate::types::PyConfig;
 #[pyclass(name = "WsClient")]
 pub struct PyWsClient {
     client: Arc<Mutex<ReconnectingWsClient>>,
-    is_private: bool,
+    conn_type: ConnectionType,
 }
 
 #[pymethods]
@@ -78,7 +79,7 @@ impl PyWsClient {
 
             Ok(PyWsClient {
                 client: Arc::new(Mutex::new(client)),
-                is_private: false,
+                conn_type: ConnectionType::Public,
             })
         })
     }
@@ -114,7 +115,46 @@ impl PyWsClient {
 
             Ok(PyWsClient {
                 client: Arc::new(Mutex::new(client)),
-                is_private: true,
+                conn_type: ConnectionType::Private,
+            })
+        })
+    }
+
+    /// Connect to the business WebSocket endpoint.
+    ///
+    /// Serves candles, algo/grid, recurring-buy, RFQ and block-trade channels;
+    /// private business channels log in automatically.
+    ///
+    /// Args:
+    ///     config: Client configuration
+    ///     max_reconnect_attempts: Maximum reconnection attempts (default: unlimited)
+    ///
+    /// Returns:
+    ///     WsClient instance
+    #[staticmethod]
+    #[pyo3(signature = (config, max_reconnect_attempts=None))]
+    fn connect_business<'py>(
+        py: Python<'py>,
+        config: PyConfig,
+        max_reconnect_attempts: Option<u32>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut reconnect_config = ReconnectConfig::default();
+            if let Some(max) = max_reconnect_attempts {
+                reconnect_config = reconnect_config.with_max_attempts(max);
+            }
+
+            let client = ReconnectingWsClient::connect(
+                config.inner,
+                ConnectionType::Business,
+                reconnect_config,
+            )
+            .await
+            .map_err(to_py_err)?;
+
+            Ok(PyWsClient {
+                client: Arc::new(Mutex::new(client)),
+                conn_type: ConnectionType::Business,
             })
         })
     }
@@ -329,11 +369,104 @@ impl PyWsClient {
         })
     }
 
+    /// Subscribe to open interest channel.
+    ///
+    /// Args:
+    ///     inst_id: Instrument ID (e.g., "BTC-USDT-SWAP")
+    fn subscribe_open_interest<'py>(
+        &self,
+        py: Python<'py>,
+        inst_id: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::OpenInterest { inst_id })
+    }
+
+    /// Subscribe to price limit channel.
+    ///
+    /// Args:
+    ///     inst_id: Instrument ID (e.g., "BTC-USDT-SWAP")
+    fn subscribe_price_limit<'py>(
+        &self,
+        py: Python<'py>,
+        inst_id: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::PriceLimit { inst_id })
+    }
+
+    /// Subscribe to liquidation orders channel.
+    ///
+    /// Args:
+    ///     inst_type: Instrument type (MARGIN, SWAP, FUTURES, OPTION)
+    fn subscribe_liquidation_orders<'py>(
+        &self,
+        py: Python<'py>,
+        inst_type: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::LiquidationOrders { inst_type })
+    }
+
+    /// Subscribe to option summary channel.
+    ///
+    /// Args:
+    ///     inst_family: Instrument family (e.g., "BTC-USD")
+    fn subscribe_opt_summary<'py>(
+        &self,
+        py: Python<'py>,
+        inst_family: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::OptSummary { inst_family })
+    }
+
+    /// Subscribe to estimated delivery/exercise price channel.
+    ///
+    /// Args:
+    ///     inst_type: Instrument type (OPTION, FUTURES)
+    ///     inst_family: Instrument family (either this or inst_id is required)
+    ///     inst_id: Instrument ID
+    #[pyo3(signature = (inst_type, inst_family=None, inst_id=None))]
+    fn subscribe_estimated_price<'py>(
+        &self,
+        py: Python<'py>,
+        inst_type: String,
+        inst_family: Option<String>,
+        inst_id: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = Channel::EstimatedPrice {
+            inst_type,
+            inst_family,
+            inst_id,
+        };
+        self.subscribe_channel(py, channel)
+    }
+
+    /// Subscribe to instruments channel.
+    ///
+    /// Args:
+    ///     inst_type: Instrument type (SPOT, MARGIN, SWAP, FUTURES, OPTION)
+    fn subscribe_instruments<'py>(
+        &self,
+        py: Python<'py>,
+        inst_type: String,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::Instruments { inst_type })
+    }
+
+    /// Subscribe to system status channel.
+    fn subscribe_status<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::Status)
+    }
+
+    /// Subscribe to economic calendar channel (business connection, requires login).
+    fn subscribe_economic_calendar<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::EconomicCalendar)
+    }
+
     /// Subscribe to candlestick channel.
     ///
     /// Args:
     ///     inst_id: Instrument ID (e.g., "BTC-USDT")
-    ///     interval: Candle interval (1m, 5m, 15m, 1H, 4H, 1D)
+    ///     interval: Candle interval (1m/3m/5m/15m/30m/1H/2H/4H/6H/12H/1D/2D/3D/1W/1M/3M,
+    ///         or 6Hutc/12Hutc/1Dutc/2Dutc/3Dutc/1Wutc/1Mutc/3Mutc)
     #[pyo3(signature = (inst_id, interval="1m"))]
     fn subscribe_candles<'py>(
         &self,
@@ -341,30 +474,49 @@ impl PyWsClient {
         inst_id: String,
         interval: &str,
     ) -> PyResult<Bound<'py, PyAny>> {
-        let client = Arc::clone(&self.client);
-        let channel = match interval {
-            "1m" => Channel::Candle1m { inst_id },
-            "5m" => Channel::Candle5m { inst_id },
-            "15m" => Channel::Candle15m { inst_id },
-            "1H" => Channel::Candle1H { inst_id },
-            "4H" => Channel::Candle4H { inst_id },
-            "1D" => Channel::Candle1D { inst_id },
-            _ => {
-                return Err(pyo3::exceptions::PyValueError::new_err(format!(
-                    "Invalid interval: {}. Valid values: 1m, 5m, 15m, 1H, 4H, 1D",
-                    interval
-                )))
-            }
+        let channel = Channel::Candle {
+            bar: parse_bar(interval)?,
+            inst_id,
         };
+        self.subscribe_channel(py, channel)
+    }
 
-        pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            client
-                .lock()
-                .await
-                .subscribe(vec![channel])
-                .await
-                .map_err(to_py_err)
-        })
+    /// Subscribe to mark price candlestick channel.
+    ///
+    /// Args:
+    ///     inst_id: Instrument ID (e.g., "BTC-USDT-SWAP")
+    ///     interval: Candle interval, same values as `subscribe_candles`
+    #[pyo3(signature = (inst_id, interval="1m"))]
+    fn subscribe_mark_price_candles<'py>(
+        &self,
+        py: Python<'py>,
+        inst_id: String,
+        interval: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = Channel::MarkPriceCandle {
+            bar: parse_bar(interval)?,
+            inst_id,
+        };
+        self.subscribe_channel(py, channel)
+    }
+
+    /// Subscribe to index candlestick channel.
+    ///
+    /// Args:
+    ///     inst_id: Index (e.g., "BTC-USD")
+    ///     interval: Candle interval, same values as `subscribe_candles`
+    #[pyo3(signature = (inst_id, interval="1m"))]
+    fn subscribe_index_candles<'py>(
+        &self,
+        py: Python<'py>,
+        inst_id: String,
+        interval: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = Channel::IndexCandle {
+            bar: parse_bar(interval)?,
+            inst_id,
+        };
+        self.subscribe_channel(py, channel)
     }
 
     /// Subscribe to account channel (private).
@@ -389,6 +541,86 @@ impl PyWsClient {
         })
     }
 
+    /// Subscribe to liquidation warning channel (private).
+    ///
+    /// Args:
+    ///     inst_type: Instrument type (MARGIN, SWAP, FUTURES, OPTION, ANY)
+    ///     inst_family: Instrument family (optional)
+    ///     inst_id: Instrument ID (optional)
+    #[pyo3(signature = (inst_type, inst_family=None, inst_id=None))]
+    fn subscribe_liquidation_warning<'py>(
+        &self,
+        py: Python<'py>,
+        inst_type: String,
+        inst_family: Option<String>,
+        inst_id: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = Channel::LiquidationWarning {
+            inst_type,
+            inst_family,
+            inst_id,
+        };
+        self.subscribe_channel(py, channel)
+    }
+
+    /// Subscribe to account greeks channel (private).
+    ///
+    /// Args:
+    ///     ccy: Currency filter (optional)
+    #[pyo3(signature = (ccy=None))]
+    fn subscribe_account_greeks<'py>(
+        &self,
+        py: Python<'py>,
+        ccy: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::AccountGreeks { ccy })
+    }
+
+    /// Subscribe to auto-deleveraging warning channel (public).
+    ///
+    /// Args:
+    ///     inst_type: Instrument type (SWAP, FUTURES, OPTION)
+    ///     inst_family: Instrument family (optional)
+    #[pyo3(signature = (inst_type, inst_family=None))]
+    fn subscribe_adl_warning<'py>(
+        &self,
+        py: Python<'py>,
+        inst_type: String,
+        inst_family: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let channel = Channel::AdlWarning {
+            inst_type,
+            inst_family,
+        };
+        self.subscribe_channel(py, channel)
+    }
+
+    /// Subscribe to deposit info channel (business connection, requires login).
+    ///
+    /// Args:
+    ///     ccy: Currency filter (optional)
+    #[pyo3(signature = (ccy=None))]
+    fn subscribe_deposit_info<'py>(
+        &self,
+        py: Python<'py>,
+        ccy: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::DepositInfo { ccy })
+    }
+
+    /// Subscribe to withdrawal info channel (business connection, requires login).
+    ///
+    /// Args:
+    ///     ccy: Currency filter (optional)
+    #[pyo3(signature = (ccy=None))]
+    fn subscribe_withdrawal_info<'py>(
+        &self,
+        py: Python<'py>,
+        ccy: Option<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.subscribe_channel(py, Channel::WithdrawalInfo { ccy })
+    }
+
     /// Subscribe to positions channel (private).
     ///
     /// Args:
@@ -819,7 +1051,11 @@ impl PyWsClient {
     fn __repr__(&self) -> String {
         format!(
             "WsClient(type='{}')",
-            if self.is_private { "private" } else { "public" }
+            match self.conn_type {
+                ConnectionType::Public => "public",
+                ConnectionType::Private => "private",
+                ConnectionType::Business => "business",
+            }
         )
     }
 
@@ -834,17 +1070,55 @@ impl PyWsClient {
     }
 }
 
+impl PyWsClient {
+    /// Subscribe to a single channel on the wrapped client.
+    fn subscribe_channel<'py>(
+        &self,
+        py: Python<'py>,
+        channel: Channel,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let client = Arc::clone(&self.client);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            client
+                .lock()
+                .await
+                .subscribe(vec![channel])
+                .await
+                .map_err(to_py_err)
+        })
+    }
+}
+
+/// Parse a candle interval such as "1H" or "1Dutc".
+fn parse_bar(interval: &str) -> PyResult<Bar> {
+    Bar::from_api_str(interval).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Invalid interval: {}. Valid values: {}",
+            interval,
+            Bar::ALL.map(|bar| bar.as_str()).join(", ")
+        ))
+    })
+}
+
 /// Convert WsMessage to Python dict.
 fn ws_message_to_py(py: Python<'_>, msg: WsMessage) -> PyResult<Py<PyAny>> {
     let dict = PyDict::new(py);
 
     match msg {
-        WsMessage::Data { channel, arg, data } => {
+        WsMessage::Data {
+            channel,
+            arg,
+            data,
+            action,
+        } => {
             dict.set_item("type", "data")?;
             dict.set_item("channel", channel)?;
             dict.set_item("arg", arg.to_string())?;
             let data_list: Vec<String> = data.iter().map(|v| v.to_string()).collect();
             dict.set_item("data", data_list)?;
+            if let Some(a) = action {
+                dict.set_item("action", a)?;
+            }
         }
         WsMessage::Event {
             event,
@@ -868,6 +1142,21 @@ fn ws_message_to_py(py: Python<'_>, msg: WsMessage) -> PyResult<Py<PyAny>> {
                 dict.set_item("connId", id)?;
             }
         }
+        WsMessage::Response {
+            id,
+            op,
+            code,
+            msg,
+            data,
+        } => {
+            dict.set_item("type", "response")?;
+            dict.set_item("id", id)?;
+            dict.set_item("op", op)?;
+            dict.set_item("code", code)?;
+            dict.set_item("msg", msg)?;
+            let data_list: Vec<String> = data.iter().map(|v| v.to_string()).collect();
+            dict.set_item("data", data_list)?;
+        }
         WsMessage::Pong => {
             dict.set_item("type", "pong")?;
         }
@@ -887,6 +1176,17 @@ fn ws_message_to_py(py: Python<'_>, msg: WsMessage) -> PyResult<Py<PyAny>> {
             dict.set_item("code", code)?;
             dict.set_item("msg", msg)?;
         }
+        WsMessage::Reconnecting { attempt } => {
+            dict.set_item("type", "reconnecting")?;
+            dict.set_item("attempt", attempt)?;
+        }
+        WsMessage::Reconnected => {
+            dict.set_item("type", "reconnected")?;
+        }
+        WsMessage::SubscriptionsRestored { count } => {
+            dict.set_item("type", "subscriptions_restored")?;
+            dict.set_item("count", count)?;
+        }
         WsMessage::Unknown(text) => {
             dict.set_item("type", "unknown")?;
             dict.set_item("raw", text)?;
@@ -912,6 +1212,7 @@ mod tests {
                 channel: "tickers".to_string(),
                 arg: arg_value.clone(),
                 data: vec![json!({"px":"1.1"}), json!(3)],
+                action: None,
             };
 
             let obj = ws_message_to_py(py, msg).expect("转换成功");
@@ -1057,6 +1358,54 @@ mod tests {
         });
     }
 
+    #[test]
+    fn ws_message_to_py_maps_reconnect_lifecycle() {
+        Python::attach(|py| {
+            let obj =
+                ws_message_to_py(py, WsMessage::Reconnecting { attempt: 2 }).expect("转换成功");
+            let dict = obj.bind(py).cast::<PyDict>().expect("应为字典");
+            assert_eq!(
+                dict.get_item("type")
+                    .expect("读取 type 失败")
+                    .expect("需包含 type")
+                    .extract::<String>()
+                    .unwrap(),
+                "reconnecting"
+            );
+            assert_eq!(
+                dict.get_item("attempt")
+                    .expect("读取 attempt 失败")
+                    .expect("需包含 attempt")
+                    .extract::<u32>()
+                    .unwrap(),
+                2
+            );
+
+            let obj = ws_message_to_py(py, WsMessage::SubscriptionsRestored { count: 3 })
+                .expect("转换成功");
+            let dict = obj.bind(py).cast::<PyDict>().expect("应为字典");
+            assert_eq!(
+                dict.get_item("count")
+                    .expect("读取 count 失败")
+                    .expect("需包含 count")
+                    .extract::<usize>()
+                    .unwrap(),
+                3
+            );
+
+            let obj = ws_message_to_py(py, WsMessage::Reconnected).expect("转换成功");
+            let dict = obj.bind(py).cast::<PyDict>().expect("应为字典");
+            assert_eq!(
+                dict.get_item("type")
+                    .expect("读取 type 失败")
+                    .expect("需包含 type")
+                    .extract::<String>()
+                    .unwrap(),
+                "reconnected"
+            );
+        });
+    }
+
     #[test]
     fn ws_message_to_py_maps_channel_conn_count() {
         Python::attach(|py| {
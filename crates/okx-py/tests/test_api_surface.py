@@ -45,10 +45,25 @@ def test_ws_client_has_subscription_methods():
         "subscribe_orderbook",
         "subscribe_trades",
         "subscribe_candles",
+        "subscribe_mark_price_candles",
+        "subscribe_index_candles",
+        "subscribe_open_interest",
+        "subscribe_price_limit",
+        "subscribe_liquidation_orders",
+        "subscribe_opt_summary",
+        "subscribe_estimated_price",
+        "subscribe_instruments",
+        "subscribe_status",
+        "subscribe_economic_calendar",
         "subscribe_account",
         "subscribe_positions",
         "subscribe_orders",
         "subscribe_orders_algo",
+        "subscribe_liquidation_warning",
+        "subscribe_account_greeks",
+        "subscribe_adl_warning",
+        "subscribe_deposit_info",
+        "subscribe_withdrawal_info",
         "subscribe_balance_and_position",
         "recv",
         "is_connected",
@@ -4,7 +4,9 @@
 #![allow(missing_docs)]
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use okx_core::types::{Balance, Instrument, Order, PlaceOrderRequest, Ticker};
+use okx_core::types::{
+    Balance, Instrument, OrdType, Order, PlaceOrderRequest, Side, TdMode, Ticker,
+};
 
 fn bench_deserialization(c: &mut Criterion) {
     // 测试 Ticker 反序列化
@@ -168,9 +170,9 @@ fn bench_deserialization(c: &mut Criterion) {
 fn bench_serialization(c: &mut Criterion) {
     let order_params = PlaceOrderRequest {
         inst_id: "BTC-USDT".to_string(),
-        td_mode: "cash".to_string(),
-        side: "buy".to_string(),
-        ord_type: "limit".to_string(),
+        td_mode: TdMode::Cash,
+        side: Side::Buy,
+        ord_type: OrdType::Limit,
         sz: "0.1".to_string(),
         px: Some("50000".to_string()),
         ccy: None,
@@ -3,16 +3,20 @@
 //! Source: OKX API v5 Order Book Trading REST API
 //! - <https://www.okx.com/docs-v5/en/#order-book-trading-trade>
 
+use std::future::Future;
+
+use futures_util::{stream, StreamExt};
 use serde::Serialize;
 use serde_json::Value;
 
 use okx_core::{
     types::{
-        AlgoOrder, AmendOrderRequest, AmendOrderResponse, CancelAlgoOrderRequest,
-        CancelAlgoOrderResponse, CancelOrderRequest, CancelOrderResponse, Fill, Order,
+        AlgoOrder, AmendOrderRequest, AmendOrderResponse, BatchResult, CancelAlgoOrderRequest,
+        CancelAlgoOrderResponse, CancelOrderRequest, CancelOrderResponse, FailedChunk, Fill, Order,
         PlaceAlgoOrderRequest, PlaceAlgoOrderResponse, PlaceOrderRequest, PlaceOrderResponse,
+        SplitBatchResult,
     },
-    Result,
+    OkxError, Result,
 };
 
 use crate::OkxRestClient;
@@ -72,6 +76,12 @@ pub mod endpoints {
     pub const ONE_CLICK_REPAY_HISTORY_V2: &str = "/api/v5/trade/one-click-repay-history-v2";
 }
 
+/// Maximum number of orders per batch request.
+pub const MAX_BATCH_SIZE: usize = 20;
+
+/// Maximum number of batch requests in flight for the `*_split` helpers.
+pub const MAX_CONCURRENT_BATCHES: usize = 4;
+
 /// Query parameters for get_order.
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -300,6 +310,9 @@ pub trait TradeApi {
 
     /// Place multiple orders (up to 20).
     ///
+    /// Partial failures (top-level code "1"/"2") are returned as a [`BatchResult`]
+    /// with per-item `sCode`/`sMsg` instead of an error.
+    ///
     /// ## API Details
     ///
     /// - Endpoint: POST /api/v5/trade/batch-orders
@@ -308,7 +321,7 @@ pub trait TradeApi {
     fn place_batch_orders(
         &self,
         requests: Vec<PlaceOrderRequest>,
-    ) -> impl std::future::Future<Output = Result<Vec<PlaceOrderResponse>>> + Send;
+    ) -> impl std::future::Future<Output = Result<BatchResult<PlaceOrderResponse>>> + Send;
 
     /// Cancel an order.
     ///
@@ -324,6 +337,8 @@ pub trait TradeApi {
 
     /// Cancel multiple orders (up to 20).
     ///
+    /// Partial failures are returned as a [`BatchResult`] instead of an error.
+    ///
     /// ## API Details
     ///
     /// - Endpoint: POST /api/v5/trade/cancel-batch-orders
@@ -332,7 +347,7 @@ pub trait TradeApi {
     fn cancel_batch_orders(
         &self,
         requests: Vec<CancelOrderRequest>,
-    ) -> impl std::future::Future<Output = Result<Vec<CancelOrderResponse>>> + Send;
+    ) -> impl std::future::Future<Output = Result<BatchResult<CancelOrderResponse>>> + Send;
 
     /// Amend an order.
     ///
@@ -348,6 +363,8 @@ pub trait TradeApi {
 
     /// Amend multiple orders in a single request.
     ///
+    /// Partial failures are returned as a [`BatchResult`] instead of an error.
+    ///
     /// ## API Details
     ///
     /// - Endpoint: POST /api/v5/trade/amend-batch-orders
@@ -356,7 +373,33 @@ pub trait TradeApi {
     fn amend_batch_orders(
         &self,
         requests: Vec<AmendOrderRequest>,
-    ) -> impl std::future::Future<Output = Result<Vec<AmendOrderResponse>>> + Send;
+    ) -> impl std::future::Future<Output = Result<BatchResult<AmendOrderResponse>>> + Send;
+
+    /// Place any number of orders, split into batches of [`MAX_BATCH_SIZE`].
+    ///
+    /// Up to [`MAX_CONCURRENT_BATCHES`] batches are in flight at once; enable
+    /// `OkxRestClient::with_rate_limiter` to also stay within the per-instrument
+    /// order limits. A failing batch does not abort the others.
+    fn place_batch_orders_split(
+        &self,
+        requests: Vec<PlaceOrderRequest>,
+    ) -> impl std::future::Future<Output = SplitBatchResult<PlaceOrderResponse>> + Send;
+
+    /// Cancel any number of orders, split into batches of [`MAX_BATCH_SIZE`].
+    ///
+    /// See [`TradeApi::place_batch_orders_split`] for concurrency and failure handling.
+    fn cancel_batch_orders_split(
+        &self,
+        requests: Vec<CancelOrderRequest>,
+    ) -> impl std::future::Future<Output = SplitBatchResult<CancelOrderResponse>> + Send;
+
+    /// Amend any number of orders, split into batches of [`MAX_BATCH_SIZE`].
+    ///
+    /// See [`TradeApi::place_batch_orders_split`] for concurrency and failure handling.
+    fn amend_batch_orders_split(
+        &self,
+        requests: Vec<AmendOrderRequest>,
+    ) -> impl std::future::Future<Output = SplitBatchResult<AmendOrderResponse>> + Send;
 
     /// Get order details.
     ///
@@ -656,8 +699,11 @@ impl TradeApi for OkxRestClient {
     async fn place_batch_orders(
         &self,
         requests: Vec<PlaceOrderRequest>,
-    ) -> Result<Vec<PlaceOrderResponse>> {
-        self.post(endpoints::PLACE_BATCH_ORDERS, &requests).await
+    ) -> Result<BatchResult<PlaceOrderResponse>> {
+        let response = self
+            .post_raw(endpoints::PLACE_BATCH_ORDERS, &requests)
+            .await?;
+        BatchResult::from_response(serde_json::from_value(response)?)
     }
 
     async fn cancel_order(&self, request: CancelOrderRequest) -> Result<Vec<CancelOrderResponse>> {
@@ -667,8 +713,11 @@ impl TradeApi for OkxRestClient {
     async fn cancel_batch_orders(
         &self,
         requests: Vec<CancelOrderRequest>,
-    ) -> Result<Vec<CancelOrderResponse>> {
-        self.post(endpoints::CANCEL_BATCH_ORDERS, &requests).await
+    ) -> Result<BatchResult<CancelOrderResponse>> {
+        let response = self
+            .post_raw(endpoints::CANCEL_BATCH_ORDERS, &requests)
+            .await?;
+        BatchResult::from_response(serde_json::from_value(response)?)
     }
 
     async fn amend_order(&self, request: AmendOrderRequest) -> Result<Vec<AmendOrderResponse>> {
@@ -678,8 +727,32 @@ impl TradeApi for OkxRestClient {
     async fn amend_batch_orders(
         &self,
         requests: Vec<AmendOrderRequest>,
-    ) -> Result<Vec<AmendOrderResponse>> {
-        self.post(endpoints::AMEND_BATCH_ORDERS, &requests).await
+    ) -> Result<BatchResult<AmendOrderResponse>> {
+        let response = self
+            .post_raw(endpoints::AMEND_BATCH_ORDERS, &requests)
+            .await?;
+        BatchResult::from_response(serde_json::from_value(response)?)
+    }
+
+    async fn place_batch_orders_split(
+        &self,
+        requests: Vec<PlaceOrderRequest>,
+    ) -> SplitBatchResult<PlaceOrderResponse> {
+        split_batch(requests, |chunk| self.place_batch_orders(chunk)).await
+    }
+
+    async fn cancel_batch_orders_split(
+        &self,
+        requests: Vec<CancelOrderRequest>,
+    ) -> SplitBatchResult<CancelOrderResponse> {
+        split_batch(requests, |chunk| self.cancel_batch_orders(chunk)).await
+    }
+
+    async fn amend_batch_orders_split(
+        &self,
+        requests: Vec<AmendOrderRequest>,
+    ) -> SplitBatchResult<AmendOrderResponse> {
+        split_batch(requests, |chunk| self.amend_batch_orders(chunk)).await
     }
 
     async fn get_order(&self, params: GetOrderParams) -> Result<Vec<Order>> {
@@ -787,3 +860,49 @@ impl TradeApi for OkxRestClient {
             .await
     }
 }
+
+/// 按 `MAX_BATCH_SIZE` 切分请求并发发送，按输入顺序合并结果。
+async fn split_batch<R, T, F, Fut>(requests: Vec<R>, send: F) -> SplitBatchResult<T>
+where
+    F: Fn(Vec<R>) -> Fut,
+    Fut: Future<Output = Result<BatchResult<T>>>,
+{
+    let mut chunks = Vec::new();
+    let mut requests = requests.into_iter().peekable();
+    let mut offset = 0;
+    while requests.peek().is_some() {
+        let chunk: Vec<R> = requests.by_ref().take(MAX_BATCH_SIZE).collect();
+        let range = offset..offset + chunk.len();
+        offset = range.end;
+        chunks.push((range, chunk));
+    }
+
+    // buffered 保持输入顺序
+    let mut responses = stream::iter(chunks)
+        .map(|(range, chunk)| {
+            let response = send(chunk);
+            async move { (range, response.await) }
+        })
+        .buffered(MAX_CONCURRENT_BATCHES);
+
+    let mut merged = SplitBatchResult {
+        items: Vec::new(),
+        failed_chunks: Vec::new(),
+    };
+    while let Some((range, response)) = responses.next().await {
+        match response {
+            // 条目数与请求数不符时无法按下标对齐，整批记为失败
+            Ok(batch) if batch.items.len() != range.len() => {
+                let error = OkxError::Other(format!(
+                    "batch returned {} items for {} requests",
+                    batch.items.len(),
+                    range.len()
+                ));
+                merged.failed_chunks.push(FailedChunk { range, error });
+            }
+            Ok(batch) => merged.items.extend(batch.items),
+            Err(error) => merged.failed_chunks.push(FailedChunk { range, error }),
+        }
+    }
+    merged
+}
@@ -3,90 +3,53 @@
 //! 参考：官方 Python SDK 的组织方式
 //! - <https://github.com/okxapi/python-okx>
 
-use std::{collections::HashMap, future::Future, pin::Pin, time::Duration};
+use std::{fmt, sync::Arc, time::Duration};
 
 use reqwest::{Client, Method, Proxy, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 
-use okx_core::{types::ApiResponse, Config, OkxError, Result, Signer};
+use okx_core::{signer, types::ApiResponse, Config, OkxError, Result, Signer};
 
+use crate::middleware::{Middleware, Next, RestRequest};
+use crate::rate_limit::{request_inst_ids, RateLimiter};
+use crate::transport::{HttpTransport, ReqwestTransport, TransportResponse};
 use crate::ResponseMeta;
 
-/// Transport response: (status, body, headers)
-type TransportResponse = (StatusCode, String, HashMap<String, String>);
-type TransportFuture = Pin<Box<dyn Future<Output = Result<TransportResponse>> + Send>>;
-
-/// HTTP 传输层抽象（便于在无网络/受限环境中做纯内存测试）。
-trait HttpTransport: Clone + Send + Sync + 'static {
-    fn send(
-        &self,
-        method: Method,
-        url: String,
-        headers: Vec<(&'static str, String)>,
-        body: Option<String>,
-    ) -> TransportFuture;
+/// HTTP 5xx 与 429 视为瞬时失败。
+fn is_retryable_status(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
 }
 
-#[derive(Debug, Clone)]
-struct ReqwestTransport {
-    client: Client,
-}
-
-impl HttpTransport for ReqwestTransport {
-    fn send(
-        &self,
-        method: Method,
-        url: String,
-        headers: Vec<(&'static str, String)>,
-        body: Option<String>,
-    ) -> TransportFuture {
-        let client = self.client.clone();
-        Box::pin(async move {
-            let mut request = client.request(method, &url);
-
-            for (name, value) in headers {
-                request = request.header(name, value);
-            }
-
-            if let Some(body) = body {
-                request = request.body(body);
-            }
-
-            let response = request
-                .send()
-                .await
-                .map_err(|e| OkxError::Http(e.to_string()))?;
-
-            let status = response.status();
-
-            // Extract headers
-            let resp_headers: HashMap<String, String> = response
-                .headers()
-                .iter()
-                .filter_map(|(k, v)| {
-                    v.to_str()
-                        .ok()
-                        .map(|val| (k.as_str().to_lowercase(), val.to_string()))
-                })
-                .collect();
-
-            let text = response
-                .text()
-                .await
-                .map_err(|e| OkxError::Http(format!("Failed to read response: {e}")))?;
+/// 判断请求重试是否安全：GET 幂等；POST 仅当每个订单都带有 `clOrdId` 时才可重试。
+fn is_retry_safe(method: &Method, body: Option<&str>) -> bool {
+    if *method == Method::GET {
+        return true;
+    }
+    if *method != Method::POST {
+        return false;
+    }
 
-            Ok((status, text, resp_headers))
-        })
+    let has_cl_ord_id = |item: &Value| {
+        item.get("clOrdId")
+            .and_then(Value::as_str)
+            .is_some_and(|id| !id.is_empty())
+    };
+    match body.and_then(|b| serde_json::from_str::<Value>(b).ok()) {
+        Some(Value::Array(items)) => !items.is_empty() && items.iter().all(has_cl_ord_id),
+        Some(item) => has_cl_ord_id(&item),
+        None => false,
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 struct OkxRestClientInner<T: HttpTransport> {
     transport: T,
     config: Config,
     signer: Signer,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    middleware: Vec<Arc<dyn Middleware>>,
 }
 
 impl<T: HttpTransport> OkxRestClientInner<T> {
@@ -96,6 +59,8 @@ impl<T: HttpTransport> OkxRestClientInner<T> {
             transport,
             config,
             signer,
+            rate_limiter: None,
+            middleware: Vec::new(),
         }
     }
 
@@ -103,6 +68,123 @@ impl<T: HttpTransport> OkxRestClientInner<T> {
         &self.config
     }
 
+    /// 经过限速器后交给传输层发送；按 `RetryPolicy` 重试瞬时失败。
+    ///
+    /// 返回最后一次响应及已尝试次数。
+    async fn send(
+        &self,
+        method: Method,
+        url: String,
+        headers: Vec<(&'static str, String)>,
+        body: Option<String>,
+    ) -> Result<(TransportResponse, u32)> {
+        let max_attempts = match self.config.retry_policy() {
+            Some(policy) if is_retry_safe(&method, body.as_deref()) => policy.max_attempts(),
+            _ => 1,
+        };
+
+        let mut attempt = 1;
+        let mut headers = headers;
+        loop {
+            let result = self
+                .send_once(method.clone(), url.clone(), headers.clone(), body.clone())
+                .await;
+
+            let retry_after = match &result {
+                Ok((status, _, resp_headers)) if is_retryable_status(*status) => {
+                    Some(ResponseMeta::new(status.as_u16(), resp_headers.clone()).retry_after())
+                }
+                Err(OkxError::Http(_) | OkxError::Timeout) => Some(None),
+                _ => None,
+            };
+
+            let Some(retry_after) = retry_after.filter(|_| attempt < max_attempts) else {
+                return match result {
+                    Ok((status, text, _)) if attempt > 1 && is_retryable_status(status) => {
+                        Err(OkxError::http_status(status.as_u16(), text).with_attempts(attempt))
+                    }
+                    Ok(response) => Ok((response, attempt)),
+                    Err(err) => Err(err.with_attempts(attempt)),
+                };
+            };
+
+            // 服务端给出的延迟同样受 max_delay 约束，防止重置时间戳被当作秒数长时间休眠
+            let delay = match (retry_after, self.config.retry_policy()) {
+                (Some(delay), Some(policy)) => delay.min(policy.max_delay()),
+                (None, Some(policy)) => policy.backoff(attempt),
+                (Some(_) | None, None) => Duration::ZERO,
+            };
+            warn!(
+                "Retrying {} {} in {:?} (attempt {}/{})",
+                method,
+                url,
+                delay,
+                attempt + 1,
+                max_attempts
+            );
+            tokio::time::sleep(delay).await;
+
+            attempt += 1;
+            headers = self.resign(&method, &url, headers, body.as_deref());
+        }
+    }
+
+    /// 单次发送：先经过限速器，再经中间件链交给传输层。
+    async fn send_once(
+        &self,
+        method: Method,
+        url: String,
+        headers: Vec<(&'static str, String)>,
+        body: Option<String>,
+    ) -> Result<TransportResponse> {
+        if let Some(limiter) = &self.rate_limiter {
+            let request_path = self.extract_request_path(&url);
+            let path = request_path
+                .split_once('?')
+                .map_or(request_path.as_str(), |(p, _)| p);
+            let inst_ids = request_inst_ids(&url, body.as_deref());
+            limiter.acquire(&method, path, &inst_ids).await?;
+        }
+
+        if self.middleware.is_empty() {
+            return self.transport.send(method, url, headers, body).await;
+        }
+
+        let request = RestRequest {
+            path: self.extract_request_path(&url),
+            method,
+            url,
+            headers,
+            body,
+        };
+        Next::new(&self.transport, &self.middleware)
+            .run(request)
+            .await
+    }
+
+    /// 重试前重新签名，避免时间戳过期；公有请求头原样返回。
+    fn resign(
+        &self,
+        method: &Method,
+        url: &str,
+        headers: Vec<(&'static str, String)>,
+        body: Option<&str>,
+    ) -> Vec<(&'static str, String)> {
+        if !headers
+            .iter()
+            .any(|(name, _)| *name == signer::headers::OK_ACCESS_SIGN)
+        {
+            return headers;
+        }
+
+        self.signer.generate_headers(
+            method.as_str(),
+            &self.extract_request_path(url),
+            body.unwrap_or(""),
+            self.config.is_simulated(),
+        )
+    }
+
     async fn send_and_parse<TOut: DeserializeOwned>(
         &self,
         method: Method,
@@ -110,9 +192,10 @@ impl<T: HttpTransport> OkxRestClientInner<T> {
         headers: Vec<(&'static str, String)>,
         body: Option<String>,
     ) -> Result<Vec<TOut>> {
-        let (status, text, _) = self.transport.send(method, url, headers, body).await?;
+        let ((status, text, _), attempts) = self.send(method, url, headers, body).await?;
         debug!("Response status={} body={}", status, text);
         self.parse_body(status, &text)
+            .map_err(|e| e.with_attempts(attempts))
     }
 
     async fn send_and_parse_raw(
@@ -122,9 +205,10 @@ impl<T: HttpTransport> OkxRestClientInner<T> {
         headers: Vec<(&'static str, String)>,
         body: Option<String>,
     ) -> Result<Value> {
-        let (status, text, _) = self.transport.send(method, url, headers, body).await?;
+        let ((status, text, _), attempts) = self.send(method, url, headers, body).await?;
         debug!("Raw response status={} body={}", status, text);
         self.parse_body_raw(status, &text)
+            .map_err(|e| e.with_attempts(attempts))
     }
 
     async fn send_and_parse_with_meta<TOut: DeserializeOwned>(
@@ -134,10 +218,13 @@ impl<T: HttpTransport> OkxRestClientInner<T> {
         headers: Vec<(&'static str, String)>,
         body: Option<String>,
     ) -> Result<(Vec<TOut>, ResponseMeta)> {
-        let (status, text, resp_headers) = self.transport.send(method, url, headers, body).await?;
+        let ((status, text, resp_headers), attempts) =
+            self.send(method, url, headers, body).await?;
         debug!("Response status={} body={}", status, text);
         let meta = ResponseMeta::new(status.as_u16(), resp_headers);
-        let data = self.parse_body(status, &text)?;
+        let data = self
+            .parse_body(status, &text)
+            .map_err(|e| e.with_attempts(attempts))?;
         Ok((data, meta))
     }
 
@@ -149,10 +236,13 @@ impl<T: HttpTransport> OkxRestClientInner<T> {
         headers: Vec<(&'static str, String)>,
         body: Option<String>,
     ) -> Result<(Value, ResponseMeta)> {
-        let (status, text, resp_headers) = self.transport.send(method, url, headers, body).await?;
+        let ((status, text, resp_headers), attempts) =
+            self.send(method, url, headers, body).await?;
         debug!("Raw response status={} body={}", status, text);
         let meta = ResponseMeta::new(status.as_u16(), resp_headers);
-        let data = self.parse_body_raw(status, &text)?;
+        let data = self
+            .parse_body_raw(status, &text)
+            .map_err(|e| e.with_attempts(attempts))?;
         Ok((data, meta))
     }
 
@@ -357,9 +447,19 @@ impl<T: HttpTransport> OkxRestClientInner<T> {
 /// - 构造 URL（含 query 编码）
 /// - 生成签名请求头（私有接口）
 /// - 发送请求并解析 OKX 标准响应
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct OkxRestClient {
-    inner: OkxRestClientInner<ReqwestTransport>,
+    inner: OkxRestClientInner<Arc<dyn HttpTransport>>,
+}
+
+impl fmt::Debug for OkxRestClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OkxRestClient")
+            .field("config", &self.inner.config)
+            .field("rate_limiter", &self.inner.rate_limiter)
+            .field("middleware", &self.inner.middleware.len())
+            .finish_non_exhaustive()
+    }
 }
 
 impl OkxRestClient {
@@ -383,12 +483,45 @@ impl OkxRestClient {
     /// 使用自定义 `reqwest::Client` 创建 REST 客户端。
     #[must_use]
     pub fn with_http_client(config: Config, http: Client) -> Self {
-        let transport = ReqwestTransport { client: http };
+        Self::with_transport(config, ReqwestTransport::new(http))
+    }
+
+    /// 使用自定义传输层创建 REST 客户端。
+    ///
+    /// 签名、限速与重试仍由客户端完成，传输层只负责收发；
+    /// 可用于注入录制/回放传输层做离线测试，或替换为自定义 HTTP 栈。
+    #[must_use]
+    pub fn with_transport(config: Config, transport: impl HttpTransport) -> Self {
         Self {
-            inner: OkxRestClientInner::new(config, transport),
+            inner: OkxRestClientInner::new(config, Arc::new(transport)),
         }
     }
 
+    /// 启用客户端限速器。
+    ///
+    /// 所有请求在发出前按端点规则排队或拒绝；克隆出的客户端共享同一限速器。
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.inner.rate_limiter = Some(Arc::new(limiter));
+        self
+    }
+
+    /// 追加请求/响应中间件。
+    ///
+    /// 中间件按添加顺序由外到内执行，位于签名与限速之后、传输层之前；
+    /// 每次重试都会重新经过中间件链。
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: impl Middleware) -> Self {
+        self.inner.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// 读取限速器（如已启用）。
+    #[must_use]
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.inner.rate_limiter.as_deref()
+    }
+
     /// 读取配置。
     #[must_use]
     pub fn config(&self) -> &Config {
@@ -503,10 +636,13 @@ impl OkxRestClient {
 mod tests {
     use super::*;
 
-    use okx_core::{signer::headers, Credentials};
+    use crate::transport::TransportFuture;
+    use crate::MiddlewareFuture;
+    use crate::PublicApi;
+    use okx_core::{signer::headers, Credentials, RetryPolicy};
     use serde::{Deserialize, Serialize};
     use std::{
-        collections::VecDeque,
+        collections::{HashMap, VecDeque},
         sync::{Arc, Mutex},
     };
 
@@ -557,6 +693,22 @@ mod tests {
                 .push_back(Ok((status, body.into(), HashMap::new())));
         }
 
+        fn push_ok_with_headers(
+            &self,
+            status: StatusCode,
+            body: impl Into<String>,
+            headers: &[(&str, &str)],
+        ) {
+            let headers = headers
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect();
+            self.responses
+                .lock()
+                .expect("锁 responses 失败")
+                .push_back(Ok((status, body.into(), headers)));
+        }
+
         fn push_err(&self, err: OkxError) {
             self.responses
                 .lock()
@@ -825,6 +977,312 @@ mod tests {
         assert_eq!(raw["code"], "51000");
     }
 
+    #[tokio::test]
+    async fn rate_limiter_rejects_before_transport_is_called() {
+        use crate::{RateLimitMode, RateLimitRule, RateLimitScope};
+
+        let transport = FakeTransport::default();
+        transport.push_ok(StatusCode::OK, r#"{"code":"0","msg":"","data":[]}"#);
+
+        let mut client = inner_client_with_base("https://example.com", transport.clone());
+        client.rate_limiter = Some(Arc::new(RateLimiter::new(RateLimitMode::Reject).with_rule(
+            Method::POST,
+            "/api/v5/trade/order",
+            RateLimitRule::new(1, Duration::from_secs(2), RateLimitScope::Instrument),
+        )));
+
+        let body = serde_json::json!({"instId": "BTC-USDT"});
+        client
+            .post::<DummyData, _>("/api/v5/trade/order", &body)
+            .await
+            .expect("首个请求应放行");
+        let err = client
+            .post::<DummyData, _>("/api/v5/trade/order", &body)
+            .await
+            .expect_err("超过窗口应被本地拒绝");
+        assert!(matches!(err, OkxError::RateLimitExceeded { .. }));
+        assert_eq!(transport.take_requests().len(), 1);
+    }
+
+    fn retrying_client(transport: FakeTransport) -> OkxRestClientInner<FakeTransport> {
+        let cfg = Config::new(Credentials::new("k", "s", "p"))
+            .with_rest_url("https://example.com")
+            .with_retry_policy(
+                RetryPolicy::new(3)
+                    .with_base_delay(Duration::from_millis(100))
+                    .with_jitter(false),
+            );
+        OkxRestClientInner::new(cfg, transport)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn get_is_retried_on_transient_failures_and_resigned() {
+        let transport = FakeTransport::default();
+        transport.push_err(OkxError::Http("connection reset".into()));
+        transport.push_ok(StatusCode::SERVICE_UNAVAILABLE, "busy");
+        transport.push_ok(
+            StatusCode::OK,
+            r#"{"code":"0","msg":"","data":[{"value":7}]}"#,
+        );
+
+        let client = retrying_client(transport.clone());
+        let started = tokio::time::Instant::now();
+        let data = client
+            .get::<DummyData, ()>("/api/v5/account/balance", None)
+            .await
+            .expect("第三次尝试应成功");
+        assert_eq!(data[0].value, 7);
+        // 退避 100ms + 200ms
+        assert_eq!(started.elapsed(), Duration::from_millis(300));
+
+        let requests = transport.take_requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests
+            .iter()
+            .all(|r| r.headers.iter().any(|(k, _)| *k == headers::OK_ACCESS_SIGN)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_after_header_overrides_backoff() {
+        let transport = FakeTransport::default();
+        transport.push_ok_with_headers(
+            StatusCode::TOO_MANY_REQUESTS,
+            "slow down",
+            &[("retry-after", "2")],
+        );
+        transport.push_ok(StatusCode::OK, r#"{"code":"0","msg":"","data":[]}"#);
+
+        let client = retrying_client(transport.clone());
+        let started = tokio::time::Instant::now();
+        client
+            .get_public::<DummyData, ()>("/api/v5/public/time", None)
+            .await
+            .expect("重试后应成功");
+        assert_eq!(started.elapsed(), Duration::from_secs(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn server_delay_is_capped_by_max_delay() {
+        let transport = FakeTransport::default();
+        transport.push_ok_with_headers(
+            StatusCode::TOO_MANY_REQUESTS,
+            "slow down",
+            &[("retry-after", "86400")],
+        );
+        transport.push_ok_with_headers(
+            StatusCode::TOO_MANY_REQUESTS,
+            "slow down",
+            &[("x-ratelimit-reset", "1760000000")],
+        );
+        transport.push_ok(StatusCode::OK, r#"{"code":"0","msg":"","data":[]}"#);
+
+        let client = retrying_client(transport.clone());
+        let started = tokio::time::Instant::now();
+        client
+            .get_public::<DummyData, ()>("/api/v5/public/time", None)
+            .await
+            .expect("重试后应成功");
+        // 两次重试均被截断为默认的 5 秒上限
+        assert_eq!(started.elapsed(), Duration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn exhausted_retries_report_attempt_count() {
+        let transport = FakeTransport::default();
+        for _ in 0..3 {
+            transport.push_ok(StatusCode::BAD_GATEWAY, "bad gateway");
+        }
+
+        let client = retrying_client(transport.clone());
+        let err = client
+            .get_public::<DummyData, ()>("/api/v5/public/time", None)
+            .await
+            .expect_err("重试耗尽应返回错误");
+        assert_eq!(err.attempts(), 3);
+        assert!(err.is_http_status(502));
+        assert_eq!(transport.take_requests().len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn post_is_retried_only_with_cl_ord_id() {
+        let transport = FakeTransport::default();
+        transport.push_ok(StatusCode::INTERNAL_SERVER_ERROR, "oops");
+
+        let client = retrying_client(transport.clone());
+        let body = serde_json::json!({"instId": "BTC-USDT", "sz": "1"});
+        let err = client
+            .post::<DummyData, _>("/api/v5/trade/order", &body)
+            .await
+            .expect_err("无 clOrdId 的下单不应重试");
+        assert_eq!(err.attempts(), 1);
+        assert_eq!(transport.take_requests().len(), 1);
+
+        transport.push_ok(StatusCode::INTERNAL_SERVER_ERROR, "oops");
+        transport.push_ok(StatusCode::OK, r#"{"code":"0","msg":"","data":[]}"#);
+        let body = serde_json::json!([
+            {"instId": "BTC-USDT", "clOrdId": "a1"},
+            {"instId": "ETH-USDT", "clOrdId": "a2"}
+        ]);
+        client
+            .post::<DummyData, _>("/api/v5/trade/batch-orders", &body)
+            .await
+            .expect("带 clOrdId 的批量下单应重试成功");
+        assert_eq!(transport.take_requests().len(), 2);
+    }
+
+    #[test]
+    fn retry_safety_follows_method_and_cl_ord_id() {
+        assert!(is_retry_safe(&Method::GET, None));
+        assert!(is_retry_safe(&Method::POST, Some(r#"{"clOrdId":"x"}"#)));
+        assert!(!is_retry_safe(&Method::POST, Some(r#"{"clOrdId":""}"#)));
+        assert!(!is_retry_safe(
+            &Method::POST,
+            Some(r#"[{"clOrdId":"x"},{"instId":"y"}]"#)
+        ));
+        assert!(!is_retry_safe(&Method::POST, Some("[]")));
+        assert!(!is_retry_safe(&Method::DELETE, None));
+    }
+
+    #[tokio::test]
+    async fn with_transport_routes_api_calls_through_custom_transport() {
+        let transport = FakeTransport::default();
+        transport.push_ok(
+            StatusCode::OK,
+            r#"{"code":"0","msg":"","data":[{"ts":"1700000000000"}]}"#,
+        );
+
+        let cfg = Config::new(Credentials::new("k", "s", "p")).with_rest_url("https://example.com");
+        let client = OkxRestClient::with_transport(cfg, transport.clone());
+        let time = client.get_system_time().await.expect("应使用注入的传输层");
+        assert_eq!(time[0].ts, "1700000000000");
+
+        let req = transport.take_requests().pop().expect("应记录一次请求");
+        assert_eq!(req.url, "https://example.com/api/v5/public/time");
+    }
+
+    type AuditLog = Arc<Mutex<Vec<(Method, String, Option<String>, bool, StatusCode)>>>;
+
+    /// 注入请求头并记录请求与响应的中间件。
+    struct AuditMiddleware {
+        tag: &'static str,
+        log: AuditLog,
+    }
+
+    impl Middleware for AuditMiddleware {
+        fn handle<'a>(&'a self, mut request: RestRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+            Box::pin(async move {
+                let signed = request.header(headers::OK_ACCESS_SIGN).is_some();
+                let (method, path, body) = (
+                    request.method.clone(),
+                    request.path.clone(),
+                    request.body.clone(),
+                );
+                request.headers.push(("x-audit-tag", self.tag.to_string()));
+
+                let response = next.run(request).await?;
+                self.log
+                    .lock()
+                    .expect("锁 audit log 失败")
+                    .push((method, path, body, signed, response.0));
+                Ok(response)
+            })
+        }
+    }
+
+    /// 不访问传输层，直接返回固定响应的中间件。
+    struct CannedMiddleware;
+
+    impl Middleware for CannedMiddleware {
+        fn handle<'a>(&'a self, _request: RestRequest, _next: Next<'a>) -> MiddlewareFuture<'a> {
+            Box::pin(async {
+                Ok((
+                    StatusCode::OK,
+                    r#"{"code":"0","msg":"","data":[{"value":9}]}"#.to_string(),
+                    HashMap::new(),
+                ))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn middleware_sees_signed_requests_and_responses_for_all_variants() {
+        let transport = FakeTransport::default();
+        transport.push_ok(StatusCode::OK, r#"{"code":"1","msg":"partial","data":[]}"#);
+        transport.push_ok(
+            StatusCode::OK,
+            r#"{"code":"0","msg":"","data":[{"value":3}]}"#,
+        );
+
+        let log = AuditLog::default();
+        let cfg = Config::new(Credentials::new("k", "s", "p")).with_rest_url("https://example.com");
+        let client = OkxRestClient::with_transport(cfg, transport.clone())
+            .with_middleware(AuditMiddleware {
+                tag: "outer",
+                log: log.clone(),
+            })
+            .with_middleware(AuditMiddleware {
+                tag: "inner",
+                log: log.clone(),
+            });
+
+        let body = serde_json::json!({"instId": "BTC-USDT"});
+        let raw = client
+            .post_raw("/api/v5/trade/order", &body)
+            .await
+            .expect("raw 请求应成功");
+        assert_eq!(raw["code"], "1");
+        let params = QueryParams {
+            inst_id: "BTC-USDT".into(),
+            limit: None,
+        };
+        let (data, meta) = client
+            .get_with_meta::<DummyData, _>("/api/v5/account/positions", Some(&params))
+            .await
+            .expect("with_meta 请求应成功");
+        assert_eq!(data[0].value, 3);
+        assert_eq!(meta.status(), 200);
+
+        let requests = transport.take_requests();
+        let tags: Vec<_> = requests[0]
+            .headers
+            .iter()
+            .filter(|(k, _)| *k == "x-audit-tag")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(tags, ["outer", "inner"]);
+
+        let log = log.lock().expect("锁 audit log 失败");
+        assert_eq!(log.len(), 4);
+        assert_eq!(
+            log[0],
+            (
+                Method::POST,
+                "/api/v5/trade/order".to_string(),
+                Some(r#"{"instId":"BTC-USDT"}"#.to_string()),
+                true,
+                StatusCode::OK
+            )
+        );
+        assert_eq!(log[2].1, "/api/v5/account/positions?instId=BTC-USDT");
+        assert!(log[2].2.is_none());
+    }
+
+    #[tokio::test]
+    async fn middleware_can_short_circuit_the_transport() {
+        // FakeTransport 未配置返回值，若被调用会 panic
+        let transport = FakeTransport::default();
+        let cfg = Config::new(Credentials::new("k", "s", "p")).with_rest_url("https://example.com");
+        let client =
+            OkxRestClient::with_transport(cfg, transport.clone()).with_middleware(CannedMiddleware);
+
+        let data = client
+            .get::<DummyData, ()>("/api/v5/account/balance", None)
+            .await
+            .expect("应返回中间件的固定响应");
+        assert_eq!(data[0].value, 9);
+        assert!(transport.take_requests().is_empty());
+    }
+
     #[test]
     fn new_accepts_proxy_url_when_configured() {
         let cfg = Config::new(Credentials::new("k", "s", "p"))
@@ -30,11 +30,19 @@
 
 pub mod api;
 mod client;
+mod middleware;
+mod rate_limit;
 mod response_meta;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 mod time_sync;
+mod transport;
 
+pub use middleware::{Middleware, MiddlewareFuture, Next, RestRequest};
+pub use rate_limit::{RateLimitMode, RateLimitRule, RateLimitScope, RateLimiter};
 pub use response_meta::ResponseMeta;
 pub use time_sync::{TimeSync, TimeSyncHandle};
+pub use transport::{HttpTransport, ReqwestTransport, TransportFuture, TransportResponse};
 
 pub use api::account::AccountApi;
 pub use api::block_rfq::BlockRfqApi;
@@ -53,6 +61,9 @@ pub use api::trade::TradeApi;
 pub use api::trading_data::TradingDataApi;
 pub use client::OkxRestClient;
 
+// Re-export HTTP types used by `HttpTransport` implementations
+pub use reqwest::{Method, StatusCode};
+
 // Re-export core types for standalone usage
 // When using the unified `okx` crate, these are already available
 pub use okx_core::{Config, Credentials, OkxError, Result};
@@ -0,0 +1,105 @@
+//! Request/response middleware for the REST client.
+//!
+//! Middleware wraps every request sent by `OkxRestClient` — including the
+//! `_raw` and `_with_meta` variants — after signing and rate limiting, and
+//! sees the `(StatusCode, body, headers)` produced by the transport. A
+//! middleware can inspect or rewrite the request, short-circuit with its own
+//! response, or post-process the response returned by [`Next::run`].
+
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use reqwest::Method;
+
+use okx_core::Result;
+
+use crate::transport::{HttpTransport, TransportResponse};
+
+/// Boxed future returned by [`Middleware::handle`].
+pub type MiddlewareFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<TransportResponse>> + Send + 'a>>;
+
+/// Signed request passed through the middleware chain.
+///
+/// `headers` already contain the `OK-ACCESS-*` signature for private endpoints,
+/// so rewriting `path`, `url` or `body` of a private request invalidates it.
+#[derive(Debug, Clone)]
+pub struct RestRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Absolute URL (base URL + path + query)
+    pub url: String,
+    /// Request path including the query string, as used for signing
+    pub path: String,
+    /// Request headers
+    pub headers: Vec<(&'static str, String)>,
+    /// JSON body (POST only)
+    pub body: Option<String>,
+}
+
+impl RestRequest {
+    /// Get a header value by name (case-insensitive).
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Hook around every REST request.
+///
+/// ## Example
+///
+/// ```rust
+/// use okx_rest::{Middleware, MiddlewareFuture, Next, RestRequest};
+///
+/// /// Tags every request with a broker code.
+/// struct BrokerTag;
+///
+/// impl Middleware for BrokerTag {
+///     fn handle<'a>(&'a self, mut request: RestRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+///         request.headers.push(("x-broker-tag", "demo".to_string()));
+///         next.run(request)
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    /// Handle a request, usually by calling `next.run(request)`.
+    fn handle<'a>(&'a self, request: RestRequest, next: Next<'a>) -> MiddlewareFuture<'a>;
+}
+
+/// Remaining middleware chain, ending in the transport.
+pub struct Next<'a> {
+    transport: &'a dyn HttpTransport,
+    middleware: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(
+        transport: &'a dyn HttpTransport,
+        middleware: &'a [Arc<dyn Middleware>],
+    ) -> Self {
+        Self {
+            transport,
+            middleware,
+        }
+    }
+
+    /// Pass the request to the next middleware, or to the transport at the end of the chain.
+    #[must_use]
+    pub fn run(self, request: RestRequest) -> MiddlewareFuture<'a> {
+        match self.middleware.split_first() {
+            Some((first, rest)) => first.handle(
+                request,
+                Next {
+                    transport: self.transport,
+                    middleware: rest,
+                },
+            ),
+            None => self
+                .transport
+                .send(request.method, request.url, request.headers, request.body),
+        }
+    }
+}
@@ -0,0 +1,618 @@
+//! Client-side rate limiting for REST API calls.
+//!
+//! OKX applies per-endpoint limits (e.g. 60 requests per 2 seconds for
+//! `POST /api/v5/trade/order`), counted either per IP, per User ID or per
+//! User ID + Instrument ID. `RateLimiter` tracks these windows locally so that
+//! requests are queued (or rejected) before they leave the process.
+//!
+//! Source: OKX API v5 rate limit rules
+//! - <https://www.okx.com/docs-v5/en/#overview-rate-limits>
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::Method;
+use serde_json::Value;
+use tokio::time::{sleep, Instant};
+use tracing::debug;
+
+use okx_core::{OkxError, Result};
+
+use crate::api::{account, market, public, trade};
+
+/// Dimension a rate limit is counted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitScope {
+    /// Counted per IP (public endpoints)
+    Ip,
+    /// Counted per User ID (API key)
+    UserId,
+    /// Counted per User ID + Instrument ID
+    Instrument,
+}
+
+/// Rate limit rule for one endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitRule {
+    /// Maximum number of permits within `window`
+    pub limit: u32,
+    /// Length of the sliding window
+    pub window: Duration,
+    /// Dimension the limit is counted on
+    pub scope: RateLimitScope,
+    /// Whether each item of a batch request consumes one permit
+    pub per_item: bool,
+}
+
+impl RateLimitRule {
+    /// Create a rule consuming one permit per request.
+    #[must_use]
+    pub const fn new(limit: u32, window: Duration, scope: RateLimitScope) -> Self {
+        Self {
+            limit,
+            window,
+            scope,
+            per_item: false,
+        }
+    }
+
+    /// Create a rule where every item of a batch request consumes one permit.
+    #[must_use]
+    pub const fn per_item(limit: u32, window: Duration, scope: RateLimitScope) -> Self {
+        Self {
+            limit,
+            window,
+            scope,
+            per_item: true,
+        }
+    }
+}
+
+/// Behaviour when a request would exceed its limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RateLimitMode {
+    /// Wait until the window frees up, then send
+    #[default]
+    Wait,
+    /// Fail immediately with `OkxError::RateLimitExceeded`
+    Reject,
+}
+
+const TWO_SECS: Duration = Duration::from_secs(2);
+
+/// Documented limits of POST endpoints.
+const POST_RULES: &[(&str, RateLimitRule)] = {
+    use RateLimitScope::{Instrument, UserId};
+    &[
+        (
+            trade::endpoints::PLACE_ORDER,
+            RateLimitRule::new(60, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::PLACE_BATCH_ORDERS,
+            RateLimitRule::per_item(300, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::CANCEL_ORDER,
+            RateLimitRule::new(60, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::CANCEL_BATCH_ORDERS,
+            RateLimitRule::per_item(300, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::AMEND_ORDER,
+            RateLimitRule::new(60, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::AMEND_BATCH_ORDERS,
+            RateLimitRule::per_item(300, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::CLOSE_POSITION,
+            RateLimitRule::new(20, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::PLACE_ALGO_ORDER,
+            RateLimitRule::new(20, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::CANCEL_ALGO_ORDERS,
+            RateLimitRule::new(20, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::MASS_CANCEL,
+            RateLimitRule::new(5, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::CANCEL_ALL_AFTER,
+            RateLimitRule::new(1, Duration::from_secs(1), UserId),
+        ),
+        (
+            account::endpoints::SET_LEVERAGE,
+            RateLimitRule::new(20, TWO_SECS, UserId),
+        ),
+    ]
+};
+
+/// Documented limits of GET endpoints.
+const GET_RULES: &[(&str, RateLimitRule)] = {
+    use RateLimitScope::{Instrument, Ip, UserId};
+    &[
+        (
+            trade::endpoints::GET_ORDER,
+            RateLimitRule::new(60, TWO_SECS, Instrument),
+        ),
+        (
+            trade::endpoints::ORDERS_PENDING,
+            RateLimitRule::new(60, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::ORDERS_HISTORY,
+            RateLimitRule::new(40, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::ORDERS_HISTORY_ARCHIVE,
+            RateLimitRule::new(20, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::FILLS,
+            RateLimitRule::new(60, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::FILLS_HISTORY,
+            RateLimitRule::new(10, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::ALGO_ORDERS_PENDING,
+            RateLimitRule::new(20, TWO_SECS, UserId),
+        ),
+        (
+            trade::endpoints::ALGO_ORDERS_HISTORY,
+            RateLimitRule::new(20, TWO_SECS, UserId),
+        ),
+        (
+            account::endpoints::BALANCE,
+            RateLimitRule::new(10, TWO_SECS, UserId),
+        ),
+        (
+            account::endpoints::POSITIONS,
+            RateLimitRule::new(10, TWO_SECS, UserId),
+        ),
+        (
+            account::endpoints::CONFIG,
+            RateLimitRule::new(5, TWO_SECS, UserId),
+        ),
+        (
+            market::endpoints::TICKER,
+            RateLimitRule::new(20, TWO_SECS, Ip),
+        ),
+        (
+            market::endpoints::TICKERS,
+            RateLimitRule::new(20, TWO_SECS, Ip),
+        ),
+        (
+            market::endpoints::BOOKS,
+            RateLimitRule::new(40, TWO_SECS, Ip),
+        ),
+        (
+            market::endpoints::CANDLES,
+            RateLimitRule::new(40, TWO_SECS, Ip),
+        ),
+        (
+            market::endpoints::CANDLES_HISTORY,
+            RateLimitRule::new(20, TWO_SECS, Ip),
+        ),
+        (
+            market::endpoints::TRADES,
+            RateLimitRule::new(100, TWO_SECS, Ip),
+        ),
+        (
+            public::endpoints::INSTRUMENTS,
+            RateLimitRule::new(20, TWO_SECS, Ip),
+        ),
+        (
+            public::endpoints::TIME,
+            RateLimitRule::new(10, TWO_SECS, Ip),
+        ),
+        (
+            public::endpoints::FUNDING_RATE,
+            RateLimitRule::new(20, TWO_SECS, Ip),
+        ),
+        (
+            public::endpoints::MARK_PRICE,
+            RateLimitRule::new(10, TWO_SECS, Ip),
+        ),
+    ]
+};
+
+/// Key of a single sliding-window bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct BucketKey {
+    method: Method,
+    path: String,
+    inst_id: Option<String>,
+}
+
+/// Client-side rate limiter keyed by OKX endpoint rules.
+///
+/// A limiter models the quota of a single API key; share it between clones of
+/// the same `OkxRestClient`, not between clients with different credentials.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use okx_rest::{OkxRestClient, RateLimitMode, RateLimiter};
+/// use okx_core::{Config, Credentials};
+///
+/// let config = Config::new(Credentials::new("key", "secret", "pass"));
+/// let client = OkxRestClient::new(config)
+///     .with_rate_limiter(RateLimiter::okx_default(RateLimitMode::Wait));
+/// ```
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    rules: HashMap<(Method, String), RateLimitRule>,
+    mode: RateLimitMode,
+    buckets: Mutex<HashMap<BucketKey, VecDeque<Instant>>>,
+}
+
+impl RateLimiter {
+    /// Create an empty limiter (no rules, every request passes).
+    #[must_use]
+    pub fn new(mode: RateLimitMode) -> Self {
+        Self {
+            rules: HashMap::new(),
+            mode,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a limiter preloaded with the documented limits of common endpoints.
+    #[must_use]
+    pub fn okx_default(mode: RateLimitMode) -> Self {
+        let post = POST_RULES
+            .iter()
+            .map(|(path, rule)| ((Method::POST, (*path).to_string()), *rule));
+        let get = GET_RULES
+            .iter()
+            .map(|(path, rule)| ((Method::GET, (*path).to_string()), *rule));
+
+        Self {
+            rules: post.chain(get).collect(),
+            ..Self::new(mode)
+        }
+    }
+
+    /// Add or replace the rule for an endpoint.
+    #[must_use]
+    pub fn with_rule(
+        mut self,
+        method: Method,
+        path: impl Into<String>,
+        rule: RateLimitRule,
+    ) -> Self {
+        self.rules.insert((method, path.into()), rule);
+        self
+    }
+
+    /// Get the configured mode.
+    #[must_use]
+    pub fn mode(&self) -> RateLimitMode {
+        self.mode
+    }
+
+    /// Get the rule for an endpoint, if any.
+    #[must_use]
+    pub fn rule(&self, method: &Method, path: &str) -> Option<&RateLimitRule> {
+        self.rules.get(&(method.clone(), path.to_string()))
+    }
+
+    /// Acquire permits for a request, waiting or rejecting according to the mode.
+    ///
+    /// `inst_ids` lists the instrument of every item in the request (one entry
+    /// per order for batch endpoints); it is only used by instrument-scoped and
+    /// per-item rules.
+    pub async fn acquire(&self, method: &Method, path: &str, inst_ids: &[String]) -> Result<()> {
+        let Some(rule) = self.rule(method, path).copied() else {
+            return Ok(());
+        };
+        let costs = Self::costs(method, path, &rule, inst_ids);
+
+        loop {
+            let wait = self.try_acquire(&rule, &costs);
+            let Some(wait) = wait else {
+                return Ok(());
+            };
+
+            if self.mode == RateLimitMode::Reject {
+                return Err(OkxError::RateLimitExceeded {
+                    endpoint: path.to_string(),
+                    retry_after_ms: u64::try_from(wait.as_millis()).unwrap_or(u64::MAX),
+                });
+            }
+
+            debug!(
+                "Rate limit reached for {} {}, waiting {:?}",
+                method, path, wait
+            );
+            sleep(wait).await;
+        }
+    }
+
+    /// Permits needed per bucket.
+    fn costs(
+        method: &Method,
+        path: &str,
+        rule: &RateLimitRule,
+        inst_ids: &[String],
+    ) -> Vec<(BucketKey, u32)> {
+        let key = |inst_id: Option<String>| BucketKey {
+            method: method.clone(),
+            path: path.to_string(),
+            inst_id,
+        };
+
+        if rule.scope != RateLimitScope::Instrument || inst_ids.is_empty() {
+            let cost = if rule.per_item {
+                u32::try_from(inst_ids.len()).unwrap_or(u32::MAX).max(1)
+            } else {
+                1
+            };
+            return vec![(key(None), cost)];
+        }
+
+        let mut costs: Vec<(BucketKey, u32)> = Vec::new();
+        for inst_id in inst_ids {
+            match costs
+                .iter_mut()
+                .find(|(k, _)| k.inst_id.as_deref() == Some(inst_id.as_str()))
+            {
+                Some((_, cost)) if rule.per_item => *cost += 1,
+                Some(_) => {}
+                None => costs.push((key(Some(inst_id.clone())), 1)),
+            }
+        }
+        costs
+    }
+
+    /// Take permits from every bucket atomically, or return the time to wait.
+    fn try_acquire(&self, rule: &RateLimitRule, costs: &[(BucketKey, u32)]) -> Option<Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+
+        // 清理窗口内已无记录的桶，避免按产品划分的桶随 instId 无限增长
+        buckets.retain(|key, bucket| {
+            let window = self
+                .rules
+                .get(&(key.method.clone(), key.path.clone()))
+                .map_or(Duration::ZERO, |r| r.window);
+            bucket
+                .back()
+                .is_some_and(|t| now.duration_since(*t) < window)
+        });
+
+        let mut wait = Duration::ZERO;
+        for (key, cost) in costs {
+            let bucket = buckets.entry(key.clone()).or_default();
+            while bucket
+                .front()
+                .is_some_and(|t| now.duration_since(*t) >= rule.window)
+            {
+                bucket.pop_front();
+            }
+
+            // 单次请求超过窗口上限时，只要求窗口为空，避免永久等待
+            let cost = (*cost).min(rule.limit) as usize;
+            let limit = rule.limit as usize;
+            if bucket.len() + cost > limit {
+                let oldest = bucket[bucket.len() + cost - limit - 1];
+                wait = wait.max(rule.window - now.duration_since(oldest));
+            }
+        }
+
+        if wait > Duration::ZERO {
+            return Some(wait);
+        }
+
+        for (key, cost) in costs {
+            let bucket = buckets.entry(key.clone()).or_default();
+            for _ in 0..(*cost).min(rule.limit) {
+                bucket.push_back(now);
+            }
+        }
+        None
+    }
+}
+
+/// Collect the instrument of every item in a request.
+///
+/// Looks at the `instId` query parameter and at the JSON body (object or array).
+pub(crate) fn request_inst_ids(url: &str, body: Option<&str>) -> Vec<String> {
+    let mut inst_ids = Vec::new();
+
+    if let Some((_, query)) = url.split_once('?') {
+        if let Ok(pairs) = serde_urlencoded::from_str::<Vec<(String, String)>>(query) {
+            inst_ids.extend(
+                pairs
+                    .into_iter()
+                    .filter(|(k, _)| k == "instId")
+                    .map(|(_, v)| v),
+            );
+        }
+    }
+
+    let body_inst = |v: &Value| v.get("instId").and_then(Value::as_str).map(String::from);
+    match body.and_then(|b| serde_json::from_str::<Value>(b).ok()) {
+        Some(Value::Array(items)) => inst_ids.extend(items.iter().filter_map(body_inst)),
+        Some(v @ Value::Object(_)) => inst_ids.extend(body_inst(&v)),
+        _ => {}
+    }
+
+    inst_ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(v: &[&str]) -> Vec<String> {
+        v.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn request_inst_ids_reads_query_object_and_array_bodies() {
+        assert_eq!(
+            request_inst_ids("https://x/api/v5/trade/order?instId=BTC-USDT&ordId=1", None),
+            ids(&["BTC-USDT"])
+        );
+        assert_eq!(
+            request_inst_ids("https://x/p", Some(r#"{"instId":"ETH-USDT","sz":"1"}"#)),
+            ids(&["ETH-USDT"])
+        );
+        assert_eq!(
+            request_inst_ids(
+                "https://x/p",
+                Some(r#"[{"instId":"A"},{"instId":"B"},{"instId":"A"}]"#)
+            ),
+            ids(&["A", "B", "A"])
+        );
+        assert!(request_inst_ids("https://x/p", Some("not json")).is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_endpoint_is_not_limited() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        for _ in 0..100 {
+            limiter
+                .acquire(&Method::GET, "/api/v5/unknown", &[])
+                .await
+                .expect("无规则的端点不应限速");
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_mode_fails_once_window_is_full() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject).with_rule(
+            Method::GET,
+            "/p",
+            RateLimitRule::new(2, Duration::from_secs(2), RateLimitScope::UserId),
+        );
+
+        limiter.acquire(&Method::GET, "/p", &[]).await.unwrap();
+        limiter.acquire(&Method::GET, "/p", &[]).await.unwrap();
+        let err = limiter
+            .acquire(&Method::GET, "/p", &[])
+            .await
+            .expect_err("第三次请求应被拒绝");
+        assert!(err.is_rate_limited());
+        assert!(matches!(
+            err,
+            OkxError::RateLimitExceeded { ref endpoint, retry_after_ms } if endpoint == "/p" && retry_after_ms > 0
+        ));
+
+        // 同路径不同方法不共享窗口
+        limiter.acquire(&Method::POST, "/p", &[]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn instrument_scope_keeps_separate_buckets() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject).with_rule(
+            Method::POST,
+            "/order",
+            RateLimitRule::new(1, Duration::from_secs(2), RateLimitScope::Instrument),
+        );
+
+        limiter
+            .acquire(&Method::POST, "/order", &ids(&["BTC-USDT"]))
+            .await
+            .unwrap();
+        limiter
+            .acquire(&Method::POST, "/order", &ids(&["ETH-USDT"]))
+            .await
+            .expect("不同产品应使用独立窗口");
+        assert!(limiter
+            .acquire(&Method::POST, "/order", &ids(&["BTC-USDT"]))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn expired_instrument_buckets_are_pruned() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject).with_rule(
+            Method::POST,
+            "/order",
+            RateLimitRule::new(1, Duration::from_millis(20), RateLimitScope::Instrument),
+        );
+
+        for inst_id in ["BTC-USDT", "ETH-USDT", "SOL-USDT"] {
+            limiter
+                .acquire(&Method::POST, "/order", &ids(&[inst_id]))
+                .await
+                .unwrap();
+        }
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 3);
+
+        sleep(Duration::from_millis(30)).await;
+        limiter
+            .acquire(&Method::POST, "/order", &ids(&["XRP-USDT"]))
+            .await
+            .unwrap();
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 1, "过期的桶应被移除");
+    }
+
+    #[tokio::test]
+    async fn per_item_rule_counts_every_order() {
+        let limiter = RateLimiter::new(RateLimitMode::Reject).with_rule(
+            Method::POST,
+            "/batch",
+            RateLimitRule::per_item(3, Duration::from_secs(2), RateLimitScope::Instrument),
+        );
+
+        limiter
+            .acquire(&Method::POST, "/batch", &ids(&["A", "A", "B"]))
+            .await
+            .unwrap();
+        limiter
+            .acquire(&Method::POST, "/batch", &ids(&["A"]))
+            .await
+            .unwrap();
+        assert!(limiter
+            .acquire(&Method::POST, "/batch", &ids(&["A"]))
+            .await
+            .is_err());
+        limiter
+            .acquire(&Method::POST, "/batch", &ids(&["B", "B"]))
+            .await
+            .expect("B 仍有余量");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn wait_mode_queues_until_window_frees() {
+        let limiter = RateLimiter::new(RateLimitMode::Wait).with_rule(
+            Method::GET,
+            "/p",
+            RateLimitRule::new(1, Duration::from_secs(2), RateLimitScope::Ip),
+        );
+
+        let start = Instant::now();
+        limiter.acquire(&Method::GET, "/p", &[]).await.unwrap();
+        limiter.acquire(&Method::GET, "/p", &[]).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(2));
+    }
+
+    #[test]
+    fn okx_default_contains_trade_rules() {
+        let limiter = RateLimiter::okx_default(RateLimitMode::Wait);
+        let rule = limiter
+            .rule(&Method::POST, trade::endpoints::PLACE_ORDER)
+            .expect("应包含下单规则");
+        assert_eq!(rule.limit, 60);
+        assert_eq!(rule.window, Duration::from_secs(2));
+        assert_eq!(rule.scope, RateLimitScope::Instrument);
+        assert!(limiter
+            .rule(&Method::POST, trade::endpoints::PLACE_BATCH_ORDERS)
+            .is_some_and(|r| r.per_item));
+        assert_eq!(limiter.mode(), RateLimitMode::Wait);
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides `ResponseMeta` for accessing HTTP response headers
 //! and status information, useful for implementing rate limit handling.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 /// HTTP response metadata.
 ///
@@ -97,6 +97,17 @@ impl ResponseMeta {
             .or_else(|| self.header("ratelimit-reset"))
             .and_then(|v| v.parse().ok())
     }
+
+    /// Get the server-suggested delay before retrying (if present in headers).
+    ///
+    /// Reads `Retry-After` (seconds) first, then falls back to the rate limit reset.
+    #[must_use]
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.header("retry-after")
+            .and_then(|v| v.trim().parse().ok())
+            .or_else(|| self.rate_limit_reset())
+            .map(Duration::from_secs)
+    }
 }
 
 #[cfg(test)]
@@ -144,4 +155,22 @@ mod tests {
         assert!(meta.rate_limit_limit().is_none());
         assert!(meta.rate_limit_reset().is_none());
     }
+
+    #[test]
+    fn response_meta_retry_after_prefers_retry_after_header() {
+        let mut headers = HashMap::new();
+        headers.insert("retry-after".to_string(), "2".to_string());
+        headers.insert("x-ratelimit-reset".to_string(), "5".to_string());
+        let meta = ResponseMeta::new(429, headers);
+        assert_eq!(meta.retry_after(), Some(Duration::from_secs(2)));
+
+        let mut headers = HashMap::new();
+        headers.insert("x-ratelimit-reset".to_string(), "5".to_string());
+        let meta = ResponseMeta::new(429, headers);
+        assert_eq!(meta.retry_after(), Some(Duration::from_secs(5)));
+
+        assert!(ResponseMeta::new(429, HashMap::new())
+            .retry_after()
+            .is_none());
+    }
 }
@@ -0,0 +1,888 @@
+//! In-process mock of the OKX REST API for offline tests.
+//!
+//! Enabled with the `testing` feature. [`MockRestServer`] serves HTTP/1.1 on
+//! `127.0.0.1`; point a client at it with [`MockRestServer::config`] (which
+//! uses `Config::with_rest_url`). Private requests are authenticated like OKX
+//! does:
+//!
+//! - `OK-ACCESS-SIGN` is recomputed with [`Signer::pre_hash`] / [`Signer::sign`]
+//!   from the timestamp, method, request path (with query) and body
+//! - timestamps more than 30 seconds away from the server clock are rejected
+//! - `x-simulated-trading` must match the environment of the API key
+//!
+//! Authentication failures return HTTP 401 with the OKX `501xx` codes.
+//!
+//! A few endpoints keep simple state: balances set with
+//! [`MockRestServer::set_balance`] and orders placed, queried and canceled
+//! through the trade endpoints. Any other path can be scripted with
+//! [`MockRestServer::respond_to`]. [`MockRestServer::inject`] queues a
+//! [`Fault`] (OKX error code, HTTP 429 or latency) for the next matching
+//! request.
+//!
+//! Source: OKX API v5 REST authentication
+//! - <https://www.okx.com/docs-v5/en/#overview-rest-authentication>
+//! - <https://www.okx.com/docs-v5/en/#error-code-rest-api-public>
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tokio::time::sleep;
+use tracing::debug;
+
+use okx_core::signer::headers;
+use okx_core::{Config, Credentials, OkxError, Result, Signer};
+
+/// Default tolerance between the request timestamp and the server clock.
+const DEFAULT_TIMESTAMP_WINDOW: Duration = Duration::from_secs(30);
+
+/// Paths that need no authentication.
+const PUBLIC_PREFIXES: [&str; 3] = ["/api/v5/public/", "/api/v5/market/", "/api/v5/system/"];
+
+/// Failure injected into the next request of a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// HTTP 200 with this OKX error code and message
+    Error {
+        /// OKX error code
+        code: String,
+        /// Error message
+        msg: String,
+    },
+    /// HTTP 429 (code 50011), with an optional `Retry-After` in seconds
+    RateLimited {
+        /// Value of the `Retry-After` header
+        retry_after_secs: Option<u64>,
+    },
+    /// Answer normally after this delay
+    Latency(Duration),
+}
+
+/// A request received by the mock server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    /// HTTP method
+    pub method: String,
+    /// Request path including the query string
+    pub path: String,
+    /// Headers, names in lowercase
+    pub headers: BTreeMap<String, String>,
+    /// Request body
+    pub body: String,
+}
+
+impl RecordedRequest {
+    /// Header value by case-insensitive name.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+/// In-process OKX REST server.
+///
+/// Dropping the server stops accepting connections.
+///
+/// ```rust,no_run
+/// use okx_rest::testing::{Fault, MockRestServer};
+/// use okx_rest::{AccountApi, Credentials, OkxRestClient};
+///
+/// # async fn run() -> okx_rest::Result<()> {
+/// let server = MockRestServer::start(Credentials::new("key", "secret", "pass")).await?;
+/// server.set_balance("USDT", "1000");
+/// let client = OkxRestClient::new(server.config());
+/// let balance = client.get_balance(None).await?;
+/// assert_eq!(balance[0].details[0].ccy, "USDT");
+///
+/// server.inject("/api/v5/account/balance", Fault::RateLimited { retry_after_secs: None });
+/// assert!(client.get_balance(None).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub struct MockRestServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    accept_task: JoinHandle<()>,
+}
+
+struct State {
+    credentials: Credentials,
+    simulated: bool,
+    timestamp_window: Duration,
+    latency: Duration,
+    faults: VecDeque<(String, Fault)>,
+    scripted: HashMap<String, Vec<Value>>,
+    balances: BTreeMap<String, String>,
+    orders: Vec<Value>,
+    last_ord_id: u64,
+    requests: Vec<RecordedRequest>,
+}
+
+/// Response to write back.
+struct Reply {
+    status: u16,
+    body: Value,
+    retry_after_secs: Option<u64>,
+}
+
+impl Reply {
+    fn ok(data: Vec<Value>) -> Self {
+        Self::api("0", "", data)
+    }
+
+    fn api(code: &str, msg: &str, data: Vec<Value>) -> Self {
+        Self {
+            status: 200,
+            body: json!({"code": code, "msg": msg, "data": data}),
+            retry_after_secs: None,
+        }
+    }
+
+    fn status(status: u16, code: &str, msg: &str) -> Self {
+        Self {
+            status,
+            body: json!({"code": code, "msg": msg}),
+            retry_after_secs: None,
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            429 => "Too Many Requests",
+            _ => "Bad Request",
+        };
+        let body = self.body.to_string();
+        let mut head = format!(
+            "HTTP/1.1 {} {reason}\r\ncontent-type: application/json\r\ncontent-length: {}\r\n",
+            self.status,
+            body.len()
+        );
+        if let Some(secs) = self.retry_after_secs {
+            head.push_str(&format!("retry-after: {secs}\r\n"));
+        }
+        head.push_str("\r\n");
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(body.as_bytes());
+        bytes
+    }
+}
+
+impl MockRestServer {
+    /// Bind `127.0.0.1` on a free port and start serving.
+    ///
+    /// `credentials` belong to the live environment; see
+    /// [`MockRestServer::set_simulated`].
+    pub async fn start(credentials: Credentials) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| OkxError::Http(e.to_string()))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| OkxError::Http(e.to_string()))?;
+
+        let state = Arc::new(Mutex::new(State {
+            credentials,
+            simulated: false,
+            timestamp_window: DEFAULT_TIMESTAMP_WINDOW,
+            latency: Duration::ZERO,
+            faults: VecDeque::new(),
+            scripted: HashMap::new(),
+            balances: BTreeMap::new(),
+            orders: Vec::new(),
+            last_ord_id: 0,
+            requests: Vec::new(),
+        }));
+
+        let accept_state = Arc::clone(&state);
+        let accept_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, Arc::clone(&accept_state)));
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            accept_task,
+        })
+    }
+
+    /// Base URL of the server, e.g. `http://127.0.0.1:4321`.
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A [`Config`] with the server credentials, its environment and the REST
+    /// URL pointed at this server.
+    #[must_use]
+    pub fn config(&self) -> Config {
+        let state = self.lock();
+        Config::new(state.credentials.clone())
+            .simulated(state.simulated)
+            .with_rest_url(self.url())
+    }
+
+    /// Treat the API key as a demo-trading key: private requests must then
+    /// carry `x-simulated-trading: 1`, and must not otherwise (code 50101).
+    pub fn set_simulated(&self, simulated: bool) {
+        self.lock().simulated = simulated;
+    }
+
+    /// Set the accepted distance between `OK-ACCESS-TIMESTAMP` and the server
+    /// clock (default 30 seconds).
+    pub fn set_timestamp_window(&self, window: Duration) {
+        self.lock().timestamp_window = window;
+    }
+
+    /// Delay every response by `latency`.
+    pub fn set_latency(&self, latency: Duration) {
+        self.lock().latency = latency;
+    }
+
+    /// Queue a fault for the next request to `path` (without query string);
+    /// `"*"` matches any path. Each fault is used once, in order.
+    pub fn inject(&self, path: &str, fault: Fault) {
+        self.lock().faults.push_back((path.to_string(), fault));
+    }
+
+    /// Answer every later request to `path` with `code = "0"` and `data`,
+    /// overriding the built-in endpoints.
+    pub fn respond_to(&self, path: &str, data: Vec<Value>) {
+        self.lock().scripted.insert(path.to_string(), data);
+    }
+
+    /// Set the available balance of a currency returned by
+    /// `GET /api/v5/account/balance`.
+    pub fn set_balance(&self, ccy: &str, amount: &str) {
+        self.lock()
+            .balances
+            .insert(ccy.to_string(), amount.to_string());
+    }
+
+    /// Orders placed so far, in the shape of `GET /api/v5/trade/order`.
+    #[must_use]
+    pub fn orders(&self) -> Vec<Value> {
+        self.lock().orders.clone()
+    }
+
+    /// Every request received so far, in order.
+    #[must_use]
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for MockRestServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Serve keep-alive requests on one connection until the client leaves.
+async fn serve(stream: TcpStream, state: Arc<Mutex<State>>) {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+    loop {
+        let request = match read_request(&mut reader).await {
+            Ok(Some(request)) => request,
+            Ok(None) => break,
+            Err(e) => {
+                debug!("Mock REST server dropped a malformed request: {e}");
+                break;
+            }
+        };
+        let (reply, delay) = lock(&state).handle(request);
+        if !delay.is_zero() {
+            sleep(delay).await;
+        }
+        if write.write_all(&reply.to_bytes()).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// 读取一个 HTTP/1.1 请求；连接关闭时返回 `None`。
+async fn read_request<R>(reader: &mut R) -> io::Result<Option<RecordedRequest>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid request line",
+        ));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = BTreeMap::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line).await?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+
+    Ok(Some(RecordedRequest {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    }))
+}
+
+impl State {
+    /// Answer one request; returns the reply and how long to wait before it.
+    fn handle(&mut self, request: RecordedRequest) -> (Reply, Duration) {
+        self.requests.push(request.clone());
+        let (path, query) = request
+            .path
+            .split_once('?')
+            .unwrap_or((request.path.as_str(), ""));
+        let mut delay = self.latency;
+
+        if let Some(i) = self.faults.iter().position(|(p, _)| p == path || p == "*") {
+            match self.faults.remove(i).map(|(_, fault)| fault) {
+                Some(Fault::Error { code, msg }) => {
+                    return (Reply::api(&code, &msg, Vec::new()), delay)
+                }
+                Some(Fault::RateLimited { retry_after_secs }) => {
+                    let mut reply = Reply::status(429, "50011", "Too Many Requests");
+                    reply.retry_after_secs = retry_after_secs;
+                    return (reply, delay);
+                }
+                Some(Fault::Latency(extra)) => delay += extra,
+                None => {}
+            }
+        }
+
+        if !PUBLIC_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            if let Err((code, msg)) = self.authenticate(&request) {
+                return (Reply::status(401, code, msg), delay);
+            }
+        }
+
+        let reply = if let Some(data) = self.scripted.get(path) {
+            Reply::ok(data.clone())
+        } else {
+            let params: HashMap<String, String> =
+                serde_urlencoded::from_str(query).unwrap_or_default();
+            self.route(&request.method, path, &params, &request.body)
+        };
+        (reply, delay)
+    }
+
+    /// 按 OKX 的顺序校验鉴权请求头，签名通过 `Signer` 重新计算后比对。
+    fn authenticate(
+        &self,
+        request: &RecordedRequest,
+    ) -> std::result::Result<(), (&'static str, &'static str)> {
+        let header = |name: &str| request.header(name).unwrap_or_default();
+        let key = header(headers::OK_ACCESS_KEY);
+        let passphrase = header(headers::OK_ACCESS_PASSPHRASE);
+        let timestamp = header(headers::OK_ACCESS_TIMESTAMP);
+        let sign = header(headers::OK_ACCESS_SIGN);
+
+        if key.is_empty() {
+            return Err(("50103", "Request header OK-ACCESS-KEY can not be empty."));
+        }
+        if passphrase.is_empty() {
+            return Err((
+                "50104",
+                "Request header OK-ACCESS-PASSPHRASE can not be empty.",
+            ));
+        }
+        if sign.is_empty() {
+            return Err(("50106", "Request header OK-ACCESS-SIGN can not be empty."));
+        }
+        if timestamp.is_empty() {
+            return Err((
+                "50107",
+                "Request header OK-ACCESS-TIMESTAMP can not be empty.",
+            ));
+        }
+        if key != self.credentials.api_key() {
+            return Err(("50111", "Invalid OK-ACCESS-KEY."));
+        }
+        if passphrase != self.credentials.passphrase() {
+            return Err(("50105", "Request header OK-ACCESS-PASSPHRASE incorrect."));
+        }
+        let Ok(sent_at) = DateTime::parse_from_rfc3339(timestamp) else {
+            return Err(("50112", "Invalid OK-ACCESS-TIMESTAMP."));
+        };
+        let skew = Utc::now()
+            .signed_duration_since(sent_at)
+            .num_milliseconds()
+            .unsigned_abs();
+        if u128::from(skew) > self.timestamp_window.as_millis() {
+            return Err(("50102", "Timestamp request expired."));
+        }
+        if (header(headers::X_SIMULATED_TRADING) == "1") != self.simulated {
+            return Err(("50101", "APIKey does not match current environment."));
+        }
+
+        let pre_hash = Signer::pre_hash(timestamp, &request.method, &request.path, &request.body);
+        if sign != Signer::sign(&pre_hash, self.credentials.secret_key()) {
+            return Err(("50113", "Invalid Sign."));
+        }
+        Ok(())
+    }
+
+    fn route(
+        &mut self,
+        method: &str,
+        path: &str,
+        params: &HashMap<String, String>,
+        body: &str,
+    ) -> Reply {
+        match (method, path) {
+            ("GET", "/api/v5/public/time") => Reply::ok(vec![
+                json!({"ts": Utc::now().timestamp_millis().to_string()}),
+            ]),
+            ("GET", "/api/v5/account/balance") => self.balance(params),
+            ("POST", "/api/v5/trade/order") => self.place_order(body),
+            ("POST", "/api/v5/trade/cancel-order") => self.cancel_order(body),
+            ("GET", "/api/v5/trade/order") => match self.find_order(params) {
+                Some(i) => Reply::ok(vec![self.orders[i].clone()]),
+                None => Reply::api("51603", "Order does not exist", Vec::new()),
+            },
+            ("GET", "/api/v5/trade/orders-pending") => {
+                let inst_id = params.get("instId");
+                let live = self
+                    .orders
+                    .iter()
+                    .filter(|order| order["state"] == "live")
+                    .filter(|order| match inst_id {
+                        Some(id) => order["instId"] == id.as_str(),
+                        None => true,
+                    })
+                    .rev()
+                    .cloned()
+                    .collect();
+                Reply::ok(live)
+            }
+            _ => Reply::status(404, "404", "Not Found"),
+        }
+    }
+
+    fn balance(&self, params: &HashMap<String, String>) -> Reply {
+        let wanted: Option<Vec<&str>> = params.get("ccy").map(|c| c.split(',').collect());
+        let u_time = Utc::now().timestamp_millis().to_string();
+        let details: Vec<Value> = self
+            .balances
+            .iter()
+            .filter(|(ccy, _)| match &wanted {
+                Some(wanted) => wanted.contains(&ccy.as_str()),
+                None => true,
+            })
+            .map(|(ccy, amount)| {
+                json!({
+                    "ccy": ccy,
+                    "eq": amount,
+                    "cashBal": amount,
+                    "availBal": amount,
+                    "availEq": amount,
+                    "frozenBal": "0",
+                    "uTime": u_time,
+                })
+            })
+            .collect();
+        Reply::ok(vec![
+            json!({"totalEq": "", "details": details, "uTime": u_time}),
+        ])
+    }
+
+    fn place_order(&mut self, body: &str) -> Reply {
+        let request: Value = serde_json::from_str(body).unwrap_or_default();
+        let field = |key: &str| request[key].as_str().unwrap_or_default().to_string();
+        let cl_ord_id = field("clOrdId");
+        let tag = field("tag");
+        let failed = |s_code: &str, s_msg: &str| {
+            Reply::api(
+                "1",
+                "All operations failed",
+                vec![
+                    json!({"ordId": "", "clOrdId": cl_ord_id, "tag": tag, "sCode": s_code, "sMsg": s_msg}),
+                ],
+            )
+        };
+
+        for key in ["instId", "tdMode", "side", "ordType"] {
+            if field(key).is_empty() {
+                return failed("51000", &format!("Parameter {key} error"));
+            }
+        }
+        if !field("sz").parse::<f64>().is_ok_and(|sz| sz > 0.0) {
+            return failed("51000", "Parameter sz error");
+        }
+        if !cl_ord_id.is_empty()
+            && self
+                .orders
+                .iter()
+                .any(|o| o["clOrdId"] == cl_ord_id.as_str() && o["state"] == "live")
+        {
+            return failed("51016", "Duplicated clOrdId");
+        }
+
+        self.last_ord_id += 1;
+        let ord_id = self.last_ord_id.to_string();
+        let inst_id = field("instId");
+        let inst_type = match inst_id.matches('-').count() {
+            1 => "SPOT",
+            _ if inst_id.ends_with("-SWAP") => "SWAP",
+            _ => "FUTURES",
+        };
+        // 市价单立即全部成交，其余订单保持挂单
+        let filled = field("ordType") == "market";
+        let sz = field("sz");
+        let now = Utc::now().timestamp_millis().to_string();
+        let pos_side = Some(field("posSide"))
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| "net".to_string());
+        self.orders.push(json!({
+            "instType": inst_type,
+            "instId": inst_id,
+            "ccy": field("ccy"),
+            "ordId": ord_id,
+            "clOrdId": cl_ord_id,
+            "tag": tag,
+            "px": field("px"),
+            "sz": sz,
+            "pnl": "0",
+            "ordType": field("ordType"),
+            "side": field("side"),
+            "posSide": pos_side,
+            "tdMode": field("tdMode"),
+            "accFillSz": if filled { sz.as_str() } else { "0" },
+            "fillPx": "",
+            "tradeId": "",
+            "fillSz": if filled { sz.as_str() } else { "0" },
+            "fillTime": if filled { now.as_str() } else { "" },
+            "avgPx": "",
+            "state": if filled { "filled" } else { "live" },
+            "lever": "",
+            "fee": "0",
+            "feeCcy": "",
+            "rebate": "0",
+            "rebateCcy": "",
+            "category": "normal",
+            "reduceOnly": "false",
+            "cancelSource": "",
+            "cTime": now,
+            "uTime": now,
+        }));
+
+        Reply::ok(vec![json!({
+            "ordId": ord_id,
+            "clOrdId": cl_ord_id,
+            "tag": tag,
+            "sCode": "0",
+            "sMsg": "Order placed",
+        })])
+    }
+
+    fn cancel_order(&mut self, body: &str) -> Reply {
+        let request: Value = serde_json::from_str(body).unwrap_or_default();
+        let params: HashMap<String, String> = ["instId", "ordId", "clOrdId"]
+            .into_iter()
+            .filter_map(|key| Some((key.to_string(), request[key].as_str()?.to_string())))
+            .collect();
+        let ord_id = params.get("ordId").cloned().unwrap_or_default();
+        let cl_ord_id = params.get("clOrdId").cloned().unwrap_or_default();
+
+        let live = self
+            .find_order(&params)
+            .filter(|&i| self.orders[i]["state"] == "live");
+        let Some(i) = live else {
+            return Reply::api(
+                "1",
+                "All operations failed",
+                vec![json!({
+                    "ordId": ord_id,
+                    "clOrdId": cl_ord_id,
+                    "sCode": "51400",
+                    "sMsg": "Order cancellation failed as the order has been filled, canceled or does not exist",
+                })],
+            );
+        };
+
+        let order = &mut self.orders[i];
+        order["state"] = "canceled".into();
+        order["uTime"] = Utc::now().timestamp_millis().to_string().into();
+        Reply::ok(vec![json!({
+            "ordId": order["ordId"],
+            "clOrdId": order["clOrdId"],
+            "sCode": "0",
+            "sMsg": "",
+        })])
+    }
+
+    /// 按 `instId` 加 `ordId` 或 `clOrdId` 查找订单（`clOrdId` 取最新一笔）。
+    fn find_order(&self, params: &HashMap<String, String>) -> Option<usize> {
+        let inst_id = params.get("instId")?;
+        let (key, id) = match (params.get("ordId"), params.get("clOrdId")) {
+            (Some(id), _) if !id.is_empty() => ("ordId", id),
+            (_, Some(id)) if !id.is_empty() => ("clOrdId", id),
+            _ => return None,
+        };
+        self.orders
+            .iter()
+            .rposition(|o| o["instId"] == inst_id.as_str() && o[key] == id.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+
+    use okx_core::types::{CancelOrderRequest, OrdState, PlaceOrderRequest};
+    use okx_core::RetryPolicy;
+
+    use crate::api::trade::{GetOrderParams, GetOrdersPendingParams};
+    use crate::{AccountApi, OkxRestClient, PublicApi, TradeApi};
+
+    fn credentials() -> Credentials {
+        Credentials::new("key", "secret", "pass")
+    }
+
+    async fn start() -> Option<MockRestServer> {
+        match MockRestServer::start(credentials()).await {
+            Ok(server) => Some(server),
+            Err(e) => {
+                eprintln!("无法启动本地模拟服务端，跳过用例: {e}");
+                None
+            }
+        }
+    }
+
+    fn limit_order(cl_ord_id: &str) -> PlaceOrderRequest {
+        serde_json::from_value(json!({
+            "instId": "BTC-USDT",
+            "tdMode": "cash",
+            "side": "buy",
+            "ordType": "limit",
+            "sz": "0.01",
+            "px": "30000",
+            "clOrdId": cl_ord_id,
+        }))
+        .expect("下单请求应可反序列化")
+    }
+
+    fn api_code(err: &OkxError) -> Option<String> {
+        err.api_code().map(|code| code.as_str().to_string())
+    }
+
+    #[tokio::test]
+    async fn signed_requests_drive_order_and_balance_state() {
+        let Some(server) = start().await else {
+            return;
+        };
+        server.set_balance("USDT", "1000");
+        server.set_balance("BTC", "0.5");
+        let client = OkxRestClient::new(server.config());
+
+        let time = client.get_system_time().await.expect("公共接口无需签名");
+        assert!(!time[0].ts.is_empty());
+
+        let balance = client
+            .get_balance(Some("USDT"))
+            .await
+            .expect("余额查询应成功");
+        assert_eq!(balance[0].details.len(), 1);
+        assert_eq!(balance[0].details[0].cash_bal, "1000");
+
+        let placed = client
+            .place_order(limit_order("c1"))
+            .await
+            .expect("下单应成功");
+        let ord_id = placed[0].ord_id.clone();
+        let err = client
+            .place_order(limit_order("c1"))
+            .await
+            .expect_err("重复 clOrdId 应被拒绝");
+        assert_eq!(api_code(&err).as_deref(), Some("1"));
+
+        let order = client
+            .get_order(GetOrderParams {
+                inst_id: "BTC-USDT".to_string(),
+                ord_id: Some(ord_id.clone()),
+                cl_ord_id: None,
+            })
+            .await
+            .expect("查单应成功");
+        assert_eq!(order[0].cl_ord_id, "c1");
+        assert_eq!(order[0].state, OrdState::Live);
+
+        let pending = client
+            .get_orders_pending(Some(GetOrdersPendingParams::default()))
+            .await
+            .expect("查询挂单应成功");
+        assert_eq!(pending.len(), 1);
+
+        let cancel = CancelOrderRequest {
+            inst_id: "BTC-USDT".to_string(),
+            ord_id: Some(ord_id),
+            cl_ord_id: None,
+        };
+        client
+            .cancel_order(cancel.clone())
+            .await
+            .expect("撤单应成功");
+        assert!(client.cancel_order(cancel).await.is_err());
+        assert_eq!(server.orders()[0]["state"], "canceled");
+
+        let signed = server
+            .requests()
+            .into_iter()
+            .find(|r| r.path.starts_with("/api/v5/account/balance"))
+            .expect("应记录余额请求");
+        assert_eq!(signed.path, "/api/v5/account/balance?ccy=USDT");
+        assert!(signed.header("OK-ACCESS-SIGN").is_some());
+    }
+
+    #[tokio::test]
+    async fn bad_signatures_stale_timestamps_and_wrong_environment_are_rejected() {
+        let Some(server) = start().await else {
+            return;
+        };
+
+        let wrong_secret =
+            Config::new(Credentials::new("key", "other", "pass")).with_rest_url(server.url());
+        let err = OkxRestClient::new(wrong_secret)
+            .get_balance(None)
+            .await
+            .expect_err("错误签名应被拒绝");
+        assert!(err.is_http_status(401));
+        assert!(err.to_string().contains("50113"), "{err}");
+
+        let demo = server.config().simulated(true);
+        let err = OkxRestClient::new(demo)
+            .get_balance(None)
+            .await
+            .expect_err("环境不符应被拒绝");
+        assert!(err.to_string().contains("50101"), "{err}");
+
+        server.set_simulated(true);
+        OkxRestClient::new(server.config())
+            .get_balance(None)
+            .await
+            .expect("模拟盘密钥配合模拟盘请求头应成功");
+
+        // 手工构造时间戳早已过期的请求
+        let stale = "2020-01-01T00:00:00.000Z";
+        let request = RecordedRequest {
+            method: "GET".to_string(),
+            path: "/api/v5/account/balance".to_string(),
+            headers: Signer::new(credentials())
+                .generate_headers_with_timestamp(stale, "GET", "/api/v5/account/balance", "", true)
+                .into_iter()
+                .map(|(name, value)| (name.to_ascii_lowercase(), value))
+                .collect(),
+            body: String::new(),
+        };
+        let (reply, _) = lock(&server.state).handle(request);
+        assert_eq!(reply.status, 401);
+        assert_eq!(reply.body["code"], "50102");
+    }
+
+    #[tokio::test]
+    async fn faults_inject_errors_rate_limits_and_latency() {
+        let Some(server) = start().await else {
+            return;
+        };
+        let client = OkxRestClient::new(server.config());
+
+        server.inject(
+            "/api/v5/trade/order",
+            Fault::Error {
+                code: "50001".to_string(),
+                msg: "Service temporarily unavailable".to_string(),
+            },
+        );
+        let err = client
+            .place_order(limit_order("c1"))
+            .await
+            .expect_err("注入的错误码应返回");
+        assert_eq!(api_code(&err).as_deref(), Some("50001"));
+        client
+            .place_order(limit_order("c1"))
+            .await
+            .expect("故障只生效一次");
+
+        server.inject(
+            "*",
+            Fault::RateLimited {
+                retry_after_secs: None,
+            },
+        );
+        let err = client.get_system_time().await.expect_err("应返回 429");
+        assert!(err.is_http_status(429));
+
+        // 带重试策略的客户端在 429 后重试成功
+        server.inject(
+            "/api/v5/public/time",
+            Fault::RateLimited {
+                retry_after_secs: None,
+            },
+        );
+        let retrying = OkxRestClient::new(
+            server.config().with_retry_policy(
+                RetryPolicy::new(2)
+                    .with_base_delay(Duration::from_millis(10))
+                    .with_jitter(false),
+            ),
+        );
+        retrying.get_system_time().await.expect("重试后应成功");
+
+        server.inject("*", Fault::Latency(Duration::from_millis(100)));
+        let started = Instant::now();
+        client.get_system_time().await.expect("延迟后应成功");
+        assert!(started.elapsed() >= Duration::from_millis(100));
+
+        server.respond_to("/api/v5/public/time", vec![json!({"ts": "1"})]);
+        let time = client.get_system_time().await.expect("脚本响应应成功");
+        assert_eq!(time[0].ts, "1");
+    }
+}
@@ -0,0 +1,136 @@
+//! Pluggable HTTP transport for the REST client.
+//!
+//! `OkxRestClient` builds URLs, signs requests and parses OKX responses; the
+//! transport only moves bytes. Implement [`HttpTransport`] to inject a recording
+//! or replaying transport in offline tests, or to use a custom HTTP stack.
+
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+use reqwest::{Client, Method, StatusCode};
+
+use okx_core::{OkxError, Result};
+
+/// Transport response: (status, body, headers with lowercase keys)
+pub type TransportResponse = (StatusCode, String, HashMap<String, String>);
+
+/// Boxed future returned by [`HttpTransport::send`].
+pub type TransportFuture = Pin<Box<dyn Future<Output = Result<TransportResponse>> + Send>>;
+
+/// HTTP transport used by `OkxRestClient` to send already signed requests.
+///
+/// Implementations should report connection-level failures as [`OkxError::Http`]
+/// and return non-2xx responses as `Ok` so the client can map them to
+/// [`OkxError::HttpStatus`].
+///
+/// ## Example
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use okx_rest::{HttpTransport, Method, StatusCode, TransportFuture};
+///
+/// /// Always answers with an empty OKX success payload.
+/// struct EmptyTransport;
+///
+/// impl HttpTransport for EmptyTransport {
+///     fn send(
+///         &self,
+///         _method: Method,
+///         _url: String,
+///         _headers: Vec<(&'static str, String)>,
+///         _body: Option<String>,
+///     ) -> TransportFuture {
+///         Box::pin(async {
+///             let body = r#"{"code":"0","msg":"","data":[]}"#.to_string();
+///             Ok((StatusCode::OK, body, HashMap::new()))
+///         })
+///     }
+/// }
+/// ```
+pub trait HttpTransport: Send + Sync + 'static {
+    /// Send a request and return the raw response.
+    ///
+    /// `url` is absolute (base URL + path + query); `headers` already contain the
+    /// `OK-ACCESS-*` signature for private endpoints.
+    fn send(
+        &self,
+        method: Method,
+        url: String,
+        headers: Vec<(&'static str, String)>,
+        body: Option<String>,
+    ) -> TransportFuture;
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send(
+        &self,
+        method: Method,
+        url: String,
+        headers: Vec<(&'static str, String)>,
+        body: Option<String>,
+    ) -> TransportFuture {
+        (**self).send(method, url, headers, body)
+    }
+}
+
+/// Default transport backed by `reqwest`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Create a transport from a `reqwest::Client`.
+    #[must_use]
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(
+        &self,
+        method: Method,
+        url: String,
+        headers: Vec<(&'static str, String)>,
+        body: Option<String>,
+    ) -> TransportFuture {
+        let client = self.client.clone();
+        Box::pin(async move {
+            let mut request = client.request(method, &url);
+
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+
+            if let Some(body) = body {
+                request = request.body(body);
+            }
+
+            let response = request
+                .send()
+                .await
+                .map_err(|e| OkxError::Http(e.to_string()))?;
+
+            let status = response.status();
+
+            // Extract headers
+            let resp_headers: HashMap<String, String> = response
+                .headers()
+                .iter()
+                .filter_map(|(k, v)| {
+                    v.to_str()
+                        .ok()
+                        .map(|val| (k.as_str().to_lowercase(), val.to_string()))
+                })
+                .collect();
+
+            let text = response
+                .text()
+                .await
+                .map_err(|e| OkxError::Http(format!("Failed to read response: {e}")))?;
+
+            Ok((status, text, resp_headers))
+        })
+    }
+}
@@ -0,0 +1,215 @@
+//! 批量下单/撤单/改单：部分成功时保留逐项结果。
+//!
+//! 使用自定义 `HttpTransport` 回放固定响应，不依赖外网。
+
+#![allow(missing_docs)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use okx_core::types::{BatchItem, CancelOrderRequest, PlaceOrderRequest};
+use okx_core::{Config, Credentials};
+use okx_rest::api::trade::MAX_BATCH_SIZE;
+use okx_rest::{HttpTransport, Method, OkxRestClient, StatusCode, TradeApi, TransportFuture};
+use serde_json::{json, Value};
+
+/// 对所有请求返回同一响应体的传输层。
+struct ReplayTransport {
+    body: &'static str,
+}
+
+impl HttpTransport for ReplayTransport {
+    fn send(
+        &self,
+        _method: Method,
+        _url: String,
+        _headers: Vec<(&'static str, String)>,
+        _body: Option<String>,
+    ) -> TransportFuture {
+        let body = self.body.to_string();
+        Box::pin(async move { Ok((StatusCode::OK, body, HashMap::new())) })
+    }
+}
+
+/// 按请求体逐项回显的传输层：`clOrdId` 为 `bad` 的项返回 51008，
+/// 含 `boom` 的整批返回 HTTP 500。
+#[derive(Clone, Default)]
+struct EchoTransport {
+    batch_sizes: Arc<Mutex<Vec<usize>>>,
+}
+
+impl HttpTransport for EchoTransport {
+    fn send(
+        &self,
+        _method: Method,
+        _url: String,
+        _headers: Vec<(&'static str, String)>,
+        body: Option<String>,
+    ) -> TransportFuture {
+        let orders: Vec<Value> =
+            serde_json::from_str(body.as_deref().unwrap_or("[]")).expect("请求体应为数组");
+        self.batch_sizes
+            .lock()
+            .expect("锁 batch_sizes 失败")
+            .push(orders.len());
+
+        let cl_ord_id = |o: &Value| o["clOrdId"].as_str().unwrap_or_default().to_string();
+        let response = if orders.iter().any(|o| cl_ord_id(o) == "boom") {
+            (StatusCode::INTERNAL_SERVER_ERROR, "boom".to_string())
+        } else {
+            // clOrdId 为 skip 的订单不返回条目，模拟条目数不足的响应
+            let data: Vec<Value> = orders
+                .iter()
+                .filter(|o| cl_ord_id(o) != "skip")
+                .map(|o| {
+                    let id = cl_ord_id(o);
+                    let s_code = if id == "bad" { "51008" } else { "0" };
+                    json!({"ordId": "", "clOrdId": id, "tag": "", "sCode": s_code, "sMsg": ""})
+                })
+                .collect();
+            let code = if data.iter().all(|d| d["sCode"] == "0") {
+                "0"
+            } else {
+                "2"
+            };
+            let body = json!({"code": code, "msg": "", "data": data}).to_string();
+            (StatusCode::OK, body)
+        };
+        Box::pin(async move { Ok((response.0, response.1, HashMap::new())) })
+    }
+}
+
+fn client_replaying(body: &'static str) -> OkxRestClient {
+    let config =
+        Config::new(Credentials::new("key", "secret", "pass")).with_rest_url("http://127.0.0.1:9");
+    OkxRestClient::with_transport(config, ReplayTransport { body })
+}
+
+fn limit_order(cl_ord_id: &str) -> PlaceOrderRequest {
+    serde_json::from_value(json!({
+        "instId": "BTC-USDT",
+        "tdMode": "cash",
+        "side": "buy",
+        "ordType": "limit",
+        "sz": "0.01",
+        "px": "30000",
+        "clOrdId": cl_ord_id,
+    }))
+    .expect("下单请求应可反序列化")
+}
+
+#[tokio::test]
+async fn place_batch_orders_keeps_successful_items_on_partial_failure() {
+    let client = client_replaying(
+        r#"{"code":"2","msg":"","data":[
+            {"ordId":"1","clOrdId":"q1","tag":"","sCode":"0","sMsg":""},
+            {"ordId":"","clOrdId":"q2","tag":"","sCode":"51008","sMsg":"Insufficient balance"}
+        ]}"#,
+    );
+
+    let result = client
+        .place_batch_orders(vec![limit_order("q1"), limit_order("q2")])
+        .await
+        .expect("部分成功不应返回错误");
+
+    assert!(result.is_partial());
+    assert_eq!(result.succeeded_cl_ord_ids(), ["q1"]);
+    assert_eq!(result.failed_cl_ord_ids(), ["q2"]);
+    let (item, err) = &result.errors()[0];
+    assert_eq!(item.s_msg(), "Insufficient balance");
+    assert!(err.api_code().is_some_and(|c| c.is_insufficient_balance()));
+}
+
+#[tokio::test]
+async fn cancel_batch_orders_reports_request_level_errors() {
+    let client = client_replaying(r#"{"code":"50011","msg":"Too Many Requests","data":[]}"#);
+
+    let err = client
+        .cancel_batch_orders(vec![CancelOrderRequest {
+            inst_id: "BTC-USDT".into(),
+            ord_id: Some("1".into()),
+            cl_ord_id: None,
+        }])
+        .await
+        .expect_err("请求级错误应返回 Err");
+    assert!(err.is_api_error("50011"));
+}
+
+#[tokio::test]
+async fn place_batch_orders_split_chunks_and_merges_in_input_order() {
+    let transport = EchoTransport::default();
+    let config =
+        Config::new(Credentials::new("key", "secret", "pass")).with_rest_url("http://127.0.0.1:9");
+    let client = OkxRestClient::with_transport(config, transport.clone());
+
+    // 3 批：第 2 批整体失败，第 3 批有一项失败
+    let mut ids: Vec<String> = (0..45).map(|i| format!("o{i}")).collect();
+    ids[25] = "boom".into();
+    ids[44] = "bad".into();
+    let orders = ids.iter().map(|id| limit_order(id)).collect();
+
+    let result = client.place_batch_orders_split(orders).await;
+
+    let mut sizes = transport
+        .batch_sizes
+        .lock()
+        .expect("锁 batch_sizes 失败")
+        .clone();
+    sizes.sort_unstable();
+    assert_eq!(sizes, [5, MAX_BATCH_SIZE, MAX_BATCH_SIZE]);
+
+    assert!(!result.is_success());
+    assert_eq!(result.failed_chunks.len(), 1);
+    assert_eq!(result.failed_chunks[0].range, 20..40);
+    assert!(result.failed_chunks[0].error.is_http_status(500));
+
+    let answered: Vec<&str> = result.items.iter().map(|i| i.cl_ord_id.as_str()).collect();
+    let expected: Vec<&str> = ids[..20]
+        .iter()
+        .chain(&ids[40..])
+        .map(String::as_str)
+        .collect();
+    assert_eq!(answered, expected);
+    assert_eq!(result.failed_cl_ord_ids(), ["bad"]);
+    assert_eq!(result.succeeded_cl_ord_ids().len(), 24);
+
+    // by_input 按输入下标对齐：失败批次的位置对应批次错误
+    let by_input = result.by_input();
+    assert_eq!(by_input.len(), ids.len());
+    for (index, (outcome, id)) in by_input.iter().zip(&ids).enumerate() {
+        match outcome {
+            Ok(item) => assert_eq!(&item.cl_ord_id, id, "下标 {index} 应对应同一请求"),
+            Err(error) => {
+                assert!((20..40).contains(&index), "下标 {index} 不应映射到批次错误");
+                assert!(error.is_http_status(500));
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn short_chunk_is_reported_as_failed_to_keep_alignment() {
+    let transport = EchoTransport::default();
+    let config =
+        Config::new(Credentials::new("key", "secret", "pass")).with_rest_url("http://127.0.0.1:9");
+    let client = OkxRestClient::with_transport(config, transport);
+
+    // 第 1 批少返回一个条目，后续批次不应错位
+    let mut ids: Vec<String> = (0..25).map(|i| format!("o{i}")).collect();
+    ids[3] = "skip".into();
+    let orders = ids.iter().map(|id| limit_order(id)).collect();
+
+    let result = client.place_batch_orders_split(orders).await;
+
+    assert_eq!(result.failed_chunks.len(), 1);
+    assert_eq!(result.failed_chunks[0].range, 0..MAX_BATCH_SIZE);
+    let by_input = result.by_input();
+    assert_eq!(by_input.len(), ids.len());
+    assert!(by_input[..MAX_BATCH_SIZE].iter().all(Result::is_err));
+    for (outcome, id) in by_input[MAX_BATCH_SIZE..]
+        .iter()
+        .zip(&ids[MAX_BATCH_SIZE..])
+    {
+        assert_eq!(&outcome.expect("第 2 批应成功").cl_ord_id, id);
+    }
+}
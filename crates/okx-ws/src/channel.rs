@@ -3,12 +3,76 @@
 //! Source: OKX API v5 WebSocket API
 //! - <https://www.okx.com/docs-v5/en/#websocket-api>
 
+use okx_core::types::Bar;
 use serde::{Deserialize, Serialize};
 
+use crate::reconnect::ConnectionType;
+
+/// Channel name of a candle channel `prefix` for `bar`, as a `&'static str`.
+macro_rules! candle_name {
+    ($prefix:literal, $bar:expr) => {
+        match $bar {
+            Bar::M1 => concat!($prefix, "1m"),
+            Bar::M3 => concat!($prefix, "3m"),
+            Bar::M5 => concat!($prefix, "5m"),
+            Bar::M15 => concat!($prefix, "15m"),
+            Bar::M30 => concat!($prefix, "30m"),
+            Bar::H1 => concat!($prefix, "1H"),
+            Bar::H2 => concat!($prefix, "2H"),
+            Bar::H4 => concat!($prefix, "4H"),
+            Bar::H6 => concat!($prefix, "6H"),
+            Bar::H12 => concat!($prefix, "12H"),
+            Bar::D1 => concat!($prefix, "1D"),
+            Bar::D2 => concat!($prefix, "2D"),
+            Bar::D3 => concat!($prefix, "3D"),
+            Bar::W1 => concat!($prefix, "1W"),
+            Bar::Mo1 => concat!($prefix, "1M"),
+            Bar::Mo3 => concat!($prefix, "3M"),
+            Bar::H6Utc => concat!($prefix, "6Hutc"),
+            Bar::H12Utc => concat!($prefix, "12Hutc"),
+            Bar::D1Utc => concat!($prefix, "1Dutc"),
+            Bar::D2Utc => concat!($prefix, "2Dutc"),
+            Bar::D3Utc => concat!($prefix, "3Dutc"),
+            Bar::W1Utc => concat!($prefix, "1Wutc"),
+            Bar::Mo1Utc => concat!($prefix, "1Mutc"),
+            Bar::Mo3Utc => concat!($prefix, "3Mutc"),
+        }
+    };
+}
+
+/// Serde module mapping a [`Bar`] to and from the `channel` name of a candle channel.
+macro_rules! candle_bar_serde {
+    ($module:ident, $prefix:literal) => {
+        mod $module {
+            use okx_core::types::Bar;
+            use serde::de::Error;
+            use serde::{Deserialize, Deserializer, Serializer};
+
+            #[allow(clippy::trivially_copy_pass_by_ref)]
+            pub fn serialize<S: Serializer>(bar: &Bar, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(candle_name!($prefix, *bar))
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Bar, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                name.strip_prefix($prefix)
+                    .and_then(Bar::from_api_str)
+                    .ok_or_else(|| D::Error::custom(format!("unknown channel: {name}")))
+            }
+        }
+    };
+}
+
+candle_bar_serde!(candle_bar, "candle");
+candle_bar_serde!(mark_price_candle_bar, "mark-price-candle");
+candle_bar_serde!(index_candle_bar, "index-candle");
+
 /// WebSocket channel subscription.
 ///
 /// Represents a channel to subscribe to on the OKX WebSocket API.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "channel", rename_all = "kebab-case")]
 pub enum Channel {
     // ==================== Public Channels ====================
@@ -60,78 +124,92 @@ pub enum Channel {
         inst_id: String,
     },
 
-    /// Candlesticks channel
-    #[serde(rename = "candle1m")]
-    Candle1m {
+    /// Mark price channel
+    #[serde(rename = "mark-price")]
+    MarkPrice {
         /// Instrument ID
         #[serde(rename = "instId")]
         inst_id: String,
     },
 
-    /// Candlesticks channel - 5 minutes
-    #[serde(rename = "candle5m")]
-    Candle5m {
+    /// Index tickers channel
+    #[serde(rename = "index-tickers")]
+    IndexTickers {
         /// Instrument ID
         #[serde(rename = "instId")]
         inst_id: String,
     },
 
-    /// Candlesticks channel - 15 minutes
-    #[serde(rename = "candle15m")]
-    Candle15m {
+    /// Funding rate channel
+    #[serde(rename = "funding-rate")]
+    FundingRate {
         /// Instrument ID
         #[serde(rename = "instId")]
         inst_id: String,
     },
 
-    /// Candlesticks channel - 1 hour
-    #[serde(rename = "candle1H")]
-    Candle1H {
+    /// Open interest channel
+    #[serde(rename = "open-interest")]
+    OpenInterest {
         /// Instrument ID
         #[serde(rename = "instId")]
         inst_id: String,
     },
 
-    /// Candlesticks channel - 4 hours
-    #[serde(rename = "candle4H")]
-    Candle4H {
+    /// Price limit channel - maximum buy / minimum sell price
+    #[serde(rename = "price-limit")]
+    PriceLimit {
         /// Instrument ID
         #[serde(rename = "instId")]
         inst_id: String,
     },
 
-    /// Candlesticks channel - 1 day
-    #[serde(rename = "candle1D")]
-    Candle1D {
-        /// Instrument ID
-        #[serde(rename = "instId")]
-        inst_id: String,
+    /// Liquidation orders channel
+    #[serde(rename = "liquidation-orders")]
+    LiquidationOrders {
+        /// Instrument type: MARGIN, SWAP, FUTURES, OPTION
+        #[serde(rename = "instType")]
+        inst_type: String,
     },
 
-    /// Mark price channel
-    #[serde(rename = "mark-price")]
-    MarkPrice {
-        /// Instrument ID
-        #[serde(rename = "instId")]
-        inst_id: String,
+    /// Option summary channel
+    #[serde(rename = "opt-summary")]
+    OptSummary {
+        /// Instrument family (e.g., "BTC-USD")
+        #[serde(rename = "instFamily")]
+        inst_family: String,
     },
 
-    /// Index tickers channel
-    #[serde(rename = "index-tickers")]
-    IndexTickers {
+    /// Estimated delivery/exercise price channel
+    #[serde(rename = "estimated-price")]
+    EstimatedPrice {
+        /// Instrument type: OPTION, FUTURES
+        #[serde(rename = "instType")]
+        inst_type: String,
+        /// Instrument family (either `inst_family` or `inst_id` is required)
+        #[serde(rename = "instFamily", skip_serializing_if = "Option::is_none")]
+        inst_family: Option<String>,
         /// Instrument ID
-        #[serde(rename = "instId")]
-        inst_id: String,
+        #[serde(rename = "instId", skip_serializing_if = "Option::is_none")]
+        inst_id: Option<String>,
     },
 
-    /// Funding rate channel
-    #[serde(rename = "funding-rate")]
-    FundingRate {
-        /// Instrument ID
-        #[serde(rename = "instId")]
-        inst_id: String,
+    /// Instruments channel - pushes instrument changes
+    #[serde(rename = "instruments")]
+    Instruments {
+        /// Instrument type: SPOT, MARGIN, SWAP, FUTURES, OPTION
+        #[serde(rename = "instType")]
+        inst_type: String,
     },
 
+    /// System status channel - scheduled maintenance
+    #[serde(rename = "status")]
+    Status,
+
+    /// Economic calendar channel (business endpoint, requires login)
+    #[serde(rename = "economic-calendar")]
+    EconomicCalendar,
+
     // ==================== Private Channels ====================
     /// Account channel - balance updates
     #[serde(rename = "account")]
@@ -201,6 +279,112 @@ pub enum Channel {
     #[serde(rename = "balance_and_position")]
     BalanceAndPosition,
 
+    /// Liquidation warning channel - positions close to liquidation
+    #[serde(rename = "liquidation-warning")]
+    LiquidationWarning {
+        /// Instrument type: MARGIN, SWAP, FUTURES, OPTION, ANY
+        #[serde(rename = "instType")]
+        inst_type: String,
+        /// Instrument family (optional)
+        #[serde(rename = "instFamily", skip_serializing_if = "Option::is_none")]
+        inst_family: Option<String>,
+        /// Instrument ID (optional)
+        #[serde(rename = "instId", skip_serializing_if = "Option::is_none")]
+        inst_id: Option<String>,
+    },
+
+    /// Account greeks channel
+    #[serde(rename = "account-greeks")]
+    AccountGreeks {
+        /// Currency (optional)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ccy: Option<String>,
+    },
+
+    /// Auto-deleveraging warning channel (public, insurance fund state)
+    #[serde(rename = "adl-warning")]
+    AdlWarning {
+        /// Instrument type: SWAP, FUTURES, OPTION
+        #[serde(rename = "instType")]
+        inst_type: String,
+        /// Instrument family (optional)
+        #[serde(rename = "instFamily", skip_serializing_if = "Option::is_none")]
+        inst_family: Option<String>,
+    },
+
+    /// Deposit info channel (business endpoint)
+    #[serde(rename = "deposit-info")]
+    DepositInfo {
+        /// Currency (optional)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ccy: Option<String>,
+    },
+
+    /// Withdrawal info channel (business endpoint)
+    #[serde(rename = "withdrawal-info")]
+    WithdrawalInfo {
+        /// Currency (optional)
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ccy: Option<String>,
+    },
+
+    // ==================== Spread Trading Channels ====================
+    /// Spread orders channel (private)
+    #[serde(rename = "sprd-orders")]
+    SprdOrders {
+        /// Spread ID (optional)
+        #[serde(rename = "sprdId", skip_serializing_if = "Option::is_none")]
+        sprd_id: Option<String>,
+    },
+
+    /// Spread trades channel (private)
+    #[serde(rename = "sprd-trades")]
+    SprdTrades {
+        /// Spread ID (optional)
+        #[serde(rename = "sprdId", skip_serializing_if = "Option::is_none")]
+        sprd_id: Option<String>,
+    },
+
+    /// Spread public trades channel
+    #[serde(rename = "sprd-public-trades")]
+    SprdPublicTrades {
+        /// Spread ID (e.g., "BTC-USDT_BTC-USDT-SWAP")
+        #[serde(rename = "sprdId")]
+        sprd_id: String,
+    },
+
+    /// Spread order book channel - 5 levels
+    #[serde(rename = "sprd-books5")]
+    SprdBooks5 {
+        /// Spread ID
+        #[serde(rename = "sprdId")]
+        sprd_id: String,
+    },
+
+    /// Spread order book channel - best bid/offer, tick-by-tick
+    #[serde(rename = "sprd-bbo-tbt")]
+    SprdBboTbt {
+        /// Spread ID
+        #[serde(rename = "sprdId")]
+        sprd_id: String,
+    },
+
+    /// Spread order book channel - full depth, tick-by-tick
+    #[serde(rename = "sprd-books-l2-tbt")]
+    SprdBooksL2Tbt {
+        /// Spread ID
+        #[serde(rename = "sprdId")]
+        sprd_id: String,
+    },
+
+    /// Spread tickers channel
+    #[serde(rename = "sprd-tickers")]
+    SprdTickers {
+        /// Spread ID
+        #[serde(rename = "sprdId")]
+        sprd_id: String,
+    },
+
     /// Grid algo orders channel
     #[serde(rename = "grid-orders")]
     GridOrders {
@@ -358,6 +542,41 @@ pub enum Channel {
         #[serde(rename = "algoId", skip_serializing_if = "Option::is_none")]
         algo_id: Option<String>,
     },
+
+    // K 线频道名包含周期（如 `candle1H`），因此以无标签变体放在末尾，
+    // 由 `channel` 字段自行编码/解析周期。
+    /// Candlesticks channel (`candle1m` ... `candle3Mutc`)
+    #[serde(untagged)]
+    Candle {
+        /// Bar size, encoded in the channel name
+        #[serde(rename = "channel", with = "candle_bar")]
+        bar: Bar,
+        /// Instrument ID
+        #[serde(rename = "instId")]
+        inst_id: String,
+    },
+
+    /// Mark price candlesticks channel (`mark-price-candle1m` ...)
+    #[serde(untagged)]
+    MarkPriceCandle {
+        /// Bar size, encoded in the channel name
+        #[serde(rename = "channel", with = "mark_price_candle_bar")]
+        bar: Bar,
+        /// Instrument ID
+        #[serde(rename = "instId")]
+        inst_id: String,
+    },
+
+    /// Index candlesticks channel (`index-candle1m` ...)
+    #[serde(untagged)]
+    IndexCandle {
+        /// Bar size, encoded in the channel name
+        #[serde(rename = "channel", with = "index_candle_bar")]
+        bar: Bar,
+        /// Index, e.g. "BTC-USD"
+        #[serde(rename = "instId")]
+        inst_id: String,
+    },
 }
 
 impl Channel {
@@ -385,9 +604,59 @@ impl Channel {
                 | Self::Quotes { .. }
                 | Self::StrucBlockTrades { .. }
                 | Self::BalanceAndPosition
+                | Self::LiquidationWarning { .. }
+                | Self::AccountGreeks { .. }
+                | Self::DepositInfo { .. }
+                | Self::WithdrawalInfo { .. }
+                | Self::SprdOrders { .. }
+                | Self::SprdTrades { .. }
+                | Self::EconomicCalendar
         )
     }
 
+    /// Get the WebSocket endpoint serving this channel.
+    ///
+    /// OKX serves candles, algo/grid, recurring-buy, RFQ, block-trade,
+    /// spread, deposit/withdrawal-info and economic-calendar channels on
+    /// `/ws/v5/business`; subscribing elsewhere fails.
+    #[must_use]
+    pub fn endpoint(&self) -> ConnectionType {
+        match self {
+            Self::Candle { .. }
+            | Self::MarkPriceCandle { .. }
+            | Self::IndexCandle { .. }
+            | Self::OrdersAlgo { .. }
+            | Self::AlgoAdvance { .. }
+            | Self::GridOrders { .. }
+            | Self::GridOrdersSpot { .. }
+            | Self::GridOrdersContract { .. }
+            | Self::GridOrdersMoon { .. }
+            | Self::GridPositions { .. }
+            | Self::GridSubOrders { .. }
+            | Self::CopyTradingLeadNotify { .. }
+            | Self::RecurringOrders { .. }
+            | Self::AlgoRecurringBuy { .. }
+            | Self::Rfqs { .. }
+            | Self::Quotes { .. }
+            | Self::StrucBlockTrades { .. }
+            | Self::PublicStrucBlockTrades { .. }
+            | Self::PublicBlockTrades { .. }
+            | Self::BlockTickers { .. }
+            | Self::DepositInfo { .. }
+            | Self::WithdrawalInfo { .. }
+            | Self::SprdOrders { .. }
+            | Self::SprdTrades { .. }
+            | Self::SprdPublicTrades { .. }
+            | Self::SprdBooks5 { .. }
+            | Self::SprdBboTbt { .. }
+            | Self::SprdBooksL2Tbt { .. }
+            | Self::SprdTickers { .. }
+            | Self::EconomicCalendar => ConnectionType::Business,
+            _ if self.is_private() => ConnectionType::Private,
+            _ => ConnectionType::Public,
+        }
+    }
+
     /// Get the channel name.
     #[must_use]
     pub fn name(&self) -> &'static str {
@@ -398,15 +667,20 @@ impl Channel {
             Self::Books50L2Tbt { .. } => "books50-l2-tbt",
             Self::BooksL2Tbt { .. } => "books-l2-tbt",
             Self::Trades { .. } => "trades",
-            Self::Candle1m { .. } => "candle1m",
-            Self::Candle5m { .. } => "candle5m",
-            Self::Candle15m { .. } => "candle15m",
-            Self::Candle1H { .. } => "candle1H",
-            Self::Candle4H { .. } => "candle4H",
-            Self::Candle1D { .. } => "candle1D",
+            Self::Candle { bar, .. } => candle_name!("candle", bar),
+            Self::MarkPriceCandle { bar, .. } => candle_name!("mark-price-candle", bar),
+            Self::IndexCandle { bar, .. } => candle_name!("index-candle", bar),
             Self::MarkPrice { .. } => "mark-price",
             Self::IndexTickers { .. } => "index-tickers",
             Self::FundingRate { .. } => "funding-rate",
+            Self::OpenInterest { .. } => "open-interest",
+            Self::PriceLimit { .. } => "price-limit",
+            Self::LiquidationOrders { .. } => "liquidation-orders",
+            Self::OptSummary { .. } => "opt-summary",
+            Self::EstimatedPrice { .. } => "estimated-price",
+            Self::Instruments { .. } => "instruments",
+            Self::Status => "status",
+            Self::EconomicCalendar => "economic-calendar",
             Self::Account { .. } => "account",
             Self::Positions { .. } => "positions",
             Self::Orders { .. } => "orders",
@@ -414,6 +688,18 @@ impl Channel {
             Self::AlgoAdvance { .. } => "algo-advance",
             Self::Fills { .. } => "fills",
             Self::BalanceAndPosition => "balance_and_position",
+            Self::LiquidationWarning { .. } => "liquidation-warning",
+            Self::AccountGreeks { .. } => "account-greeks",
+            Self::AdlWarning { .. } => "adl-warning",
+            Self::DepositInfo { .. } => "deposit-info",
+            Self::WithdrawalInfo { .. } => "withdrawal-info",
+            Self::SprdOrders { .. } => "sprd-orders",
+            Self::SprdTrades { .. } => "sprd-trades",
+            Self::SprdPublicTrades { .. } => "sprd-public-trades",
+            Self::SprdBooks5 { .. } => "sprd-books5",
+            Self::SprdBboTbt { .. } => "sprd-bbo-tbt",
+            Self::SprdBooksL2Tbt { .. } => "sprd-books-l2-tbt",
+            Self::SprdTickers { .. } => "sprd-tickers",
             Self::GridOrders { .. } => "grid-orders",
             Self::GridOrdersSpot { .. } => "grid-orders-spot",
             Self::GridOrdersContract { .. } => "grid-orders-contract",
@@ -3,17 +3,19 @@
 //! Source: OKX API v5 WebSocket API
 //! - <https://www.okx.com/docs-v5/en/#websocket-api>
 
+use std::collections::VecDeque;
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::Duration;
 
 use futures_util::{
     stream::{SplitSink, SplitStream},
-    SinkExt, Stream, StreamExt,
+    Sink, SinkExt, Stream, StreamExt,
 };
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
-use tokio::time::{interval, Interval};
+use tokio::time::{sleep, Instant, Sleep};
 use tokio_tungstenite::{
     connect_async,
     tungstenite::{Error as WsError, Message},
@@ -21,10 +23,13 @@ use tokio_tungstenite::{
 };
 use tracing::{debug, error, info, warn};
 
-use okx_core::{Config, OkxError, Result, Signer};
+use serde_json::Value;
+
+use okx_core::{types::ApiResponse, Config, OkxError, Result, Signer};
 
 use crate::channel::Channel;
-use crate::message::{WsMessage, WsRequest};
+use crate::message::{WsEvent, WsMessage, WsRequest};
+use crate::reconnect::ConnectionType;
 
 /// WebSocket client for OKX exchange.
 ///
@@ -39,27 +44,49 @@ where
     stream: SplitStream<WebSocketStream<S>>,
     /// Client configuration
     config: Config,
-    /// Whether this is a private connection
-    is_private: bool,
+    /// Endpoint this connection is attached to
+    conn_type: ConnectionType,
     /// Whether logged in (for private connections)
     is_logged_in: bool,
-    /// Heartbeat interval
-    heartbeat: Interval,
+    /// Heartbeat timer: fires after `ping_interval` of silence, or `pong_timeout` after a ping
+    heartbeat: Option<Pin<Box<Sleep>>>,
+    /// Idle time before sending a ping
+    ping_interval: Duration,
+    /// Deadline for any message after a ping
+    pong_timeout: Duration,
+    /// Whether a ping was sent and nothing has been received since
+    awaiting_pong: bool,
+    /// Whether the connection was declared dead by the heartbeat
+    timed_out: bool,
+    /// Messages received while waiting for an op response, yielded by the stream first
+    pending: VecDeque<Result<WsMessage>>,
+    /// Last request ID used for trading ops
+    last_request_id: u64,
 }
 
 impl<S> WsClientInner<S>
 where
     S: AsyncRead + AsyncWrite + Unpin,
 {
-    fn new(ws_stream: WebSocketStream<S>, config: Config, is_private: bool) -> Self {
+    fn new(ws_stream: WebSocketStream<S>, config: Config, conn_type: ConnectionType) -> Self {
         let (sink, stream) = ws_stream.split();
+        let ping_interval = Duration::from_secs(config.ws_ping_interval_secs());
+        let pong_timeout = Duration::from_secs(config.ws_pong_timeout_secs());
+        // ping 间隔为 0 时关闭心跳
+        let heartbeat = (!ping_interval.is_zero()).then(|| Box::pin(sleep(ping_interval)));
         Self {
             sink,
             stream,
             config,
-            is_private,
+            conn_type,
             is_logged_in: false,
-            heartbeat: interval(Duration::from_secs(25)),
+            heartbeat,
+            ping_interval,
+            pong_timeout,
+            awaiting_pong: false,
+            timed_out: false,
+            pending: VecDeque::new(),
+            last_request_id: 0,
         }
     }
 
@@ -130,7 +157,7 @@ where
         timestamp: &str,
         sign: &str,
     ) -> Result<()> {
-        if !self.is_private {
+        if self.conn_type == ConnectionType::Public {
             return Err(OkxError::Auth(
                 "Cannot login on public WebSocket connection".to_string(),
             ));
@@ -145,7 +172,7 @@ where
         self.send_request(&request).await?;
 
         // Wait for login response
-        while let Some(result) = self.stream.next().await {
+        while let Some(result) = self.next_frame().await {
             match result {
                 Ok(Message::Text(text)) => {
                     let msg = WsMessage::parse(&text);
@@ -179,7 +206,8 @@ where
                             error!("{}", err_msg);
                             return Err(OkxError::Auth(err_msg));
                         }
-                        _ => continue,
+                        // 登录前到达的其他消息保留给流
+                        other => self.pending.push_back(Ok(other)),
                     }
                 }
                 Ok(Message::Ping(data)) => {
@@ -202,36 +230,159 @@ where
     ///
     /// For private channels, login must be called first.
     pub async fn subscribe(&mut self, channels: Vec<Channel>) -> Result<()> {
-        // Check if any channel is private and we need to login
-        let has_private = channels.iter().any(|c| c.is_private());
-        if has_private && !self.is_logged_in {
-            if self.is_private {
-                self.login().await?;
-            } else {
+        self.ensure_login_for(&channels).await?;
+        let request = WsRequest::subscribe(channel_args(&channels));
+        self.send_request(&request).await
+    }
+
+    /// Unsubscribe from channels.
+    pub async fn unsubscribe(&mut self, channels: Vec<Channel>) -> Result<()> {
+        let request = WsRequest::unsubscribe(channel_args(&channels));
+        self.send_request(&request).await
+    }
+
+    /// Subscribe to channels and wait for the acknowledgement of each one.
+    pub async fn subscribe_confirmed(
+        &mut self,
+        channels: Vec<Channel>,
+    ) -> Result<Vec<(Channel, Result<()>)>> {
+        self.ensure_login_for(&channels).await?;
+        let args = channel_args(&channels);
+        self.send_request(&WsRequest::subscribe(args.clone()))
+            .await?;
+        self.wait_acks(WsEvent::Subscribe, channels, &args).await
+    }
+
+    /// Unsubscribe from channels and wait for the acknowledgement of each one.
+    pub async fn unsubscribe_confirmed(
+        &mut self,
+        channels: Vec<Channel>,
+    ) -> Result<Vec<(Channel, Result<()>)>> {
+        let args = channel_args(&channels);
+        self.send_request(&WsRequest::unsubscribe(args.clone()))
+            .await?;
+        self.wait_acks(WsEvent::Unsubscribe, channels, &args).await
+    }
+
+    /// Check that every channel is served by this endpoint, then login if any is private.
+    async fn ensure_login_for(&mut self, channels: &[Channel]) -> Result<()> {
+        for channel in channels {
+            let endpoint = channel.endpoint();
+            if endpoint == self.conn_type {
+                continue;
+            }
+            if channel.is_private() && self.conn_type == ConnectionType::Public {
                 return Err(OkxError::Auth(
                     "Cannot subscribe to private channels on public connection".to_string(),
                 ));
             }
+            return Err(OkxError::InvalidParameter(format!(
+                "Channel {} is served by the {:?} endpoint, not {:?}",
+                channel.name(),
+                endpoint,
+                self.conn_type
+            )));
         }
 
-        let args: Vec<_> = channels
-            .iter()
-            .map(|c| serde_json::to_value(c).expect("Channel serialization should not fail"))
-            .collect();
+        let has_private = channels.iter().any(Channel::is_private);
+        if !has_private || self.is_logged_in {
+            return Ok(());
+        }
+        self.login().await
+    }
 
-        let request = WsRequest::subscribe(args);
-        self.send_request(&request).await
+    /// Wait for the `event` ack (or error) of every channel, up to `Config::timeout_secs`.
+    ///
+    /// Channels without an answer when the timeout expires get [`OkxError::Timeout`].
+    async fn wait_acks(
+        &mut self,
+        event: WsEvent,
+        channels: Vec<Channel>,
+        args: &[Value],
+    ) -> Result<Vec<(Channel, Result<()>)>> {
+        let mut results: Vec<Option<Result<()>>> =
+            std::iter::repeat_with(|| None).take(args.len()).collect();
+
+        let wait = Duration::from_secs(self.config.timeout_secs());
+        if let Ok(Err(e)) =
+            tokio::time::timeout(wait, self.collect_acks(event, args, &mut results)).await
+        {
+            return Err(e);
+        }
+
+        Ok(channels
+            .into_iter()
+            .zip(results)
+            .map(|(channel, result)| (channel, result.unwrap_or(Err(OkxError::Timeout))))
+            .collect())
     }
 
-    /// Unsubscribe from channels.
-    pub async fn unsubscribe(&mut self, channels: Vec<Channel>) -> Result<()> {
-        let args: Vec<_> = channels
-            .iter()
-            .map(|c| serde_json::to_value(c).expect("Channel serialization should not fail"))
-            .collect();
+    /// Read acks into `results`, buffering everything else.
+    ///
+    /// Acks and errors are matched by `arg`; an error without `arg` (OKX
+    /// rejects malformed requests as a whole) fails every unanswered channel.
+    async fn collect_acks(
+        &mut self,
+        event: WsEvent,
+        args: &[Value],
+        results: &mut [Option<Result<()>>],
+    ) -> Result<()> {
+        while results.iter().any(Option::is_none) {
+            let Some(next) = self.next_frame().await else {
+                return Err(OkxError::ConnectionClosed);
+            };
+            let msg = match Self::handle_message(next) {
+                Some(Ok(msg)) => msg,
+                Some(Err(e)) => return Err(e),
+                None => continue,
+            };
 
-        let request = WsRequest::unsubscribe(args);
-        self.send_request(&request).await
+            let WsMessage::Event {
+                event: ref received,
+                ref arg,
+                ref code,
+                msg: ref text,
+                ..
+            } = msg
+            else {
+                self.pending.push_back(Ok(msg));
+                continue;
+            };
+
+            let is_error = *received == WsEvent::Error;
+            if !is_error && *received != event {
+                self.pending.push_back(Ok(msg));
+                continue;
+            }
+
+            let unanswered = |i: &usize| results[*i].is_none();
+            let matched: Vec<usize> = match arg {
+                Some(arg) => (0..args.len())
+                    .filter(unanswered)
+                    .find(|&i| arg_matches(&args[i], arg))
+                    .into_iter()
+                    .collect(),
+                None if is_error => (0..args.len()).filter(unanswered).collect(),
+                None => Vec::new(),
+            };
+            if matched.is_empty() {
+                self.pending.push_back(Ok(msg));
+                continue;
+            }
+
+            for i in matched {
+                results[i] = Some(if is_error {
+                    Err(OkxError::api(
+                        code.clone().unwrap_or_default(),
+                        text.clone().unwrap_or_default(),
+                    ))
+                } else {
+                    Ok(())
+                });
+            }
+        }
+
+        Ok(())
     }
 
     /// Send a ping to keep the connection alive.
@@ -262,13 +413,157 @@ where
             .map_err(|e| OkxError::WebSocket(e.to_string()))
     }
 
+    /// Send a trading op and wait for the response carrying the same `id`.
+    ///
+    /// Logs in first if needed. Other messages received in the meantime are
+    /// buffered and yielded by the stream afterwards. Times out after
+    /// `Config::timeout_secs`. Spread ops (`sprd-*`) go to the business
+    /// endpoint, every other op to the private one.
+    pub async fn send_op(&mut self, op: &str, args: Vec<Value>) -> Result<ApiResponse<Value>> {
+        if self.conn_type == ConnectionType::Public {
+            return Err(OkxError::Auth(format!(
+                "Cannot send {op} on public WebSocket connection"
+            )));
+        }
+        let expected = if op.starts_with("sprd-") {
+            ConnectionType::Business
+        } else {
+            ConnectionType::Private
+        };
+        if self.conn_type != expected {
+            return Err(OkxError::InvalidParameter(format!(
+                "Op {op} must be sent on the {expected:?} WebSocket endpoint, not {:?}",
+                self.conn_type
+            )));
+        }
+        if !self.is_logged_in {
+            self.login().await?;
+        }
+
+        self.last_request_id += 1;
+        let id = self.last_request_id.to_string();
+        self.send_request(&WsRequest::op(id.clone(), op, args))
+            .await?;
+
+        let wait = Duration::from_secs(self.config.timeout_secs());
+        tokio::time::timeout(wait, self.wait_response(&id))
+            .await
+            .map_err(|_| OkxError::Timeout)?
+    }
+
+    /// Read until the op response with `id` arrives, buffering everything else.
+    async fn wait_response(&mut self, id: &str) -> Result<ApiResponse<Value>> {
+        while let Some(result) = self.next_frame().await {
+            match Self::handle_message(result) {
+                Some(Ok(WsMessage::Response {
+                    id: response_id,
+                    code,
+                    msg,
+                    data,
+                    ..
+                })) if response_id == id => {
+                    return Ok(ApiResponse { code, msg, data });
+                }
+                Some(Err(e)) => return Err(e),
+                Some(other) => self.pending.push_back(other),
+                None => {}
+            }
+        }
+
+        Err(OkxError::ConnectionClosed)
+    }
+
     /// Process the next message from the WebSocket.
     fn process_message(
         &mut self,
         result: std::result::Result<Message, WsError>,
     ) -> Option<Result<WsMessage>> {
+        self.record_activity();
         Self::handle_message(result)
     }
+
+    /// Read the next frame outside the stream, keeping the heartbeat informed.
+    async fn next_frame(&mut self) -> Option<std::result::Result<Message, WsError>> {
+        let frame = self.stream.next().await;
+        self.record_activity();
+        frame
+    }
+
+    /// Any received frame proves the connection alive: restart the idle timer.
+    fn record_activity(&mut self) {
+        self.awaiting_pong = false;
+        if let Some(heartbeat) = &mut self.heartbeat {
+            heartbeat
+                .as_mut()
+                .reset(Instant::now() + self.ping_interval);
+        }
+    }
+
+    /// Drive the heartbeat timer.
+    ///
+    /// Sends a `ping` after `ping_interval` of silence and returns
+    /// [`OkxError::ConnectionClosed`] if nothing arrives within `pong_timeout`.
+    fn poll_heartbeat(&mut self, cx: &mut Context<'_>) -> Option<OkxError> {
+        if self.awaiting_pong {
+            // 确保 ping 已真正写出
+            let _ = Pin::new(&mut self.sink).poll_flush(cx);
+        }
+
+        loop {
+            let heartbeat = self.heartbeat.as_mut()?;
+            if heartbeat.as_mut().poll(cx).is_pending() {
+                return None;
+            }
+
+            if self.awaiting_pong {
+                warn!(
+                    "No pong within {:?}, treating WebSocket connection as dead",
+                    self.pong_timeout
+                );
+                self.timed_out = true;
+                return Some(OkxError::ConnectionClosed);
+            }
+
+            debug!("Connection idle for {:?}, sending ping", self.ping_interval);
+            match Pin::new(&mut self.sink).poll_ready(cx) {
+                Poll::Ready(Ok(())) => {
+                    if let Err(e) =
+                        Pin::new(&mut self.sink).start_send(Message::Text("ping".into()))
+                    {
+                        return Some(OkxError::WebSocket(e.to_string()));
+                    }
+                    let _ = Pin::new(&mut self.sink).poll_flush(cx);
+                }
+                Poll::Ready(Err(e)) => return Some(OkxError::WebSocket(e.to_string())),
+                // 写端阻塞时同样进入 pong 等待，超时即视为断线
+                Poll::Pending => {}
+            }
+
+            self.awaiting_pong = true;
+            let deadline = Instant::now() + self.pong_timeout;
+            if let Some(heartbeat) = &mut self.heartbeat {
+                heartbeat.as_mut().reset(deadline);
+            }
+        }
+    }
+}
+
+/// 将频道序列化为请求参数。
+fn channel_args(channels: &[Channel]) -> Vec<Value> {
+    channels
+        .iter()
+        .map(|c| serde_json::to_value(c).expect("Channel serialization should not fail"))
+        .collect()
+}
+
+/// 确认消息中的 `arg` 需包含请求参数的全部字段（服务端可能附带 `uid` 等额外字段）。
+fn arg_matches(expected: &Value, actual: &Value) -> bool {
+    match (expected.as_object(), actual.as_object()) {
+        (Some(expected), Some(actual)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key) == Some(value)),
+        _ => expected == actual,
+    }
 }
 
 impl<S> Stream for WsClientInner<S>
@@ -278,25 +573,33 @@ where
     type Item = Result<WsMessage>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        // Check heartbeat timer
-        if self.heartbeat.poll_tick(cx).is_ready() {
-            // We should send a ping, but we can't do async here
-            // The user should call ping() periodically or we handle it differently
+        // 先返回等待 op 响应期间缓存的消息
+        if let Some(msg) = self.pending.pop_front() {
+            return Poll::Ready(Some(msg));
+        }
+
+        if self.timed_out {
+            return Poll::Ready(None);
         }
 
         // Poll the WebSocket stream
         match Pin::new(&mut self.stream).poll_next(cx) {
             Poll::Ready(Some(result)) => {
                 if let Some(msg) = self.process_message(result) {
-                    Poll::Ready(Some(msg))
-                } else {
-                    // Message was filtered out, poll again
-                    cx.waker().wake_by_ref();
-                    Poll::Pending
+                    return Poll::Ready(Some(msg));
                 }
+                // Message was filtered out, poll again
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
             }
-            Poll::Ready(None) => Poll::Ready(None),
-            Poll::Pending => Poll::Pending,
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => {}
+        }
+
+        // Nothing to read: send a ping when idle, fail when the pong is overdue
+        match self.poll_heartbeat(cx) {
+            Some(e) => Poll::Ready(Some(Err(e))),
+            None => Poll::Pending,
         }
     }
 }
@@ -328,7 +631,7 @@ impl WsClient {
             .map_err(|e| OkxError::WebSocket(e.to_string()))?;
 
         Ok(Self {
-            inner: WsClientInner::new(ws_stream, config.clone(), false),
+            inner: WsClientInner::new(ws_stream, config.clone(), ConnectionType::Public),
         })
     }
 
@@ -344,10 +647,33 @@ impl WsClient {
             .map_err(|e| OkxError::WebSocket(e.to_string()))?;
 
         Ok(Self {
-            inner: WsClientInner::new(ws_stream, config.clone(), true),
+            inner: WsClientInner::new(ws_stream, config.clone(), ConnectionType::Private),
+        })
+    }
+
+    /// Connect to the business WebSocket endpoint.
+    ///
+    /// Serves candles, algo/grid, recurring-buy, RFQ and block-trade channels.
+    /// Private business channels log in automatically on subscribe.
+    pub async fn connect_business(config: &Config) -> Result<Self> {
+        let url = config.ws_business_url();
+        info!("Connecting to business WebSocket: {}", url);
+
+        let (ws_stream, _) = connect_async(url)
+            .await
+            .map_err(|e| OkxError::WebSocket(e.to_string()))?;
+
+        Ok(Self {
+            inner: WsClientInner::new(ws_stream, config.clone(), ConnectionType::Business),
         })
     }
 
+    /// Get the endpoint this client is connected to.
+    #[must_use]
+    pub fn connection_type(&self) -> ConnectionType {
+        self.inner.conn_type
+    }
+
     /// Login to the private WebSocket.
     ///
     /// Must be called before subscribing to private channels.
@@ -379,6 +705,29 @@ impl WsClient {
         self.inner.unsubscribe(channels).await
     }
 
+    /// Subscribe to channels and wait for the acknowledgement of each one.
+    ///
+    /// Returns one result per channel, in request order: `Ok(())` on the
+    /// `subscribe` ack, [`OkxError::Api`] on an `error` event and
+    /// [`OkxError::Timeout`] if nothing arrived within `Config::timeout_secs`.
+    /// Other messages received meanwhile are kept for the stream.
+    pub async fn subscribe_confirmed(
+        &mut self,
+        channels: Vec<Channel>,
+    ) -> Result<Vec<(Channel, Result<()>)>> {
+        self.inner.subscribe_confirmed(channels).await
+    }
+
+    /// Unsubscribe from channels and wait for the acknowledgement of each one.
+    ///
+    /// See [`WsClient::subscribe_confirmed`] for the per-channel results.
+    pub async fn unsubscribe_confirmed(
+        &mut self,
+        channels: Vec<Channel>,
+    ) -> Result<Vec<(Channel, Result<()>)>> {
+        self.inner.unsubscribe_confirmed(channels).await
+    }
+
     /// Send a ping to keep the connection alive.
     pub async fn ping(&mut self) -> Result<()> {
         self.inner.ping().await
@@ -388,6 +737,15 @@ impl WsClient {
     pub async fn close(&mut self) -> Result<()> {
         self.inner.close().await
     }
+
+    /// Send a trading op and wait for the response carrying the same `id`.
+    pub(crate) async fn send_op(
+        &mut self,
+        op: &str,
+        args: Vec<Value>,
+    ) -> Result<ApiResponse<Value>> {
+        self.inner.send_op(op, args).await
+    }
 }
 
 impl Stream for WsClient {
@@ -403,6 +761,7 @@ mod tests {
     use super::*;
 
     use futures_util::{SinkExt, StreamExt};
+    use okx_core::types::Bar;
     use okx_core::Credentials;
     use tokio::io::{duplex, DuplexStream};
     use tokio::time::timeout;
@@ -422,7 +781,12 @@ mod tests {
         let server_ws = server_res.expect("in-memory server handshake 失败");
 
         let cfg = Config::new(Credentials::new("k", "s", "p")).simulated(true);
-        (WsClientInner::new(client_ws, cfg, is_private), server_ws)
+        let conn_type = if is_private {
+            ConnectionType::Private
+        } else {
+            ConnectionType::Public
+        };
+        (WsClientInner::new(client_ws, cfg, conn_type), server_ws)
     }
 
     #[tokio::test]
@@ -527,6 +891,31 @@ mod tests {
         assert!(matches!(err, OkxError::Auth(_)));
     }
 
+    #[tokio::test]
+    async fn subscribing_channel_on_wrong_endpoint_is_rejected() {
+        let (mut public, _public_server) = in_memory_client(false).await;
+        let err = public
+            .subscribe(vec![Channel::Candle {
+                bar: Bar::M1,
+                inst_id: "BTC-USDT".to_string(),
+            }])
+            .await
+            .expect_err("K 线频道应走业务连接");
+        assert!(matches!(err, OkxError::InvalidParameter(_)));
+
+        let (mut private, _private_server) = in_memory_client(true).await;
+        let err = private
+            .subscribe_confirmed(vec![Channel::GridPositions {
+                algo_id: None,
+                inst_type: None,
+                inst_id: None,
+            }])
+            .await
+            .expect_err("网格频道应走业务连接");
+        assert!(matches!(err, OkxError::InvalidParameter(_)));
+        assert!(!private.is_logged_in);
+    }
+
     #[tokio::test]
     async fn subscribe_unsubscribe_and_poll_next_filters_pong() {
         let (mut client, mut server) = in_memory_client(false).await;
@@ -624,4 +1013,235 @@ mod tests {
         };
         assert_eq!(text, "ping");
     }
+
+    #[tokio::test]
+    async fn send_op_matches_response_by_id_and_buffers_pushes() {
+        let (mut client, mut server) = in_memory_client(true).await;
+
+        let server_task = tokio::spawn(async move {
+            let _ = server.next().await;
+            let _ = server
+                .send(Message::Text(
+                    r#"{"event":"login","code":"0","msg":""}"#.into(),
+                ))
+                .await;
+
+            let msg = server
+                .next()
+                .await
+                .expect("应收到 order 请求")
+                .expect("消息应为 Ok");
+            let Message::Text(text) = msg else {
+                panic!("预期 Text order 请求，实际为: {msg:?}");
+            };
+            let v: serde_json::Value = serde_json::from_str(&text).expect("order 请求应为 JSON");
+            assert_eq!(v.get("op").and_then(|x| x.as_str()), Some("order"));
+            let id = v
+                .get("id")
+                .and_then(|x| x.as_str())
+                .expect("应携带 id")
+                .to_string();
+
+            let _ = server
+                .send(Message::Text(
+                    r#"{"arg":{"channel":"orders","instType":"ANY"},"data":[]}"#.into(),
+                ))
+                .await;
+            let _ = server
+                .send(Message::Text(
+                    r#"{"id":"other","op":"order","code":"0","msg":"","data":[]}"#.into(),
+                ))
+                .await;
+            let _ = server
+                .send(Message::Text(
+                    format!(r#"{{"id":"{id}","op":"order","code":"0","msg":"","data":[{{"ordId":"7","sCode":"0","sMsg":""}}]}}"#)
+                        .into(),
+                ))
+                .await;
+            server
+        });
+
+        let response = client
+            .send_op("order", vec![serde_json::json!({"instId": "BTC-USDT"})])
+            .await
+            .expect("op 应成功");
+        assert!(response.is_success());
+        assert_eq!(response.data[0]["ordId"], "7");
+
+        // 等待期间收到的推送按顺序留在流中
+        let msg = client
+            .next()
+            .await
+            .expect("应收到缓存消息")
+            .expect("消息应为 Ok");
+        assert!(matches!(msg, WsMessage::Data { ref channel, .. } if channel == "orders"));
+        let msg = client
+            .next()
+            .await
+            .expect("应收到缓存消息")
+            .expect("消息应为 Ok");
+        assert!(matches!(msg, WsMessage::Response { ref id, .. } if id == "other"));
+
+        let _server = server_task.await.expect("服务端任务不应 panic");
+    }
+
+    #[tokio::test]
+    async fn send_op_times_out_and_rejects_public_connection() {
+        let (mut public, _public_server) = in_memory_client(false).await;
+        let err = public
+            .send_op("order", Vec::new())
+            .await
+            .expect_err("公有连接不应允许交易操作");
+        assert!(matches!(err, OkxError::Auth(_)));
+
+        // 价差操作只能走业务连接，普通交易操作只能走私有连接
+        let (mut private, _private_server) = in_memory_client(true).await;
+        let err = private
+            .send_op("sprd-order", Vec::new())
+            .await
+            .expect_err("私有连接不应允许价差操作");
+        assert!(matches!(err, OkxError::InvalidParameter(_)));
+        private.conn_type = ConnectionType::Business;
+        let err = private
+            .send_op("order", Vec::new())
+            .await
+            .expect_err("业务连接不应允许普通交易操作");
+        assert!(matches!(err, OkxError::InvalidParameter(_)));
+
+        let (mut client, mut server) = in_memory_client(true).await;
+        client.config = client.config.clone().with_timeout_secs(1);
+        client.is_logged_in = true;
+
+        let server_task = tokio::spawn(async move {
+            let _ = server.next().await;
+            server
+        });
+
+        let err = client
+            .send_op("cancel-order", Vec::new())
+            .await
+            .expect_err("无响应时应超时");
+        assert!(matches!(err, OkxError::Timeout));
+
+        let _server = server_task.await.expect("服务端任务不应 panic");
+    }
+
+    #[tokio::test]
+    async fn subscribe_confirmed_matches_acks_by_arg_and_buffers_data() {
+        let (mut client, mut server) = in_memory_client(false).await;
+        client.config = client.config.clone().with_timeout_secs(1);
+
+        let server_task = tokio::spawn(async move {
+            let _ = server.next().await;
+            for text in [
+                r#"{"arg":{"channel":"tickers","instId":"ETH-USDT"},"data":[{"last":"1"}]}"#,
+                r#"{"event":"error","arg":{"channel":"books","instId":"FOO-BAR"},"code":"60018","msg":"doesn't exist"}"#,
+                r#"{"event":"subscribe","arg":{"channel":"tickers","instId":"BTC-USDT"},"connId":"a"}"#,
+            ] {
+                let _ = server.send(Message::Text(text.into())).await;
+            }
+            server
+        });
+
+        let results = client
+            .subscribe_confirmed(vec![
+                Channel::Books {
+                    inst_id: "FOO-BAR".to_string(),
+                },
+                Channel::Tickers {
+                    inst_id: "BTC-USDT".to_string(),
+                },
+                Channel::Tickers {
+                    inst_id: "SOL-USDT".to_string(),
+                },
+            ])
+            .await
+            .expect("请求应发送成功");
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(&results[0].1, Err(OkxError::Api { code, .. }) if *code == "60018"));
+        assert!(results[1].1.is_ok());
+        assert!(matches!(results[2].1, Err(OkxError::Timeout)));
+
+        // 等待确认期间收到的推送留在流中
+        let msg = client
+            .next()
+            .await
+            .expect("应收到缓存消息")
+            .expect("消息应为 Ok");
+        assert!(matches!(msg, WsMessage::Data { ref channel, .. } if channel == "tickers"));
+
+        let _server = server_task.await.expect("服务端任务不应 panic");
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_confirmed_fails_all_channels_on_error_without_arg() {
+        let (mut client, mut server) = in_memory_client(false).await;
+
+        let server_task = tokio::spawn(async move {
+            let _ = server.next().await;
+            let _ = server
+                .send(Message::Text(
+                    r#"{"event":"error","code":"60012","msg":"Invalid request"}"#.into(),
+                ))
+                .await;
+            server
+        });
+
+        let results = client
+            .unsubscribe_confirmed(vec![
+                Channel::Tickers {
+                    inst_id: "BTC-USDT".to_string(),
+                },
+                Channel::Trades {
+                    inst_id: "BTC-USDT".to_string(),
+                },
+            ])
+            .await
+            .expect("请求应发送成功");
+        assert!(results.iter().all(|(_, r)| r.is_err()));
+
+        let _server = server_task.await.expect("服务端任务不应 panic");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_connection_sends_ping_and_closes_without_pong() {
+        let (mut client, mut server) = in_memory_client(false).await;
+
+        let server_task = tokio::spawn(async move {
+            for reply in [Some("pong"), None] {
+                let msg = server
+                    .next()
+                    .await
+                    .expect("应收到 ping")
+                    .expect("消息应为 Ok");
+                assert_eq!(msg, Message::Text("ping".into()));
+                if let Some(reply) = reply {
+                    let _ = server.send(Message::Text(reply.into())).await;
+                }
+            }
+            server
+        });
+
+        let started = Instant::now();
+        let msg = client
+            .next()
+            .await
+            .expect("应收到 pong")
+            .expect("消息应为 Ok");
+        assert!(matches!(msg, WsMessage::Pong));
+        assert!(started.elapsed() >= Duration::from_secs(20));
+
+        // 第二次 ping 无响应：pong 超时后报告连接关闭并结束流
+        let err = client
+            .next()
+            .await
+            .expect("应收到错误")
+            .expect_err("pong 超时应报错");
+        assert!(matches!(err, OkxError::ConnectionClosed));
+        assert!(started.elapsed() >= Duration::from_secs(50));
+        assert!(client.next().await.is_none());
+
+        let _server = server_task.await.expect("服务端任务不应 panic");
+    }
 }
@@ -0,0 +1,311 @@
+//! Typed WebSocket data payloads.
+//!
+//! [`WsMessage::Data`] keeps the raw JSON; [`WsData`] decodes it into the
+//! `okx-core` types by channel name, falling back to [`WsData::Raw`] for
+//! channels without a typed model.
+//!
+//! Source: OKX API v5 WebSocket channels
+//! - <https://www.okx.com/docs-v5/en/#websocket-api>
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::debug;
+
+use okx_core::types::{
+    AccountGreeks, AdlWarning, AlgoOrder, Balance, Candle, DepositRecord, FundingRate, IndexTicker,
+    Instrument, MarkPrice, Order, OrderBook, Position, Ticker, Trade, WithdrawalRecord,
+};
+use okx_core::{OkxError, Result};
+
+use crate::message::WsMessage;
+
+/// Decoded data of a push message.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum WsData {
+    /// `tickers`
+    Tickers(Vec<Ticker>),
+    /// `books`, `books5`, `bbo-tbt`, `books-l2-tbt`, `books50-l2-tbt`
+    Books(Vec<OrderBook>),
+    /// `trades`, `trades-all`
+    Trades(Vec<Trade>),
+    /// `candle*`, `mark-price-candle*`, `index-candle*` (the bar is part of
+    /// the channel name; price candles carry no volume)
+    Candles(Vec<Candle>),
+    /// `mark-price`
+    MarkPrice(Vec<MarkPrice>),
+    /// `index-tickers`
+    IndexTickers(Vec<IndexTicker>),
+    /// `funding-rate`
+    FundingRate(Vec<FundingRate>),
+    /// `instruments`
+    Instruments(Vec<Instrument>),
+    /// `account`
+    Account(Vec<Balance>),
+    /// `positions`
+    Positions(Vec<Position>),
+    /// `orders`
+    Orders(Vec<Order>),
+    /// `orders-algo`
+    OrdersAlgo(Vec<AlgoOrder>),
+    /// `liquidation-warning` (positions close to liquidation)
+    LiquidationWarning(Vec<Position>),
+    /// `account-greeks`
+    AccountGreeks(Vec<AccountGreeks>),
+    /// `adl-warning`
+    AdlWarning(Vec<AdlWarning>),
+    /// `deposit-info`
+    DepositInfo(Vec<DepositRecord>),
+    /// `withdrawal-info`
+    WithdrawalInfo(Vec<WithdrawalRecord>),
+    /// Channel without a typed model, or payload that failed to decode
+    Raw {
+        /// Channel name
+        channel: String,
+        /// Raw data payload
+        data: Vec<Value>,
+    },
+}
+
+impl WsData {
+    /// Decode the data of a push message by channel name.
+    ///
+    /// Returns `None` for non-data messages. Unknown channels and payloads that
+    /// do not match the typed model are returned as [`WsData::Raw`].
+    #[must_use]
+    pub fn from_message(msg: &WsMessage) -> Option<Self> {
+        let WsMessage::Data { channel, data, .. } = msg else {
+            return None;
+        };
+        Some(Self::decode(channel, data))
+    }
+
+    /// Decode a data payload of the given channel.
+    #[must_use]
+    pub fn decode(channel: &str, data: &[Value]) -> Self {
+        let typed = match channel {
+            "tickers" => decode_vec(data).map(Self::Tickers),
+            "books" | "books5" | "bbo-tbt" | "books-l2-tbt" | "books50-l2-tbt" => {
+                decode_vec(data).map(Self::Books)
+            }
+            "trades" | "trades-all" => decode_vec(data).map(Self::Trades),
+            "mark-price" => decode_vec(data).map(Self::MarkPrice),
+            "index-tickers" => decode_vec(data).map(Self::IndexTickers),
+            "funding-rate" => decode_vec(data).map(Self::FundingRate),
+            "instruments" => decode_vec(data).map(Self::Instruments),
+            "account" => decode_vec(data).map(Self::Account),
+            "positions" => decode_vec(data).map(Self::Positions),
+            "orders" => decode_vec(data).map(Self::Orders),
+            "orders-algo" => decode_vec(data).map(Self::OrdersAlgo),
+            "liquidation-warning" => decode_vec(data).map(Self::LiquidationWarning),
+            "account-greeks" => decode_vec(data).map(Self::AccountGreeks),
+            "adl-warning" => decode_vec(data).map(Self::AdlWarning),
+            "deposit-info" => decode_vec(data).map(Self::DepositInfo),
+            "withdrawal-info" => decode_vec(data).map(Self::WithdrawalInfo),
+            name if name.starts_with("candle")
+                || name.starts_with("mark-price-candle")
+                || name.starts_with("index-candle") =>
+            {
+                decode_candles(data).map(Self::Candles)
+            }
+            _ => return Self::raw(channel, data),
+        };
+
+        typed.unwrap_or_else(|e| {
+            debug!("Falling back to raw data for channel {}: {}", channel, e);
+            Self::raw(channel, data)
+        })
+    }
+
+    fn raw(channel: &str, data: &[Value]) -> Self {
+        Self::Raw {
+            channel: channel.to_string(),
+            data: data.to_vec(),
+        }
+    }
+
+    /// Check if the payload was not decoded into a typed model.
+    #[must_use]
+    pub fn is_raw(&self) -> bool {
+        matches!(self, Self::Raw { .. })
+    }
+}
+
+impl WsMessage {
+    /// Decode the data of a push message into `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OkxError::WebSocket`] for non-data messages and
+    /// [`OkxError::Serde`] if an element does not match `T`.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<Vec<T>> {
+        match self {
+            Self::Data { data, .. } => decode_vec(data),
+            other => Err(OkxError::WebSocket(format!(
+                "Cannot decode non-data message: {other:?}"
+            ))),
+        }
+    }
+
+    /// Decode the data of a push message by channel, see [`WsData::from_message`].
+    #[must_use]
+    pub fn typed_data(&self) -> Option<WsData> {
+        WsData::from_message(self)
+    }
+}
+
+fn decode_vec<T: DeserializeOwned>(data: &[Value]) -> Result<Vec<T>> {
+    data.iter()
+        .map(|item| T::deserialize(item).map_err(OkxError::from))
+        .collect()
+}
+
+/// K 线推送为字符串数组：`[ts, o, h, l, c, vol, volCcy, volCcyQuote, confirm]`，
+/// 标记价格/指数 K 线为 `[ts, o, h, l, c, confirm]`。
+fn decode_candles(data: &[Value]) -> Result<Vec<Candle>> {
+    data.iter()
+        .map(|item| {
+            let fields: Vec<String> = serde_json::from_value(item.clone())?;
+            Candle::from_array(&fields)
+                .ok_or_else(|| OkxError::WebSocket(format!("Invalid candle payload: {item}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn data_msg(channel: &str, data: Value) -> WsMessage {
+        WsMessage::parse(&json!({"arg": {"channel": channel}, "data": data}).to_string())
+    }
+
+    #[test]
+    fn decodes_known_channels() {
+        let msg = data_msg(
+            "mark-price",
+            json!([{"instType": "SWAP", "instId": "BTC-USDT-SWAP", "markPx": "43000", "ts": "1"}]),
+        );
+        match msg.typed_data() {
+            Some(WsData::MarkPrice(prices)) => assert_eq!(prices[0].mark_px, "43000"),
+            other => panic!("应解码为 MarkPrice，实际为 {other:?}"),
+        }
+
+        let candles = data_msg(
+            "candle1H",
+            json!([["1", "2", "3", "0.5", "2.5", "10", "20", "30", "0"]]),
+        );
+        match candles.typed_data() {
+            Some(WsData::Candles(c)) => {
+                assert_eq!(c[0].close, "2.5");
+                assert!(!c[0].is_confirmed());
+            }
+            other => panic!("应解码为 Candles，实际为 {other:?}"),
+        }
+
+        let mark_candles = data_msg(
+            "mark-price-candle1Dutc",
+            json!([["1", "2", "3", "0.5", "2.5", "1"]]),
+        );
+        match mark_candles.typed_data() {
+            Some(WsData::Candles(c)) => {
+                assert_eq!(c[0].open, "2");
+                assert!(c[0].vol.is_empty());
+                assert!(c[0].is_confirmed());
+            }
+            other => panic!("标记价格 K 线应解码为 Candles，实际为 {other:?}"),
+        }
+
+        let instruments = data_msg(
+            "instruments",
+            json!([{
+                "instType": "SPOT", "instId": "BTC-USDT", "state": "live",
+                "tickSz": "0.1", "lotSz": "0.00000001", "minSz": "0.00001"
+            }]),
+        );
+        match instruments.typed_data() {
+            Some(WsData::Instruments(i)) => assert_eq!(i[0].inst_id, "BTC-USDT"),
+            other => panic!("应解码为 Instruments，实际为 {other:?}"),
+        }
+    }
+
+    #[test]
+    fn decodes_risk_and_funding_channels() {
+        let greeks = data_msg(
+            "account-greeks",
+            json!([{"ccy": "BTC", "deltaBS": "1.2", "deltaPA": "0.9", "gammaBS": "0", "gammaPA": "0",
+                    "thetaBS": "0", "thetaPA": "0", "vegaBS": "0", "vegaPA": "0", "ts": "1"}]),
+        );
+        match greeks.typed_data() {
+            Some(WsData::AccountGreeks(g)) => assert_eq!(g[0].delta_bs, "1.2"),
+            other => panic!("应解码为 AccountGreeks，实际为 {other:?}"),
+        }
+
+        let adl = data_msg(
+            "adl-warning",
+            json!([{"instType": "SWAP", "instFamily": "BTC-USDT", "state": "warning",
+                    "bal": "280", "maxBal": "300", "adlRecBal": "285", "decRate": "0.06"}]),
+        );
+        match adl.typed_data() {
+            Some(WsData::AdlWarning(w)) => {
+                assert_eq!(w[0].state, "warning");
+                assert_eq!(w[0].adl_rec_bal, "285");
+            }
+            other => panic!("应解码为 AdlWarning，实际为 {other:?}"),
+        }
+
+        let deposit = data_msg(
+            "deposit-info",
+            json!([{"ccy": "USDT", "chain": "USDT-TRC20", "amt": "100", "state": "2",
+                    "ts": "1", "depId": "88", "uid": "42", "subAcct": "desk"}]),
+        );
+        match deposit.typed_data() {
+            Some(WsData::DepositInfo(d)) => {
+                assert_eq!(d[0].dep_id, "88");
+                assert_eq!(d[0].sub_acct, "desk");
+            }
+            other => panic!("应解码为 DepositInfo，实际为 {other:?}"),
+        }
+
+        let withdrawal = data_msg(
+            "withdrawal-info",
+            json!([{"ccy": "USDT", "chain": "USDT-TRC20", "amt": "50", "state": "0",
+                    "ts": "1", "wdId": "7", "uid": "42"}]),
+        );
+        match withdrawal.typed_data() {
+            Some(WsData::WithdrawalInfo(w)) => assert_eq!(w[0].wd_id, "7"),
+            other => panic!("应解码为 WithdrawalInfo，实际为 {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_channels_and_bad_payloads_fall_back_to_raw() {
+        let unknown = data_msg("opt-summary", json!([{"foo": "bar"}]));
+        assert!(unknown.typed_data().expect("应为数据消息").is_raw());
+
+        let bad = data_msg("mark-price", json!([{"markPx": 1}]));
+        match bad.typed_data() {
+            Some(WsData::Raw { channel, data }) => {
+                assert_eq!(channel, "mark-price");
+                assert_eq!(data.len(), 1);
+            }
+            other => panic!("解码失败应回退为 Raw，实际为 {other:?}"),
+        }
+
+        assert!(WsMessage::Pong.typed_data().is_none());
+    }
+
+    #[test]
+    fn decode_generic_type() {
+        let msg = data_msg(
+            "trades",
+            json!([{"instId": "BTC-USDT", "tradeId": "1", "px": "1", "sz": "2", "side": "buy", "ts": "3"}]),
+        );
+        let trades: Vec<Trade> = msg.decode().expect("应解码成功");
+        assert_eq!(trades[0].sz, "2");
+
+        assert!(msg.decode::<MarkPrice>().is_err());
+        assert!(WsMessage::Pong.decode::<Trade>().is_err());
+    }
+}
@@ -10,7 +10,12 @@
 //! - Private channels (account, positions, orders)
 //! - Automatic reconnection with exponential backoff
 //! - Subscription state recovery after reconnection
-//! - Heartbeat (ping/pong) handling
+//! - Automatic heartbeat (ping/pong) with dead-connection detection
+//! - Local order books with checksum and sequence verification
+//! - Typed data payloads (`WsMessage::typed_data` / `WsMessage::decode`)
+//! - Order placement, amend and cancel over the private socket (`WsTradeApi`)
+//! - Multi-connection routing and sharding with one merged stream (`WsManager`)
+//! - In-process mock OKX server for offline tests (`testing` feature)
 //!
 //! ## Example (Basic Client)
 //!
@@ -78,13 +83,13 @@
 //!             Ok(WsMessage::Data { channel, data, .. }) => {
 //!                 println!("Channel: {}, Data: {:?}", channel, data);
 //!             }
-//!             Err(e) => {
-//!                 eprintln!("Error: {}", e);
-//!                 // Trigger manual reconnection if needed
-//!                 if !client.is_connected() {
-//!                     client.reconnect().await?;
-//!                 }
+//!             Ok(WsMessage::Reconnecting { attempt }) => {
+//!                 println!("Reconnecting (attempt {})", attempt);
 //!             }
+//!             Ok(WsMessage::SubscriptionsRestored { count }) => {
+//!                 println!("Restored {} subscriptions", count);
+//!             }
+//!             Err(e) => eprintln!("Error: {}", e),
 //!             _ => {}
 //!         }
 //!     }
@@ -95,16 +100,28 @@
 
 mod channel;
 mod client;
+mod data;
+mod manager;
 mod message;
+mod order_book;
 mod reconnect;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod trade;
 
 pub use channel::Channel;
 pub use client::WsClient;
-pub use message::{WsEvent, WsMessage};
+pub use data::WsData;
+pub use manager::{ConnectionId, WsManager, DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION};
+pub use message::{WsEvent, WsMessage, SERVICE_UPGRADE_NOTICE_CODE};
+pub use order_book::{
+    BookError, BookUpdate, LocalOrderBook, OrderBookManager, Resubscribe, CHECKSUM_DEPTH,
+};
 pub use reconnect::{
     channel_from_key, channel_key_from, ConnectionState, ConnectionType, ReconnectConfig,
     ReconnectingWsClient,
 };
+pub use trade::WsTradeApi;
 
 // Re-export core types for standalone usage
 // When using the unified `okx` crate, these are already available
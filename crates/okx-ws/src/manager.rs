@@ -0,0 +1,587 @@
+//! Multi-connection WebSocket manager.
+//!
+//! [`WsManager`] owns one or more [`ReconnectingWsClient`]s per endpoint,
+//! routes every [`Channel`] to the endpoint serving it, opens a new shard
+//! when a connection reaches its subscription limit and merges all
+//! connections into a single stream tagged with a [`ConnectionId`].
+//!
+//! Source: OKX API v5 WebSocket API
+//! - <https://www.okx.com/docs-v5/en/#overview-websocket-connect>
+
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_util::Stream;
+use tracing::info;
+
+use okx_core::{Config, Result, TimestampProvider};
+
+use crate::channel::Channel;
+use crate::message::WsMessage;
+use crate::reconnect::{channel_key_from, ConnectionType, ReconnectConfig, ReconnectingWsClient};
+
+/// Default number of subscriptions per connection before a new shard is opened.
+pub const DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 200;
+
+/// Identifies a connection owned by a [`WsManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConnectionId {
+    /// Endpoint of the connection
+    pub conn_type: ConnectionType,
+    /// Shard index within the endpoint, starting at 0
+    pub shard: usize,
+}
+
+/// Connection owned by the manager.
+struct Shard {
+    id: ConnectionId,
+    client: ReconnectingWsClient,
+    /// Channel keys subscribed on this connection
+    keys: HashSet<String>,
+    /// Whether the stream of this connection has ended
+    ended: bool,
+}
+
+/// WebSocket manager routing channels over several connections.
+///
+/// Connections are opened lazily on the first subscription to an endpoint.
+/// Each channel is identified by [`channel_key_from`], so subscribing the
+/// same channel twice is a no-op.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use okx_ws::{Channel, WsManager, WsMessage};
+/// use okx_core::types::Bar;
+/// use okx_core::{Config, Credentials};
+/// use futures_util::StreamExt;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let config = Config::new(Credentials::new("api_key", "secret_key", "passphrase"));
+///     let mut manager = WsManager::new(config).with_max_subscriptions(100);
+///
+///     manager.subscribe(vec![
+///         Channel::Tickers { inst_id: "BTC-USDT".to_string() },
+///         Channel::Candle { bar: Bar::M1, inst_id: "BTC-USDT".to_string() },
+///     ]).await?;
+///
+///     while let Some((connection, msg)) = manager.next().await {
+///         if let Ok(WsMessage::Data { channel, .. }) = msg {
+///             println!("{:?} #{}: {}", connection.conn_type, connection.shard, channel);
+///         }
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+pub struct WsManager {
+    /// Client configuration
+    config: Config,
+    /// Reconnection configuration of every connection
+    reconnect_config: ReconnectConfig,
+    /// Subscription limit per connection
+    max_subscriptions: usize,
+    /// Optional timestamp provider for login
+    timestamp_provider: Option<Arc<dyn TimestampProvider>>,
+    /// Open connections
+    shards: Vec<Shard>,
+    /// Channel key -> index in `shards`
+    routes: HashMap<String, usize>,
+    /// Shard polled first on the next `poll_next`, for fairness
+    next_poll: usize,
+}
+
+impl WsManager {
+    /// Create a manager; no connection is opened until the first subscription.
+    #[must_use]
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            reconnect_config: ReconnectConfig::default(),
+            max_subscriptions: DEFAULT_MAX_SUBSCRIPTIONS_PER_CONNECTION,
+            timestamp_provider: None,
+            shards: Vec::new(),
+            routes: HashMap::new(),
+            next_poll: 0,
+        }
+    }
+
+    /// Set the reconnection configuration used by every connection.
+    #[must_use]
+    pub fn with_reconnect_config(mut self, reconnect_config: ReconnectConfig) -> Self {
+        self.reconnect_config = reconnect_config;
+        self
+    }
+
+    /// Set the subscription limit per connection (at least 1).
+    #[must_use]
+    pub fn with_max_subscriptions(mut self, max: usize) -> Self {
+        self.max_subscriptions = max.max(1);
+        self
+    }
+
+    /// Set the timestamp provider used to log in private and business connections.
+    #[must_use]
+    pub fn with_timestamp_provider(mut self, provider: Arc<dyn TimestampProvider>) -> Self {
+        self.timestamp_provider = Some(provider);
+        self
+    }
+
+    /// Subscribe to channels, routing each one to its endpoint.
+    ///
+    /// Channels already subscribed are skipped. A new connection is opened
+    /// when every connection of the endpoint is at the subscription limit.
+    /// Only channels acknowledged by the server are routed. Channels whose
+    /// subscription fails or is rejected are not tracked, so they can be
+    /// subscribed again; the first rejection is returned once the other
+    /// channels are routed.
+    pub async fn subscribe(&mut self, channels: Vec<Channel>) -> Result<()> {
+        let mut batches: HashMap<usize, Vec<Channel>> = HashMap::new();
+        let mut seen = HashSet::new();
+        let mut rejected = None;
+
+        for channel in channels {
+            let key = channel_key_from(&channel);
+            if self.routes.contains_key(&key) || !seen.insert(key) {
+                continue;
+            }
+
+            let conn_type = channel.endpoint();
+            let index = match self.shard_with_capacity(conn_type, &batches) {
+                Some(index) => index,
+                None => self.open_shard(conn_type).await?,
+            };
+            batches.entry(index).or_default().push(channel);
+        }
+
+        for (index, channels) in batches {
+            let shard = &mut self.shards[index];
+            // 先经时间戳提供者登录，避免订阅时以本地时钟登录
+            let subscribed = match shard.client.login_for(&channels).await {
+                Ok(()) => shard.client.subscribe_confirmed(channels.clone()).await,
+                Err(e) => Err(e),
+            };
+            let results = match subscribed {
+                Ok(results) => results,
+                Err(e) => {
+                    // 失败的频道既不路由也不在重连时恢复，调用方可直接重试
+                    shard.client.forget(&channels);
+                    return Err(e);
+                }
+            };
+            for (channel, result) in results {
+                match result {
+                    Ok(()) => {
+                        let key = channel_key_from(&channel);
+                        shard.keys.insert(key.clone());
+                        self.routes.insert(key, index);
+                    }
+                    Err(e) => {
+                        // 服务端拒绝的频道同样不路由
+                        shard.client.forget(std::slice::from_ref(&channel));
+                        rejected.get_or_insert(e);
+                    }
+                }
+            }
+        }
+
+        rejected.map_or(Ok(()), Err)
+    }
+
+    /// Unsubscribe from channels on whichever connection holds them.
+    ///
+    /// Channels that are not subscribed are ignored. Connections stay open
+    /// and are reused by later subscriptions.
+    pub async fn unsubscribe(&mut self, channels: Vec<Channel>) -> Result<()> {
+        let mut batches: HashMap<usize, Vec<Channel>> = HashMap::new();
+        for channel in channels {
+            let key = channel_key_from(&channel);
+            if let Some(index) = self.routes.remove(&key) {
+                self.shards[index].keys.remove(&key);
+                batches.entry(index).or_default().push(channel);
+            }
+        }
+
+        for (index, channels) in batches {
+            self.shards[index].client.unsubscribe(channels).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the connection a channel is subscribed on.
+    #[must_use]
+    pub fn route(&self, channel: &Channel) -> Option<ConnectionId> {
+        self.routes
+            .get(&channel_key_from(channel))
+            .map(|&index| self.shards[index].id)
+    }
+
+    /// Get the IDs of all open connections.
+    #[must_use]
+    pub fn connections(&self) -> Vec<ConnectionId> {
+        self.shards.iter().map(|shard| shard.id).collect()
+    }
+
+    /// Get the number of open connections.
+    #[must_use]
+    pub fn connection_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Get the number of subscribed channels across all connections.
+    #[must_use]
+    pub fn subscription_count(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Get a connection, e.g. to log in or place orders on it.
+    pub fn client_mut(&mut self, id: ConnectionId) -> Option<&mut ReconnectingWsClient> {
+        self.shards
+            .iter_mut()
+            .find(|shard| shard.id == id)
+            .map(|shard| &mut shard.client)
+    }
+
+    /// Close every connection.
+    pub async fn close(&mut self) -> Result<()> {
+        for shard in &mut self.shards {
+            shard.client.close().await?;
+        }
+        Ok(())
+    }
+
+    /// Find a connection of `conn_type` with room for one more channel,
+    /// counting the channels already queued for it in `batches`.
+    fn shard_with_capacity(
+        &self,
+        conn_type: ConnectionType,
+        batches: &HashMap<usize, Vec<Channel>>,
+    ) -> Option<usize> {
+        self.shards.iter().enumerate().find_map(|(index, shard)| {
+            let queued = batches.get(&index).map_or(0, Vec::len);
+            let has_room = shard.id.conn_type == conn_type
+                && !shard.ended
+                && shard.keys.len() + queued < self.max_subscriptions;
+            has_room.then_some(index)
+        })
+    }
+
+    /// Open a new connection for `conn_type` and return its index.
+    async fn open_shard(&mut self, conn_type: ConnectionType) -> Result<usize> {
+        let shard = self
+            .shards
+            .iter()
+            .filter(|shard| shard.id.conn_type == conn_type)
+            .count();
+        let id = ConnectionId { conn_type, shard };
+        info!("Opening {:?} connection #{}", conn_type, shard);
+
+        let config = self.config.clone();
+        let reconnect_config = self.reconnect_config.clone();
+        let client = match &self.timestamp_provider {
+            Some(provider) => {
+                ReconnectingWsClient::connect_with_timestamp_provider(
+                    config,
+                    conn_type,
+                    reconnect_config,
+                    Arc::clone(provider),
+                )
+                .await?
+            }
+            None => ReconnectingWsClient::connect(config, conn_type, reconnect_config).await?,
+        };
+
+        self.shards.push(Shard {
+            id,
+            client,
+            keys: HashSet::new(),
+            ended: false,
+        });
+        Ok(self.shards.len() - 1)
+    }
+}
+
+impl Stream for WsManager {
+    type Item = (ConnectionId, Result<WsMessage>);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let count = this.shards.len();
+
+        for offset in 0..count {
+            let index = (this.next_poll + offset) % count;
+            let shard = &mut this.shards[index];
+            if shard.ended {
+                continue;
+            }
+
+            match Pin::new(&mut shard.client).poll_next(cx) {
+                Poll::Ready(Some(msg)) => {
+                    this.next_poll = (index + 1) % count;
+                    return Poll::Ready(Some((shard.id, msg)));
+                }
+                Poll::Ready(None) => {
+                    // 已结束连接上的频道不再路由，之后可重新订阅到新连接
+                    shard.ended = true;
+                    shard.keys.clear();
+                    this.routes.retain(|_, route| *route != index);
+                }
+                Poll::Pending => {}
+            }
+        }
+
+        // 所有连接都已结束（或尚未建立任何连接）时结束流
+        if this.shards.iter().all(|shard| shard.ended) {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl std::fmt::Debug for WsManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WsManager")
+            .field("max_subscriptions", &self.max_subscriptions)
+            .field("connections", &self.connections())
+            .field("subscriptions", &self.routes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use futures_util::{SinkExt, StreamExt};
+    use okx_core::types::Bar;
+    use okx_core::{Credentials, OffsetTimeProvider, OkxError};
+    use serde_json::{json, Value};
+    use tokio::net::TcpListener;
+    use tokio::time::timeout;
+    use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+    use crate::testing::MockWsServer;
+
+    fn tickers(inst_id: &str) -> Channel {
+        Channel::Tickers {
+            inst_id: inst_id.to_string(),
+        }
+    }
+
+    /// 本地回显服务端：对每个订阅参数回复确认并推送一条空数据。
+    async fn spawn_echo_server() -> Option<String> {
+        let listener = match TcpListener::bind("127.0.0.1:0").await {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("无法绑定本地端口，跳过用例: {e}");
+                return None;
+            }
+        };
+        let addr = listener.local_addr().expect("应获取本地地址");
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = accept_async(stream).await.expect("握手应成功");
+                    while let Some(Ok(Message::Text(text))) = ws.next().await {
+                        let request: Value =
+                            serde_json::from_str(text.as_str()).expect("请求应为 JSON");
+                        if request["op"] != "subscribe" {
+                            continue;
+                        }
+                        for arg in request["args"].as_array().into_iter().flatten() {
+                            let ack = json!({"event": "subscribe", "arg": arg}).to_string();
+                            let _ = ws.send(Message::Text(ack.into())).await;
+                            let push = json!({"arg": arg, "data": []}).to_string();
+                            let _ = ws.send(Message::Text(push.into())).await;
+                        }
+                    }
+                });
+            }
+        });
+
+        Some(format!("ws://{addr}/ws"))
+    }
+
+    #[tokio::test]
+    async fn empty_manager_stream_ends() {
+        let mut manager = WsManager::new(Config::new(Credentials::new("k", "s", "p")));
+        assert!(manager.next().await.is_none());
+        assert_eq!(manager.connection_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn routes_shards_dedupes_and_tags_messages() {
+        let Some(url) = spawn_echo_server().await else {
+            return;
+        };
+        let config = Config::new(Credentials::new("k", "s", "p"))
+            .with_ws_public_url(url.clone())
+            .with_ws_business_url(url);
+        let mut manager = WsManager::new(config).with_max_subscriptions(2);
+
+        let candle = Channel::Candle {
+            bar: Bar::M1,
+            inst_id: "BTC-USDT".to_string(),
+        };
+        manager
+            .subscribe(vec![
+                tickers("BTC-USDT"),
+                tickers("ETH-USDT"),
+                tickers("BTC-USDT"),
+                tickers("SOL-USDT"),
+                candle.clone(),
+            ])
+            .await
+            .expect("订阅应成功");
+
+        assert_eq!(manager.subscription_count(), 4);
+        assert_eq!(manager.connection_count(), 3);
+        let public = |shard| ConnectionId {
+            conn_type: ConnectionType::Public,
+            shard,
+        };
+        assert_eq!(manager.route(&tickers("BTC-USDT")), Some(public(0)));
+        assert_eq!(manager.route(&tickers("SOL-USDT")), Some(public(1)));
+        assert_eq!(
+            manager.route(&candle),
+            Some(ConnectionId {
+                conn_type: ConnectionType::Business,
+                shard: 0,
+            })
+        );
+
+        // 每条推送都应带上所属连接的标记
+        for _ in 0..4 {
+            let (connection, msg) = timeout(Duration::from_secs(5), manager.next())
+                .await
+                .expect("等待消息超时")
+                .expect("流不应结束");
+            let Ok(WsMessage::Data { arg, .. }) = msg else {
+                panic!("预期 Data 推送，实际为: {msg:?}");
+            };
+            let channel: Channel = serde_json::from_value(arg).expect("arg 应可解析为频道");
+            assert_eq!(manager.route(&channel), Some(connection));
+        }
+
+        // 重复订阅不应新建连接；退订后空出的位置可复用
+        manager
+            .subscribe(vec![tickers("ETH-USDT")])
+            .await
+            .expect("重复订阅应成功");
+        assert_eq!(manager.subscription_count(), 4);
+        manager
+            .unsubscribe(vec![tickers("BTC-USDT")])
+            .await
+            .expect("退订应成功");
+        assert_eq!(manager.route(&tickers("BTC-USDT")), None);
+        manager
+            .subscribe(vec![tickers("XRP-USDT")])
+            .await
+            .expect("订阅应成功");
+        assert_eq!(manager.route(&tickers("XRP-USDT")), Some(public(0)));
+        assert_eq!(manager.connection_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn failed_subscriptions_are_not_routed() {
+        let Ok(server) = MockWsServer::start(Credentials::new("key", "secret", "pass")).await
+        else {
+            eprintln!("无法启动本地模拟服务端，跳过用例");
+            return;
+        };
+        // 错误的 secret 使登录失败，私有频道订阅随之失败
+        let config = Config::new(Credentials::new("key", "wrong", "pass"))
+            .with_ws_private_url(server.url(ConnectionType::Private));
+        let mut manager = WsManager::new(config);
+        let account = Channel::Account { ccy: None };
+
+        assert!(manager.subscribe(vec![account.clone()]).await.is_err());
+        assert_eq!(manager.route(&account), None);
+        assert_eq!(manager.subscription_count(), 0);
+        // 重试不应被当作重复订阅而静默跳过
+        assert!(manager.subscribe(vec![account]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejected_subscriptions_are_not_routed() {
+        let Ok(server) = MockWsServer::start(Credentials::new("key", "secret", "pass")).await
+        else {
+            eprintln!("无法启动本地模拟服务端，跳过用例");
+            return;
+        };
+        server.reject(
+            json!({"channel": "tickers", "instId": "BTC-USDT"}),
+            "60018",
+            "Invalid channel",
+        );
+        let mut manager = WsManager::new(server.config());
+
+        let result = manager
+            .subscribe(vec![tickers("BTC-USDT"), tickers("ETH-USDT")])
+            .await;
+        assert!(result.is_err(), "被拒绝的订阅应返回错误");
+        assert_eq!(manager.route(&tickers("BTC-USDT")), None);
+        assert!(manager.route(&tickers("ETH-USDT")).is_some());
+        assert_eq!(manager.subscription_count(), 1);
+        // 被拒绝的频道不应在重连时恢复
+        let id = manager.connections()[0];
+        let client = manager.client_mut(id).expect("连接应存在");
+        assert_eq!(client.subscription_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn first_login_uses_timestamp_provider() {
+        let Ok(server) = MockWsServer::start(Credentials::new("key", "secret", "pass")).await
+        else {
+            eprintln!("无法启动本地模拟服务端，跳过用例");
+            return;
+        };
+        server.set_login_window(Duration::from_secs(5));
+        // 时间戳提供者偏离本地时钟两分钟：若首次登录走本地时钟则会成功
+        let mut manager = WsManager::new(server.config())
+            .with_timestamp_provider(Arc::new(OffsetTimeProvider::new(-120_000)));
+        let account = Channel::Account { ccy: None };
+
+        let result = manager.subscribe(vec![account.clone()]).await;
+        assert!(
+            matches!(&result, Err(OkxError::Auth(msg)) if msg.contains("60006")),
+            "首次登录应使用时间戳提供者，实际为: {result:?}"
+        );
+        assert_eq!(manager.route(&account), None);
+    }
+
+    #[tokio::test]
+    async fn ended_connections_drop_their_routes() {
+        let Ok(server) = MockWsServer::start(Credentials::new("key", "secret", "pass")).await
+        else {
+            eprintln!("无法启动本地模拟服务端，跳过用例");
+            return;
+        };
+        let reconnect = ReconnectConfig::default()
+            .with_initial_delay(Duration::from_millis(10))
+            .with_max_attempts(1);
+        let mut manager = WsManager::new(server.config()).with_reconnect_config(reconnect);
+        manager
+            .subscribe(vec![tickers("BTC-USDT")])
+            .await
+            .expect("订阅应成功");
+        assert_eq!(manager.subscription_count(), 1);
+
+        // 关闭服务端后重连失败，连接的流随之结束
+        drop(server);
+        timeout(Duration::from_secs(10), async {
+            while manager.next().await.is_some() {}
+        })
+        .await
+        .expect("流应在重连失败后结束");
+        assert_eq!(manager.route(&tickers("BTC-USDT")), None);
+        assert_eq!(manager.subscription_count(), 0);
+    }
+}
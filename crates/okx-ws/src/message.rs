@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use okx_core::OkxErrorCode;
+
 /// WebSocket message received from OKX.
 #[derive(Debug, Clone)]
 pub enum WsMessage {
@@ -17,6 +19,8 @@ pub enum WsMessage {
         arg: Value,
         /// Data payload
         data: Vec<Value>,
+        /// Push action of incremental channels (`snapshot` / `update`)
+        action: Option<String>,
     },
     /// Event message (subscribe/unsubscribe/login response)
     Event {
@@ -31,6 +35,19 @@ pub enum WsMessage {
         /// Connection ID (for login)
         conn_id: Option<String>,
     },
+    /// Response to a trading op (`order`, `cancel-order`, ...)
+    Response {
+        /// Request ID echoed by the server
+        id: String,
+        /// Operation
+        op: String,
+        /// Response code ("0" = success)
+        code: String,
+        /// Response message
+        msg: String,
+        /// Per-order results
+        data: Vec<Value>,
+    },
     /// Pong response
     Pong,
     /// Channel connection count information
@@ -51,6 +68,18 @@ pub enum WsMessage {
         /// Error message
         msg: String,
     },
+    /// Connection lost, reconnection attempt in progress (emitted by `ReconnectingWsClient`)
+    Reconnecting {
+        /// Reconnection attempt number, starting at 1
+        attempt: u32,
+    },
+    /// Connection re-established, private connections logged in again
+    Reconnected,
+    /// Tracked subscriptions re-subscribed after reconnecting
+    SubscriptionsRestored {
+        /// Number of restored channels
+        count: usize,
+    },
     /// Unknown message
     Unknown(String),
 }
@@ -66,8 +95,13 @@ pub enum WsEvent {
     Login,
     /// Error occurred
     Error,
+    /// Service notice, e.g. code `64008` before a planned disconnection
+    Notice,
 }
 
+/// Code of the notice OKX sends before disconnecting for a service upgrade.
+pub const SERVICE_UPGRADE_NOTICE_CODE: &str = "64008";
+
 impl std::fmt::Display for WsEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -75,6 +109,7 @@ impl std::fmt::Display for WsEvent {
             Self::Unsubscribe => "unsubscribe",
             Self::Login => "login",
             Self::Error => "error",
+            Self::Notice => "notice",
         };
         f.write_str(s)
     }
@@ -145,6 +180,7 @@ impl WsMessage {
                 "unsubscribe" => WsEvent::Unsubscribe,
                 "login" => WsEvent::Login,
                 "error" => WsEvent::Error,
+                "notice" => WsEvent::Notice,
                 _ => return Self::Unknown(text.to_string()),
             };
 
@@ -160,6 +196,31 @@ impl WsMessage {
             };
         }
 
+        // Check if it's a trading op response
+        if let (Some(id), Some(op)) = (
+            value.get("id").and_then(|i| i.as_str()),
+            value.get("op").and_then(|o| o.as_str()),
+        ) {
+            let field = |name: &str| {
+                value
+                    .get(name)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            return Self::Response {
+                id: id.to_string(),
+                op: op.to_string(),
+                code: field("code"),
+                msg: field("msg"),
+                data: value
+                    .get("data")
+                    .and_then(|d| d.as_array())
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+        }
+
         // Check if it's a data message
         if let (Some(arg), Some(data)) = (value.get("arg"), value.get("data")) {
             let channel = arg
@@ -177,6 +238,10 @@ impl WsMessage {
                 channel,
                 arg: arg.clone(),
                 data: data_vec,
+                action: value
+                    .get("action")
+                    .and_then(|a| a.as_str())
+                    .map(String::from),
             };
         }
 
@@ -195,9 +260,23 @@ impl WsMessage {
         )
     }
 
+    /// Check if this is the service-upgrade notice (code `64008`) announcing
+    /// that the server will close the connection soon.
+    #[must_use]
+    pub fn is_service_upgrade_notice(&self) -> bool {
+        matches!(
+            self,
+            Self::Event {
+                event: WsEvent::Notice,
+                code: Some(code),
+                ..
+            } if code == SERVICE_UPGRADE_NOTICE_CODE
+        )
+    }
+
     /// Get error details if this is an error event.
     #[must_use]
-    pub fn error_details(&self) -> Option<(&str, &str)> {
+    pub fn error_details(&self) -> Option<(OkxErrorCode, &str)> {
         match self {
             Self::Event {
                 event: WsEvent::Error,
@@ -205,7 +284,7 @@ impl WsMessage {
                 msg,
                 ..
             } => {
-                let code = code.as_deref().unwrap_or("unknown");
+                let code = OkxErrorCode::from_code(code.as_deref().unwrap_or("unknown"));
                 let msg = msg.as_deref().unwrap_or("unknown error");
                 Some((code, msg))
             }
@@ -217,6 +296,9 @@ impl WsMessage {
 /// WebSocket request message.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WsRequest {
+    /// Request ID, echoed in the response of trading ops
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     /// Operation type
     pub op: String,
     /// Arguments
@@ -227,6 +309,7 @@ impl WsRequest {
     /// Create a subscribe request.
     pub fn subscribe(channels: Vec<Value>) -> Self {
         Self {
+            id: None,
             op: "subscribe".to_string(),
             args: channels,
         }
@@ -235,11 +318,21 @@ impl WsRequest {
     /// Create an unsubscribe request.
     pub fn unsubscribe(channels: Vec<Value>) -> Self {
         Self {
+            id: None,
             op: "unsubscribe".to_string(),
             args: channels,
         }
     }
 
+    /// Create a trading op request correlated by `id`.
+    pub fn op(id: impl Into<String>, op: impl Into<String>, args: Vec<Value>) -> Self {
+        Self {
+            id: Some(id.into()),
+            op: op.into(),
+            args,
+        }
+    }
+
     /// Create a login request.
     pub fn login(api_key: &str, passphrase: &str, timestamp: &str, sign: &str) -> Self {
         let arg = serde_json::json!({
@@ -249,6 +342,7 @@ impl WsRequest {
             "sign": sign
         });
         Self {
+            id: None,
             op: "login".to_string(),
             args: vec![arg],
         }
@@ -292,10 +386,17 @@ mod tests {
         })
         .to_string();
 
-        if let WsMessage::Data { channel, arg, data } = WsMessage::parse(&data_json) {
+        if let WsMessage::Data {
+            channel,
+            arg,
+            data,
+            action,
+        } = WsMessage::parse(&data_json)
+        {
             assert_eq!(channel, "tickers");
             assert_eq!(arg["instId"], "BTC-USDT");
             assert_eq!(data.len(), 2);
+            assert!(action.is_none());
         } else {
             panic!("应解析为 Data");
         }
@@ -306,7 +407,10 @@ mod tests {
         })
         .to_string();
 
-        if let WsMessage::Data { channel, arg, data } = WsMessage::parse(&single_json) {
+        if let WsMessage::Data {
+            channel, arg, data, ..
+        } = WsMessage::parse(&single_json)
+        {
             assert_eq!(channel, "tickers");
             assert_eq!(arg["instId"], "ETH-USDT");
             assert_eq!(data.len(), 1);
@@ -316,6 +420,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn op_request_and_response_carry_id() {
+        let request = WsRequest::op("42", "order", vec![json!({"instId": "BTC-USDT"})]);
+        let text = serde_json::to_string(&request).unwrap();
+        assert!(text.contains(r#""id":"42""#));
+        assert!(!serde_json::to_string(&WsRequest::subscribe(vec![]))
+            .unwrap()
+            .contains("id"));
+
+        let json = r#"{"id":"42","op":"order","code":"1","msg":"","data":[{"ordId":"","sCode":"51008","sMsg":"insufficient"}],"inTime":"1","outTime":"2"}"#;
+        match WsMessage::parse(json) {
+            WsMessage::Response {
+                id, op, code, data, ..
+            } => {
+                assert_eq!(id, "42");
+                assert_eq!(op, "order");
+                assert_eq!(code, "1");
+                assert_eq!(data[0]["sCode"], "51008");
+            }
+            other => panic!("应解析为 Response，实际为: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_keeps_book_action() {
+        let json = r#"{"arg":{"channel":"books","instId":"BTC-USDT"},"action":"update","data":[{"asks":[],"bids":[]}]}"#;
+        match WsMessage::parse(json) {
+            WsMessage::Data { action, .. } => assert_eq!(action.as_deref(), Some("update")),
+            other => panic!("应解析为 Data，实际为: {other:?}"),
+        }
+    }
+
     #[test]
     fn parse_handles_event_and_error_details() {
         let ok_event = r#"{"event":"subscribe","arg":{"channel":"orders"}}"#;
@@ -343,6 +479,24 @@ mod tests {
         assert_eq!(details.1, "invalid");
     }
 
+    #[test]
+    fn parse_service_upgrade_notice() {
+        let notice = r#"{"event":"notice","code":"64008","msg":"The connection will soon be closed for a service upgrade. Please reconnect.","connId":"a4d3ae55"}"#;
+        let parsed = WsMessage::parse(notice);
+        assert!(parsed.is_service_upgrade_notice());
+        let WsMessage::Event { event, conn_id, .. } = parsed else {
+            panic!("应解析为 Event 通知");
+        };
+        assert_eq!(event, WsEvent::Notice);
+        assert_eq!(conn_id.as_deref(), Some("a4d3ae55"));
+
+        let other = WsMessage::parse(r#"{"event":"notice","code":"64009","msg":""}"#);
+        assert!(!other.is_service_upgrade_notice());
+        assert!(
+            !WsMessage::parse(r#"{"event":"error","code":"64008"}"#).is_service_upgrade_notice()
+        );
+    }
+
     #[test]
     fn parse_pong_and_unknown_paths() {
         assert!(matches!(WsMessage::parse("pong"), WsMessage::Pong));
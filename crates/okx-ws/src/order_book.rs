@@ -0,0 +1,658 @@
+//! Local order book maintained from incremental depth channels.
+//!
+//! `books`, `books-l2-tbt` and `books50-l2-tbt` push a `snapshot` followed by
+//! `update` deltas. Every push carries a CRC32 `checksum` over the top 25
+//! levels and a `seqId` / `prevSeqId` pair; a mismatch in either means the
+//! local book diverged and the channel has to be resubscribed.
+//!
+//! Source: OKX API v5 WebSocket Order Book Channel
+//! - <https://www.okx.com/docs-v5/en/#order-book-trading-market-data-ws-order-book-channel>
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+
+use serde_json::Value;
+use tracing::warn;
+
+use okx_core::types::BookLevel;
+use okx_core::Result;
+
+use crate::channel::Channel;
+use crate::client::WsClient;
+use crate::message::WsMessage;
+use crate::reconnect::ReconnectingWsClient;
+
+/// Number of levels per side covered by the checksum.
+pub const CHECKSUM_DEPTH: usize = 25;
+
+/// Reason why a local order book became invalid.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BookError {
+    /// Checksum of the local book differs from the pushed checksum
+    #[error("checksum mismatch: expected {expected}, local {actual}")]
+    ChecksumMismatch {
+        /// Checksum sent by OKX
+        expected: i32,
+        /// Checksum computed from the local book
+        actual: i32,
+    },
+    /// `prevSeqId` of an update does not match the last applied `seqId`
+    #[error("sequence gap: expected prevSeqId {expected}, got {actual}")]
+    SequenceGap {
+        /// Last applied `seqId`
+        expected: i64,
+        /// `prevSeqId` of the update
+        actual: i64,
+    },
+    /// Update received before any snapshot
+    #[error("update received before snapshot")]
+    MissingSnapshot,
+    /// Push could not be parsed
+    #[error("malformed book push: {0}")]
+    Malformed(String),
+}
+
+/// Price key ordered numerically; the original string is kept in [`BookLevel`].
+#[derive(Debug, Clone, Copy)]
+struct PriceKey(f64);
+
+impl PartialEq for PriceKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PriceKey {}
+
+impl PartialOrd for PriceKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PriceKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// Local order book of one instrument.
+#[derive(Debug, Clone, Default)]
+pub struct LocalOrderBook {
+    /// Bids, best (highest) first
+    bids: BTreeMap<Reverse<PriceKey>, BookLevel>,
+    /// Asks, best (lowest) first
+    asks: BTreeMap<PriceKey, BookLevel>,
+    /// Last applied `seqId`
+    seq_id: Option<i64>,
+    /// Timestamp of the last applied push (Unix milliseconds)
+    ts: String,
+}
+
+impl LocalOrderBook {
+    /// Create an empty book.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one element of a `books*` push.
+    ///
+    /// A `snapshot` replaces the book; any other action applies deltas, where a
+    /// size of `0` removes the level. The sequence and checksum are verified
+    /// after the change is applied.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`BookError`] if the push is malformed, arrives before a
+    /// snapshot, skips a sequence number or fails the checksum. The book must
+    /// then be rebuilt from a fresh snapshot.
+    pub fn apply(&mut self, action: &str, data: &Value) -> std::result::Result<(), BookError> {
+        let snapshot = action == "snapshot";
+        let seq_id = data.get("seqId").and_then(Value::as_i64);
+        let prev_seq_id = data.get("prevSeqId").and_then(Value::as_i64);
+
+        if snapshot {
+            self.bids.clear();
+            self.asks.clear();
+        } else {
+            let Some(last) = self.seq_id else {
+                return Err(BookError::MissingSnapshot);
+            };
+            if let Some(prev) = prev_seq_id {
+                if prev != last {
+                    return Err(BookError::SequenceGap {
+                        expected: last,
+                        actual: prev,
+                    });
+                }
+            }
+        }
+
+        for level in parse_levels(data, "bids")? {
+            let key = Reverse(price_key(&level)?);
+            if is_zero(&level.size) {
+                self.bids.remove(&key);
+            } else {
+                self.bids.insert(key, level);
+            }
+        }
+        for level in parse_levels(data, "asks")? {
+            let key = price_key(&level)?;
+            if is_zero(&level.size) {
+                self.asks.remove(&key);
+            } else {
+                self.asks.insert(key, level);
+            }
+        }
+
+        // 快照没有 seqId 时用 0 占位，保证后续增量可继续应用
+        self.seq_id = seq_id.or(if snapshot { Some(0) } else { self.seq_id });
+        if let Some(ts) = data.get("ts").and_then(Value::as_str) {
+            self.ts = ts.to_string();
+        }
+
+        if let Some(expected) = data.get("checksum").and_then(Value::as_i64) {
+            let expected = i32::try_from(expected)
+                .map_err(|_| BookError::Malformed(format!("checksum out of range: {expected}")))?;
+            let actual = self.checksum();
+            if expected != actual {
+                return Err(BookError::ChecksumMismatch { expected, actual });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compute the OKX checksum over the top 25 levels of each side.
+    ///
+    /// Levels are interleaved as `bidPx:bidSz:askPx:askSz:...`, using the
+    /// original price/size strings.
+    #[must_use]
+    pub fn checksum(&self) -> i32 {
+        let mut bids = self.bids.values().take(CHECKSUM_DEPTH);
+        let mut asks = self.asks.values().take(CHECKSUM_DEPTH);
+        let mut parts: Vec<&str> = Vec::with_capacity(CHECKSUM_DEPTH * 4);
+        loop {
+            let bid = bids.next();
+            let ask = asks.next();
+            if bid.is_none() && ask.is_none() {
+                break;
+            }
+            for level in [bid, ask].into_iter().flatten() {
+                parts.push(&level.price);
+                parts.push(&level.size);
+            }
+        }
+        // CRC32 按有符号 32 位整数比较
+        i32::from_ne_bytes(crc32fast::hash(parts.join(":").as_bytes()).to_ne_bytes())
+    }
+
+    /// Get the best (highest) bid.
+    #[must_use]
+    pub fn best_bid(&self) -> Option<&BookLevel> {
+        self.bids.values().next()
+    }
+
+    /// Get the best (lowest) ask.
+    #[must_use]
+    pub fn best_ask(&self) -> Option<&BookLevel> {
+        self.asks.values().next()
+    }
+
+    /// Get up to `depth` bids, best first.
+    #[must_use]
+    pub fn bids(&self, depth: usize) -> Vec<&BookLevel> {
+        self.bids.values().take(depth).collect()
+    }
+
+    /// Get up to `depth` asks, best first.
+    #[must_use]
+    pub fn asks(&self, depth: usize) -> Vec<&BookLevel> {
+        self.asks.values().take(depth).collect()
+    }
+
+    /// Get the number of (bid, ask) levels.
+    #[must_use]
+    pub fn depth(&self) -> (usize, usize) {
+        (self.bids.len(), self.asks.len())
+    }
+
+    /// Get the last applied `seqId`.
+    #[must_use]
+    pub fn seq_id(&self) -> Option<i64> {
+        self.seq_id
+    }
+
+    /// Get the timestamp of the last applied push (Unix milliseconds).
+    #[must_use]
+    pub fn ts(&self) -> &str {
+        &self.ts
+    }
+}
+
+/// Result of feeding a message to [`OrderBookManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookUpdate {
+    /// Message does not belong to an incremental depth channel, or is an
+    /// update dropped while the book waits for a fresh snapshot
+    Ignored,
+    /// Book was updated
+    Updated {
+        /// Depth channel of the book
+        channel: Channel,
+    },
+    /// Book was dropped and the channel needs to be resubscribed
+    Resync {
+        /// Depth channel of the book
+        channel: Channel,
+        /// Why the book became invalid
+        reason: BookError,
+    },
+}
+
+/// Client that can resubscribe a channel to obtain a fresh snapshot.
+pub trait Resubscribe {
+    /// Unsubscribe and subscribe the channel again.
+    fn resubscribe(&mut self, channel: Channel) -> impl Future<Output = Result<()>> + Send;
+}
+
+impl Resubscribe for WsClient {
+    async fn resubscribe(&mut self, channel: Channel) -> Result<()> {
+        self.unsubscribe(vec![channel.clone()]).await?;
+        self.subscribe(vec![channel]).await
+    }
+}
+
+impl Resubscribe for ReconnectingWsClient {
+    async fn resubscribe(&mut self, channel: Channel) -> Result<()> {
+        self.unsubscribe(vec![channel.clone()]).await?;
+        self.subscribe(vec![channel]).await
+    }
+}
+
+/// Maintains local order books for `books`, `books-l2-tbt` and
+/// `books50-l2-tbt` subscriptions.
+///
+/// ## Example
+///
+/// ```rust,no_run
+/// use futures_util::StreamExt;
+/// use okx_core::{Config, Credentials};
+/// use okx_ws::{Channel, OrderBookManager, WsClient};
+///
+/// # async fn run() -> okx_core::Result<()> {
+/// let config = Config::new(Credentials::new("key", "secret", "pass"));
+/// let mut client = WsClient::connect_public(&config).await?;
+/// let channel = Channel::Books { inst_id: "BTC-USDT".to_string() };
+/// client.subscribe(vec![channel.clone()]).await?;
+///
+/// let mut books = OrderBookManager::new();
+/// while let Some(msg) = client.next().await {
+///     books.handle(&mut client, &msg?).await?;
+///     if let Some(book) = books.book(&channel) {
+///         println!("bid {:?} ask {:?}", book.best_bid(), book.best_ask());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct OrderBookManager {
+    /// Books keyed by `channel:instId`
+    books: HashMap<String, LocalOrderBook>,
+    /// Keys of books dropped by a resync and waiting for a new snapshot
+    awaiting_snapshot: HashSet<String>,
+}
+
+impl OrderBookManager {
+    /// Create an empty manager.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the book of a depth channel.
+    #[must_use]
+    pub fn book(&self, channel: &Channel) -> Option<&LocalOrderBook> {
+        let inst_id = book_inst_id(channel)?;
+        self.books.get(&book_key(channel.name(), inst_id))
+    }
+
+    /// Drop the book of a depth channel.
+    pub fn remove(&mut self, channel: &Channel) -> Option<LocalOrderBook> {
+        let inst_id = book_inst_id(channel)?;
+        let key = book_key(channel.name(), inst_id);
+        self.awaiting_snapshot.remove(&key);
+        self.books.remove(&key)
+    }
+
+    /// Apply a message without touching the connection.
+    ///
+    /// On [`BookUpdate::Resync`] the book has been dropped; the caller should
+    /// resubscribe the channel (see [`handle`](Self::handle)). Updates still in
+    /// flight for that book are then [`BookUpdate::Ignored`] until the next
+    /// snapshot arrives, so a single failure yields a single resync.
+    pub fn apply(&mut self, msg: &WsMessage) -> BookUpdate {
+        let WsMessage::Data {
+            channel: name,
+            arg,
+            data,
+            action,
+        } = msg
+        else {
+            return BookUpdate::Ignored;
+        };
+        let Some(inst_id) = arg.get("instId").and_then(Value::as_str) else {
+            return BookUpdate::Ignored;
+        };
+        let Some(channel) = depth_channel(name, inst_id) else {
+            return BookUpdate::Ignored;
+        };
+
+        let key = book_key(name, inst_id);
+        let action = action.as_deref().unwrap_or("update");
+        if action == "snapshot" {
+            self.awaiting_snapshot.remove(&key);
+        } else if self.awaiting_snapshot.contains(&key) {
+            return BookUpdate::Ignored;
+        }
+        let book = self.books.entry(key.clone()).or_default();
+        let result = data
+            .iter()
+            .try_for_each(|element| book.apply(action, element));
+
+        match result {
+            Ok(()) => BookUpdate::Updated { channel },
+            Err(reason) => {
+                warn!("Order book {} invalid, resync required: {}", key, reason);
+                self.books.remove(&key);
+                self.awaiting_snapshot.insert(key);
+                BookUpdate::Resync { channel, reason }
+            }
+        }
+    }
+
+    /// Apply a message and resubscribe the channel if the book became invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resubscription could not be sent.
+    pub async fn handle<C: Resubscribe>(
+        &mut self,
+        client: &mut C,
+        msg: &WsMessage,
+    ) -> Result<BookUpdate> {
+        let update = self.apply(msg);
+        if let BookUpdate::Resync { channel, .. } = &update {
+            client.resubscribe(channel.clone()).await?;
+        }
+        Ok(update)
+    }
+}
+
+fn book_key(channel: &str, inst_id: &str) -> String {
+    format!("{channel}:{inst_id}")
+}
+
+fn book_inst_id(channel: &Channel) -> Option<&str> {
+    match channel {
+        Channel::Books { inst_id }
+        | Channel::BooksL2Tbt { inst_id }
+        | Channel::Books50L2Tbt { inst_id } => Some(inst_id),
+        _ => None,
+    }
+}
+
+fn depth_channel(name: &str, inst_id: &str) -> Option<Channel> {
+    let inst_id = inst_id.to_string();
+    match name {
+        "books" => Some(Channel::Books { inst_id }),
+        "books-l2-tbt" => Some(Channel::BooksL2Tbt { inst_id }),
+        "books50-l2-tbt" => Some(Channel::Books50L2Tbt { inst_id }),
+        _ => None,
+    }
+}
+
+fn parse_levels(data: &Value, side: &str) -> std::result::Result<Vec<BookLevel>, BookError> {
+    let Some(levels) = data.get(side) else {
+        return Ok(Vec::new());
+    };
+    let levels = levels
+        .as_array()
+        .ok_or_else(|| BookError::Malformed(format!("{side} is not an array")))?;
+
+    levels
+        .iter()
+        .map(|level| {
+            let field = |i: usize| level.get(i).and_then(Value::as_str).map(String::from);
+            match (field(0), field(1)) {
+                (Some(price), Some(size)) => Ok(BookLevel {
+                    price,
+                    size,
+                    liquidated_orders: field(2).unwrap_or_default(),
+                    order_count: field(3).unwrap_or_default(),
+                }),
+                _ => Err(BookError::Malformed(format!(
+                    "invalid {side} level: {level}"
+                ))),
+            }
+        })
+        .collect()
+}
+
+fn price_key(level: &BookLevel) -> std::result::Result<PriceKey, BookError> {
+    level
+        .price
+        .parse()
+        .map(PriceKey)
+        .map_err(|_| BookError::Malformed(format!("invalid price: {}", level.price)))
+}
+
+fn is_zero(size: &str) -> bool {
+    size.parse::<f64>().is_ok_and(|sz| sz == 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn checksum_of(bids: &[[&str; 2]], asks: &[[&str; 2]]) -> i64 {
+        let mut parts = Vec::new();
+        for i in 0..bids.len().max(asks.len()) {
+            if let Some([px, sz]) = bids.get(i) {
+                parts.push(format!("{px}:{sz}"));
+            }
+            if let Some([px, sz]) = asks.get(i) {
+                parts.push(format!("{px}:{sz}"));
+            }
+        }
+        i64::from(i32::from_ne_bytes(
+            crc32fast::hash(parts.join(":").as_bytes()).to_ne_bytes(),
+        ))
+    }
+
+    fn book_msg(action: &str, data: Value) -> WsMessage {
+        WsMessage::Data {
+            channel: "books".to_string(),
+            arg: json!({"channel": "books", "instId": "BTC-USDT"}),
+            data: vec![data],
+            action: Some(action.to_string()),
+        }
+    }
+
+    fn snapshot() -> WsMessage {
+        book_msg(
+            "snapshot",
+            json!({
+                "bids": [["100.5", "2", "0", "1"], ["100.1", "1", "0", "1"]],
+                "asks": [["101", "3", "0", "2"], ["101.5", "4", "0", "1"]],
+                "ts": "1", "seqId": 10, "prevSeqId": -1,
+                "checksum": checksum_of(&[["100.5", "2"], ["100.1", "1"]], &[["101", "3"], ["101.5", "4"]])
+            }),
+        )
+    }
+
+    #[test]
+    fn snapshot_and_update_maintain_sorted_book() {
+        let mut manager = OrderBookManager::new();
+        let channel = Channel::Books {
+            inst_id: "BTC-USDT".to_string(),
+        };
+        assert_eq!(
+            manager.apply(&snapshot()),
+            BookUpdate::Updated {
+                channel: channel.clone()
+            }
+        );
+
+        let update = book_msg(
+            "update",
+            json!({
+                "bids": [["100.5", "0", "0", "0"], ["100.8", "5", "0", "3"]],
+                "asks": [["101", "1", "0", "1"]],
+                "ts": "2", "seqId": 11, "prevSeqId": 10,
+                "checksum": checksum_of(&[["100.8", "5"], ["100.1", "1"]], &[["101", "1"], ["101.5", "4"]])
+            }),
+        );
+        assert!(matches!(manager.apply(&update), BookUpdate::Updated { .. }));
+
+        let book = manager.book(&channel).expect("应存在订单簿");
+        assert_eq!(book.best_bid().unwrap().price, "100.8");
+        assert_eq!(book.best_ask().unwrap().size, "1");
+        assert_eq!(book.depth(), (2, 2));
+        assert_eq!(book.bids(1).len(), 1);
+        assert_eq!(book.asks(10)[1].price, "101.5");
+        assert_eq!(book.seq_id(), Some(11));
+        assert_eq!(book.ts(), "2");
+    }
+
+    #[test]
+    fn checksum_mismatch_drops_book() {
+        let mut manager = OrderBookManager::new();
+        manager.apply(&snapshot());
+
+        let bad = book_msg(
+            "update",
+            json!({"bids": [["100.9", "1", "0", "1"]], "asks": [], "seqId": 11, "prevSeqId": 10, "checksum": 1}),
+        );
+        match manager.apply(&bad) {
+            BookUpdate::Resync { reason, .. } => {
+                assert!(matches!(
+                    reason,
+                    BookError::ChecksumMismatch { expected: 1, .. }
+                ));
+            }
+            other => panic!("应要求重新订阅，实际为 {other:?}"),
+        }
+        assert!(manager
+            .book(&Channel::Books {
+                inst_id: "BTC-USDT".to_string()
+            })
+            .is_none());
+    }
+
+    /// 记录重新订阅次数的客户端
+    #[derive(Default)]
+    struct CountingClient {
+        resubscribed: Vec<Channel>,
+    }
+
+    impl Resubscribe for CountingClient {
+        async fn resubscribe(&mut self, channel: Channel) -> Result<()> {
+            self.resubscribed.push(channel);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn updates_after_resync_wait_for_snapshot_without_resubscribing() {
+        let mut manager = OrderBookManager::new();
+        let mut client = CountingClient::default();
+        manager
+            .handle(&mut client, &snapshot())
+            .await
+            .expect("快照应成功应用");
+
+        let bad = book_msg(
+            "update",
+            json!({"bids": [["100.9", "1", "0", "1"]], "asks": [], "seqId": 11, "prevSeqId": 10, "checksum": 1}),
+        );
+        assert!(matches!(
+            manager.handle(&mut client, &bad).await,
+            Ok(BookUpdate::Resync { .. })
+        ));
+
+        // 新快照到达前仍在途的增量应被丢弃，而不是反复触发重新订阅
+        for seq in 12..15 {
+            let update = book_msg(
+                "update",
+                json!({"bids": [], "asks": [], "seqId": seq, "prevSeqId": seq - 1}),
+            );
+            assert_eq!(
+                manager
+                    .handle(&mut client, &update)
+                    .await
+                    .expect("不应出错"),
+                BookUpdate::Ignored
+            );
+        }
+        assert_eq!(client.resubscribed.len(), 1);
+
+        manager
+            .handle(&mut client, &snapshot())
+            .await
+            .expect("新快照应成功应用");
+        let channel = Channel::Books {
+            inst_id: "BTC-USDT".to_string(),
+        };
+        assert_eq!(
+            manager.book(&channel).and_then(LocalOrderBook::seq_id),
+            Some(10)
+        );
+        assert_eq!(client.resubscribed, vec![channel]);
+    }
+
+    #[test]
+    fn sequence_gap_and_missing_snapshot_require_resync() {
+        let mut manager = OrderBookManager::new();
+        let update = book_msg(
+            "update",
+            json!({"bids": [], "asks": [], "seqId": 13, "prevSeqId": 12}),
+        );
+        assert!(matches!(
+            manager.apply(&update),
+            BookUpdate::Resync {
+                reason: BookError::MissingSnapshot,
+                ..
+            }
+        ));
+
+        manager.apply(&snapshot());
+        assert!(matches!(
+            manager.apply(&update),
+            BookUpdate::Resync {
+                reason: BookError::SequenceGap {
+                    expected: 10,
+                    actual: 12
+                },
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn non_book_messages_are_ignored() {
+        let mut manager = OrderBookManager::new();
+        let ticker = WsMessage::Data {
+            channel: "tickers".to_string(),
+            arg: json!({"channel": "tickers", "instId": "BTC-USDT"}),
+            data: vec![json!({})],
+            action: None,
+        };
+        assert_eq!(manager.apply(&ticker), BookUpdate::Ignored);
+        assert_eq!(manager.apply(&WsMessage::Pong), BookUpdate::Ignored);
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Provides automatic reconnection with exponential backoff and subscription state recovery.
 
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
@@ -12,7 +13,7 @@ use futures_util::Stream;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
-use okx_core::{Config, OkxError, Result, TimestampProvider};
+use okx_core::{types::ApiResponse, Config, OkxError, Result, TimestampProvider};
 
 use crate::channel::Channel;
 use crate::client::WsClient;
@@ -31,6 +32,8 @@ pub struct ReconnectConfig {
     pub max_attempts: Option<u32>,
     /// Whether to automatically restore subscriptions after reconnect
     pub restore_subscriptions: bool,
+    /// Whether to switch to a new connection when OKX announces a service upgrade
+    pub proactive_reconnect: bool,
 }
 
 impl Default for ReconnectConfig {
@@ -41,6 +44,7 @@ impl Default for ReconnectConfig {
             backoff_multiplier: 2.0,
             max_attempts: None,
             restore_subscriptions: true,
+            proactive_reconnect: true,
         }
     }
 }
@@ -86,15 +90,40 @@ impl ReconnectConfig {
         self.restore_subscriptions = restore;
         self
     }
+
+    /// Set whether a service-upgrade notice (code `64008`) triggers a
+    /// make-before-break switch to a new connection.
+    #[must_use]
+    pub fn with_proactive_reconnect(mut self, proactive: bool) -> Self {
+        self.proactive_reconnect = proactive;
+        self
+    }
 }
 
 /// Connection type for the WebSocket client.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ConnectionType {
     /// Public WebSocket connection
     Public,
     /// Private WebSocket connection (requires authentication)
     Private,
+    /// Business WebSocket connection (public channels, plus private ones after login)
+    Business,
+}
+
+impl ConnectionType {
+    /// Check if a connection of this type is logged in to serve `channels`.
+    ///
+    /// Private connections always log in; business connections only when a
+    /// private channel is involved.
+    #[must_use]
+    pub fn needs_login(self, channels: &[Channel]) -> bool {
+        match self {
+            Self::Public => false,
+            Self::Private => true,
+            Self::Business => channels.iter().any(Channel::is_private),
+        }
+    }
 }
 
 /// State of the reconnecting client.
@@ -114,6 +143,20 @@ pub enum ConnectionState {
 ///
 /// Wraps `WsClient` with automatic reconnection and subscription state recovery.
 ///
+/// The stream reconnects by itself when the connection drops: it yields
+/// [`WsMessage::Reconnecting`] per attempt, then [`WsMessage::Reconnected`]
+/// once connected (and logged in again for private connections) and
+/// [`WsMessage::SubscriptionsRestored`] after re-subscribing. Connection
+/// errors are not yielded; the stream ends after `max_attempts` failures.
+///
+/// When OKX announces a planned disconnection (a `notice` event with code
+/// `64008`), the notice is yielded and a replacement connection is opened,
+/// logged in and re-subscribed while the current one keeps streaming; the old
+/// connection is dropped once the new one is ready, followed by
+/// [`WsMessage::Reconnected`]. Channels subscribed or unsubscribed during the
+/// switch are applied to the new connection first. A few pushes may arrive on
+/// both connections around the switch.
+///
 /// ## Example
 ///
 /// ```rust,no_run
@@ -142,6 +185,7 @@ pub enum ConnectionState {
 ///             Ok(WsMessage::Data { channel, data, .. }) => {
 ///                 println!("Channel: {}, Data: {:?}", channel, data);
 ///             }
+///             Ok(WsMessage::Reconnected) => println!("Reconnected"),
 ///             Err(e) => eprintln!("Error: {}", e),
 ///             _ => {}
 ///         }
@@ -169,8 +213,17 @@ pub struct ReconnectingWsClient {
     current_delay: Duration,
     /// Optional timestamp provider for login during reconnection
     timestamp_provider: Option<Arc<dyn TimestampProvider>>,
+    /// Reconnection driven by the stream, resolving to the new client and the restored channels
+    reconnecting: Option<ReconnectFuture>,
+    /// Lifecycle events waiting to be yielded by the stream
+    events: VecDeque<WsMessage>,
+    /// Replacement connection opened after a service-upgrade notice
+    switching: Option<ReconnectFuture>,
 }
 
+/// In-flight reconnection of the stream.
+type ReconnectFuture = Pin<Box<dyn Future<Output = Result<(WsClient, Vec<ChannelKey>)>> + Send>>;
+
 /// Key for tracking subscriptions (serialized channel).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct ChannelKey(String);
@@ -207,6 +260,9 @@ impl ReconnectingWsClient {
             attempt_count: 0,
             current_delay: initial_delay,
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         })
     }
 
@@ -261,6 +317,9 @@ impl ReconnectingWsClient {
             attempt_count: 0,
             current_delay: initial_delay,
             timestamp_provider: Some(timestamp_provider),
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         })
     }
 
@@ -326,6 +385,64 @@ impl ReconnectingWsClient {
         Ok(())
     }
 
+    /// Log in through the timestamp provider if `channels` need a login.
+    ///
+    /// Does nothing when the connection is already logged in.
+    pub(crate) async fn login_for(&mut self, channels: &[Channel]) -> Result<()> {
+        if !self.conn_type.needs_login(channels) {
+            return Ok(());
+        }
+        let Some(client) = &mut self.client else {
+            return Err(OkxError::ConnectionClosed);
+        };
+        login(client, self.timestamp_provider.as_deref()).await
+    }
+
+    /// Stop tracking channels for recovery without sending an unsubscribe.
+    pub(crate) fn forget(&mut self, channels: &[Channel]) {
+        for channel in channels {
+            self.subscriptions.remove(&ChannelKey::from(channel));
+        }
+    }
+
+    /// Subscribe to channels and wait for the acknowledgement of each one.
+    ///
+    /// Only confirmed channels are tracked for recovery. See
+    /// [`WsClient::subscribe_confirmed`] for the per-channel results.
+    pub async fn subscribe_confirmed(
+        &mut self,
+        channels: Vec<Channel>,
+    ) -> Result<Vec<(Channel, Result<()>)>> {
+        let Some(client) = &mut self.client else {
+            return Err(OkxError::ConnectionClosed);
+        };
+
+        let results = client.subscribe_confirmed(channels).await?;
+        for (channel, result) in &results {
+            if result.is_ok() {
+                self.subscriptions.insert(ChannelKey::from(channel));
+            }
+        }
+        Ok(results)
+    }
+
+    /// Unsubscribe from channels and wait for the acknowledgement of each one.
+    ///
+    /// Channels are no longer tracked for recovery, whatever the outcome.
+    pub async fn unsubscribe_confirmed(
+        &mut self,
+        channels: Vec<Channel>,
+    ) -> Result<Vec<(Channel, Result<()>)>> {
+        for channel in &channels {
+            self.subscriptions.remove(&ChannelKey::from(channel));
+        }
+
+        let Some(client) = &mut self.client else {
+            return Err(OkxError::ConnectionClosed);
+        };
+        client.unsubscribe_confirmed(channels).await
+    }
+
     /// Send a ping to keep the connection alive.
     pub async fn ping(&mut self) -> Result<()> {
         if let Some(client) = &mut self.client {
@@ -335,9 +452,23 @@ impl ReconnectingWsClient {
         }
     }
 
+    /// Send a trading op on the current connection and wait for its response.
+    pub(crate) async fn send_op(
+        &mut self,
+        op: &str,
+        args: Vec<serde_json::Value>,
+    ) -> Result<ApiResponse<serde_json::Value>> {
+        if let Some(client) = &mut self.client {
+            client.send_op(op, args).await
+        } else {
+            Err(OkxError::ConnectionClosed)
+        }
+    }
+
     /// Close the connection and stop reconnection attempts.
     pub async fn close(&mut self) -> Result<()> {
         self.state = ConnectionState::Failed;
+        self.switching = None;
         if let Some(client) = &mut self.client {
             client.close().await?;
         }
@@ -362,8 +493,26 @@ impl ReconnectingWsClient {
     }
 
     /// Manually trigger a reconnection.
+    ///
+    /// Drops the current connection and runs the same reconnection as the
+    /// stream (backoff, login, subscription restore). The lifecycle events
+    /// (`Reconnecting`, `Reconnected`, `SubscriptionsRestored`) are queued and
+    /// yielded by the stream. Fails once `max_attempts` is reached.
     pub async fn reconnect(&mut self) -> Result<()> {
-        self.do_reconnect().await
+        self.reconnecting = None;
+        self.switching = None;
+        if let Some(e) = self.schedule_reconnect() {
+            return Err(e);
+        }
+
+        while let Some(reconnecting) = &mut self.reconnecting {
+            let result = reconnecting.as_mut().await;
+            self.reconnecting = None;
+            if let Some(e) = self.handle_reconnect_result(result) {
+                return Err(e);
+            }
+        }
+        Ok(())
     }
 
     /// Create a new WebSocket client.
@@ -371,105 +520,188 @@ impl ReconnectingWsClient {
         match conn_type {
             ConnectionType::Public => WsClient::connect_public(config).await,
             ConnectionType::Private => WsClient::connect_private(config).await,
+            ConnectionType::Business => WsClient::connect_business(config).await,
         }
     }
 
-    /// Perform reconnection with backoff.
-    async fn do_reconnect(&mut self) -> Result<()> {
-        self.state = ConnectionState::Reconnecting;
+    /// Start a reconnection and queue its `Reconnecting` event.
+    ///
+    /// Returns an error (and switches to `Failed`) once `max_attempts` is reached.
+    fn schedule_reconnect(&mut self) -> Option<OkxError> {
         self.client = None;
+        self.state = ConnectionState::Reconnecting;
 
-        loop {
-            // Check max attempts
-            if let Some(max) = self.reconnect_config.max_attempts {
-                if self.attempt_count >= max {
-                    error!("Max reconnection attempts ({}) reached", max);
-                    self.state = ConnectionState::Failed;
-                    return Err(OkxError::Other(format!(
-                        "Max reconnection attempts ({}) reached",
-                        max
-                    )));
-                }
+        if let Some(max) = self.reconnect_config.max_attempts {
+            if self.attempt_count >= max {
+                error!("Max reconnection attempts ({}) reached", max);
+                self.state = ConnectionState::Failed;
+                return Some(OkxError::Other(format!(
+                    "Max reconnection attempts ({}) reached",
+                    max
+                )));
             }
+        }
 
-            self.attempt_count += 1;
-            info!(
-                "Reconnection attempt {} (delay: {:?})",
-                self.attempt_count, self.current_delay
-            );
-
-            // Wait before attempting
-            sleep(self.current_delay).await;
-
-            // Try to connect
-            match Self::create_client(&self.config, self.conn_type).await {
-                Ok(client) => {
-                    info!("Reconnection successful");
-                    self.client = Some(client);
-                    self.state = ConnectionState::Connected;
-                    self.attempt_count = 0;
-                    self.current_delay = self.reconnect_config.initial_delay;
-
-                    // Restore subscriptions if configured
-                    if self.reconnect_config.restore_subscriptions {
-                        self.restore_subscriptions().await?;
-                    }
+        self.attempt_count += 1;
+        info!(
+            "Reconnection attempt {} (delay: {:?})",
+            self.attempt_count, self.current_delay
+        );
 
-                    return Ok(());
-                }
-                Err(e) => {
-                    warn!("Reconnection attempt {} failed: {}", self.attempt_count, e);
-
-                    // Increase backoff delay
-                    let new_delay = Duration::from_secs_f64(
-                        self.current_delay.as_secs_f64() * self.reconnect_config.backoff_multiplier,
-                    );
-                    self.current_delay = new_delay.min(self.reconnect_config.max_delay);
-                }
+        self.reconnecting = Some(self.connect_future(self.current_delay));
+        self.events.push_back(WsMessage::Reconnecting {
+            attempt: self.attempt_count,
+        });
+        None
+    }
+
+    /// Install the client of a finished attempt, or back off and schedule the
+    /// next one. Returns an error once `max_attempts` is reached.
+    fn handle_reconnect_result(
+        &mut self,
+        result: Result<(WsClient, Vec<ChannelKey>)>,
+    ) -> Option<OkxError> {
+        match result {
+            Ok((client, restored)) => {
+                self.reconnecting = self.sync_subscriptions(client, restored);
+                None
+            }
+            Err(e) => {
+                warn!("Reconnection attempt {} failed: {}", self.attempt_count, e);
+                self.backoff();
+                self.schedule_reconnect()
             }
         }
     }
 
-    /// Restore all tracked subscriptions.
-    async fn restore_subscriptions(&mut self) -> Result<()> {
-        if self.subscriptions.is_empty() {
-            return Ok(());
+    /// Open a replacement connection after a service-upgrade notice.
+    ///
+    /// The current connection keeps streaming until the new one is logged in
+    /// and re-subscribed.
+    fn start_switch(&mut self) {
+        if !self.reconnect_config.proactive_reconnect
+            || self.switching.is_some()
+            || self.reconnecting.is_some()
+        {
+            return;
         }
+        info!("Service upgrade notice received, opening a replacement connection");
+        self.switching = Some(self.connect_future(Duration::ZERO));
+    }
 
-        info!("Restoring {} subscriptions", self.subscriptions.len());
+    /// Wait `delay`, connect, login if needed and re-subscribe the tracked channels.
+    fn connect_future(&self, delay: Duration) -> ReconnectFuture {
+        let keys: Vec<ChannelKey> = if self.reconnect_config.restore_subscriptions {
+            self.subscriptions.iter().cloned().collect()
+        } else {
+            Vec::new()
+        };
+        let config = self.config.clone();
+        let conn_type = self.conn_type;
+        let provider = self.timestamp_provider.clone();
+
+        Box::pin(async move {
+            sleep(delay).await;
+            let mut client = Self::create_client(&config, conn_type).await?;
+            let channels: Vec<Channel> = keys.iter().filter_map(ChannelKey::to_channel).collect();
+            if conn_type.needs_login(&channels) {
+                login(&mut client, provider.as_deref()).await?;
+            }
+            resubscribe(&mut client, &keys).await?;
+            Ok((client, keys))
+        })
+    }
 
-        let channels: Vec<Channel> = self
-            .subscriptions
-            .iter()
-            .filter_map(|key| key.to_channel())
-            .collect();
+    /// Apply the subscription changes made while the reconnection was in flight.
+    ///
+    /// Channels subscribed meanwhile are subscribed on the new connection, and
+    /// channels unsubscribed meanwhile are unsubscribed from it. Returns the
+    /// follow-up future if the new connection needs changes; the caller drives
+    /// it in place of the original one and syncs again once it completes.
+    #[must_use]
+    fn sync_subscriptions(
+        &mut self,
+        client: WsClient,
+        restored: Vec<ChannelKey>,
+    ) -> Option<ReconnectFuture> {
+        let (kept, stale): (Vec<ChannelKey>, Vec<ChannelKey>) = restored
+            .into_iter()
+            .partition(|key| self.subscriptions.contains(key));
+        let missing: Vec<ChannelKey> = if self.reconnect_config.restore_subscriptions {
+            self.subscriptions
+                .iter()
+                .filter(|key| !kept.contains(key))
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        };
 
-        if channels.is_empty() {
-            return Ok(());
+        if missing.is_empty() && stale.is_empty() {
+            self.finish_reconnect(client, kept.len());
+            return None;
         }
 
-        // For private connections, login first
-        if self.conn_type == ConnectionType::Private {
-            if let Some(client) = &mut self.client {
-                // Use timestamp provider if available, otherwise use local time
-                if let Some(provider) = &self.timestamp_provider {
-                    let timestamp = provider.timestamp_unix_secs().to_string();
-                    debug!("Logging in with timestamp provider: {}", timestamp);
-                    client.login_with_timestamp(&timestamp).await?;
-                } else {
-                    client.login().await?;
-                }
-            }
-        }
+        Some(Box::pin(async move {
+            let mut client = client;
+            unsubscribe(&mut client, &stale).await?;
+            resubscribe(&mut client, &missing).await?;
+            Ok((client, kept.into_iter().chain(missing).collect()))
+        }))
+    }
 
-        // Re-subscribe
-        if let Some(client) = &mut self.client {
-            client.subscribe(channels).await?;
+    /// Install the new client and queue the lifecycle events.
+    fn finish_reconnect(&mut self, client: WsClient, restored: usize) {
+        info!("Reconnection successful");
+        self.client = Some(client);
+        self.state = ConnectionState::Connected;
+        self.attempt_count = 0;
+        self.current_delay = self.reconnect_config.initial_delay;
+
+        self.events.push_back(WsMessage::Reconnected);
+        if restored > 0 {
+            info!("Restored {} subscriptions", restored);
+            self.events
+                .push_back(WsMessage::SubscriptionsRestored { count: restored });
         }
+    }
 
-        info!("Subscriptions restored successfully");
-        Ok(())
+    /// Increase the backoff delay after a failed attempt.
+    fn backoff(&mut self) {
+        let new_delay = Duration::from_secs_f64(
+            self.current_delay.as_secs_f64() * self.reconnect_config.backoff_multiplier,
+        );
+        self.current_delay = new_delay.min(self.reconnect_config.max_delay);
+    }
+}
+
+/// Login, using the timestamp provider if available and local time otherwise.
+async fn login(client: &mut WsClient, provider: Option<&dyn TimestampProvider>) -> Result<()> {
+    if let Some(provider) = provider {
+        let timestamp = provider.timestamp_unix_secs().to_string();
+        debug!("Logging in with timestamp provider: {}", timestamp);
+        client.login_with_timestamp(&timestamp).await
+    } else {
+        client.login().await
+    }
+}
+
+/// Re-subscribe tracked channels, skipping keys that no longer deserialize.
+async fn resubscribe(client: &mut WsClient, keys: &[ChannelKey]) -> Result<()> {
+    let channels: Vec<Channel> = keys.iter().filter_map(ChannelKey::to_channel).collect();
+    if channels.is_empty() {
+        return Ok(());
     }
+    client.subscribe(channels).await
+}
+
+/// Unsubscribe channels that are no longer tracked.
+async fn unsubscribe(client: &mut WsClient, keys: &[ChannelKey]) -> Result<()> {
+    let channels: Vec<Channel> = keys.iter().filter_map(ChannelKey::to_channel).collect();
+    if channels.is_empty() {
+        return Ok(());
+    }
+    client.unsubscribe(channels).await
 }
 
 /// 将频道序列化为字符串键（用于订阅恢复或测试）。
@@ -486,44 +718,91 @@ impl Stream for ReconnectingWsClient {
     type Item = Result<WsMessage>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        // If we're in a failed state, return None
-        if self.state == ConnectionState::Failed {
-            return Poll::Ready(None);
-        }
+        let this = &mut *self;
 
-        // If we're disconnected or reconnecting, wake up later
-        if self.state != ConnectionState::Connected {
-            cx.waker().wake_by_ref();
-            return Poll::Pending;
-        }
+        loop {
+            // Lifecycle events go first
+            if let Some(event) = this.events.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+
+            // If we're in a failed state, return None
+            if this.state == ConnectionState::Failed {
+                return Poll::Ready(None);
+            }
+
+            // Drive an in-flight reconnection
+            if let Some(reconnecting) = &mut this.reconnecting {
+                let Poll::Ready(result) = reconnecting.as_mut().poll(cx) else {
+                    return Poll::Pending;
+                };
+                this.reconnecting = None;
+                if let Some(e) = this.handle_reconnect_result(result) {
+                    return Poll::Ready(Some(Err(e)));
+                }
+                continue;
+            }
+
+            // Drive a make-before-break switch while the current connection keeps streaming
+            if let Some(switching) = &mut this.switching {
+                match switching.as_mut().poll(cx) {
+                    Poll::Pending => {}
+                    Poll::Ready(Ok((client, restored))) => {
+                        // 切换期间订阅有变化时，旧连接继续推送直到新连接同步完成
+                        this.switching = this.sync_subscriptions(client, restored);
+                        if this.switching.is_none() {
+                            info!("Replacement connection ready, dropped the old one");
+                        }
+                        continue;
+                    }
+                    Poll::Ready(Err(e)) => {
+                        warn!(
+                            "Replacement connection failed, keeping the current one: {}",
+                            e
+                        );
+                        this.switching = None;
+                    }
+                }
+            }
+
+            // Poll the inner client
+            let Some(client) = &mut this.client else {
+                // No client, we're disconnected
+                if let Some(e) = this.schedule_reconnect() {
+                    return Poll::Ready(Some(Err(e)));
+                }
+                continue;
+            };
 
-        // Poll the inner client
-        if let Some(client) = &mut self.client {
             match Pin::new(client).poll_next(cx) {
-                Poll::Ready(Some(Ok(msg))) => Poll::Ready(Some(Ok(msg))),
-                Poll::Ready(Some(Err(e))) => {
-                    // Check if this is a connection error
-                    if matches!(e, OkxError::ConnectionClosed | OkxError::WebSocket(_)) {
-                        debug!("Connection error detected: {}", e);
-                        // We can't do async reconnection here, so we return the error
-                        // and let the user handle it or call reconnect()
-                        self.state = ConnectionState::Disconnected;
+                Poll::Ready(Some(Ok(msg))) => {
+                    if msg.is_service_upgrade_notice() {
+                        this.start_switch();
                     }
-                    Poll::Ready(Some(Err(e)))
+                    return Poll::Ready(Some(Ok(msg)));
                 }
-                Poll::Ready(None) => {
-                    // Stream ended, connection closed
-                    debug!("WebSocket stream ended");
-                    self.state = ConnectionState::Disconnected;
-                    Poll::Ready(Some(Err(OkxError::ConnectionClosed)))
+                Poll::Ready(Some(Err(e))) => {
+                    // Connection errors are turned into a reconnection
+                    if !matches!(e, OkxError::ConnectionClosed | OkxError::WebSocket(_)) {
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    debug!("Connection error detected: {}", e);
                 }
-                Poll::Pending => Poll::Pending,
+                Poll::Ready(None) => debug!("WebSocket stream ended"),
+                Poll::Pending => return Poll::Pending,
+            }
+
+            this.state = ConnectionState::Disconnected;
+            if let Some(switching) = this.switching.take() {
+                // 旧连接先于替换连接断开：直接等待替换连接就绪
+                this.client = None;
+                this.state = ConnectionState::Reconnecting;
+                this.reconnecting = Some(switching);
+                continue;
+            }
+            if let Some(e) = this.schedule_reconnect() {
+                return Poll::Ready(Some(Err(e)));
             }
-        } else {
-            // No client, we're disconnected
-            self.state = ConnectionState::Disconnected;
-            cx.waker().wake_by_ref();
-            Poll::Pending
         }
     }
 }
@@ -544,6 +823,7 @@ mod tests {
         assert_eq!(config.backoff_multiplier, 2.0);
         assert!(config.max_attempts.is_none());
         assert!(config.restore_subscriptions);
+        assert!(config.proactive_reconnect);
     }
 
     #[test]
@@ -553,13 +833,15 @@ mod tests {
             .with_max_delay(Duration::from_secs(30))
             .with_backoff_multiplier(1.5)
             .with_max_attempts(5)
-            .with_restore_subscriptions(false);
+            .with_restore_subscriptions(false)
+            .with_proactive_reconnect(false);
 
         assert_eq!(config.initial_delay, Duration::from_millis(500));
         assert_eq!(config.max_delay, Duration::from_secs(30));
         assert_eq!(config.backoff_multiplier, 1.5);
         assert_eq!(config.max_attempts, Some(5));
         assert!(!config.restore_subscriptions);
+        assert!(!config.proactive_reconnect);
     }
 
     #[test]
@@ -589,6 +871,9 @@ mod tests {
             attempt_count: 1,
             current_delay: Duration::from_secs(2),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         assert_eq!(client.state(), ConnectionState::Disconnected);
@@ -625,7 +910,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn do_reconnect_respects_max_attempts_and_sets_failed_state() {
+    async fn reconnect_respects_max_attempts_and_sets_failed_state() {
         let cfg = Config::new(Credentials::new("k", "s", "p"))
             .with_ws_public_url("ws://127.0.0.1:9/ws")
             .with_ws_private_url("ws://127.0.0.1:9/ws");
@@ -646,10 +931,13 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         // Windows TCP 连接超时较长（约 21 秒），需要足够的超时时间
-        let err = timeout(Duration::from_secs(30), client.do_reconnect())
+        let err = timeout(Duration::from_secs(30), client.reconnect())
             .await
             .expect("重连流程超时")
             .expect_err("应达到最大重连次数并失败");
@@ -659,36 +947,6 @@ mod tests {
         assert!(err.to_string().contains("Max reconnection attempts"));
     }
 
-    #[tokio::test]
-    async fn restore_subscriptions_returns_ok_for_empty_and_invalid_keys() {
-        let cfg = Config::new(Credentials::new("k", "s", "p"));
-        let reconnect_config = ReconnectConfig::default().with_restore_subscriptions(true);
-
-        let mut client = ReconnectingWsClient {
-            client: None,
-            config: cfg,
-            conn_type: ConnectionType::Public,
-            reconnect_config,
-            state: ConnectionState::Connected,
-            subscriptions: HashSet::new(),
-            attempt_count: 0,
-            current_delay: Duration::from_millis(1),
-            timestamp_provider: None,
-        };
-
-        // 空订阅应直接返回 Ok
-        client.restore_subscriptions().await.expect("空订阅应 Ok");
-
-        // 插入无法反序列化的 key，channels 为空也应 Ok
-        client
-            .subscriptions
-            .insert(ChannelKey("not-json".to_string()));
-        client
-            .restore_subscriptions()
-            .await
-            .expect("无效 key 应被过滤并返回 Ok");
-    }
-
     #[tokio::test]
     async fn subscribe_and_unsubscribe_track_without_inner_client() {
         let cfg = Config::new(Credentials::new("k", "s", "p"));
@@ -704,6 +962,9 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         client
@@ -737,6 +998,9 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         let err = client.ping().await.expect_err("无连接时 ping 应失败");
@@ -759,13 +1023,16 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         assert!(client.next().await.is_none());
     }
 
     #[test]
-    fn stream_poll_next_connected_without_inner_client_sets_disconnected_and_pending() {
+    fn stream_poll_next_without_inner_client_schedules_reconnect() {
         let cfg = Config::new(Credentials::new("k", "s", "p"));
         let reconnect_config = ReconnectConfig::default();
         let mut client = ReconnectingWsClient {
@@ -778,13 +1045,20 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         let waker = futures_util::task::noop_waker_ref();
         let mut cx = Context::from_waker(waker);
         let polled = Pin::new(&mut client).poll_next(&mut cx);
-        assert!(matches!(polled, Poll::Pending));
-        assert_eq!(client.state(), ConnectionState::Disconnected);
+        assert!(matches!(
+            polled,
+            Poll::Ready(Some(Ok(WsMessage::Reconnecting { attempt: 1 })))
+        ));
+        assert_eq!(client.state(), ConnectionState::Reconnecting);
+        assert!(client.reconnecting.is_some());
     }
 
     #[tokio::test]
@@ -846,6 +1120,9 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         client.close().await.expect("close 应返回 Ok");
@@ -853,8 +1130,8 @@ mod tests {
         assert!(client.client.is_none());
     }
 
-    #[test]
-    fn stream_poll_next_reconnecting_wakes_and_pending() {
+    #[tokio::test]
+    async fn stream_poll_next_is_pending_while_reconnect_in_flight() {
         let cfg = Config::new(Credentials::new("k", "s", "p"));
         let reconnect_config = ReconnectConfig::default();
         let mut client = ReconnectingWsClient {
@@ -865,17 +1142,145 @@ mod tests {
             state: ConnectionState::Reconnecting,
             subscriptions: HashSet::new(),
             attempt_count: 0,
-            current_delay: Duration::from_millis(1),
+            current_delay: Duration::from_secs(60),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         let waker = futures_util::task::noop_waker_ref();
         let mut cx = Context::from_waker(waker);
         let polled = Pin::new(&mut client).poll_next(&mut cx);
+        assert!(matches!(
+            polled,
+            Poll::Ready(Some(Ok(WsMessage::Reconnecting { .. })))
+        ));
+
+        // 退避等待中：保持 Pending
+        let polled = Pin::new(&mut client).poll_next(&mut cx);
         assert!(matches!(polled, Poll::Pending));
         assert_eq!(client.state(), ConnectionState::Reconnecting);
     }
 
+    #[tokio::test]
+    async fn stream_gives_up_after_max_attempts() {
+        use futures_util::StreamExt;
+
+        let cfg = Config::new(Credentials::new("k", "s", "p"))
+            .with_ws_public_url("ws://127.0.0.1:9/ws")
+            .with_ws_private_url("ws://127.0.0.1:9/ws");
+        let reconnect_config = ReconnectConfig::default()
+            .with_initial_delay(Duration::from_millis(1))
+            .with_max_attempts(1);
+        let mut client = ReconnectingWsClient {
+            client: None,
+            config: cfg,
+            conn_type: ConnectionType::Public,
+            reconnect_config,
+            state: ConnectionState::Disconnected,
+            subscriptions: HashSet::new(),
+            attempt_count: 0,
+            current_delay: Duration::from_millis(1),
+            timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
+        };
+
+        let msg = client.next().await.expect("应有事件").expect("事件应为 Ok");
+        assert!(matches!(msg, WsMessage::Reconnecting { attempt: 1 }));
+
+        // Windows TCP 连接超时较长（约 21 秒），需要足够的超时时间
+        let err = timeout(Duration::from_secs(30), client.next())
+            .await
+            .expect("重连流程超时")
+            .expect("应返回错误")
+            .expect_err("应达到最大重连次数");
+        assert!(err.to_string().contains("Max reconnection attempts"));
+        assert_eq!(client.state(), ConnectionState::Failed);
+        assert!(client.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn stream_reconnects_and_restores_subscriptions() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio::net::TcpListener;
+        use tokio_tungstenite::{accept_async, tungstenite::Message};
+
+        let listener = match TcpListener::bind("127.0.0.1:0").await {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("无法绑定本地端口，跳过用例: {e}");
+                return;
+            }
+        };
+        let addr = listener.local_addr().expect("应获取本地地址");
+
+        let server = tokio::spawn(async move {
+            // 第一个连接：收到订阅后立即断开
+            let (stream, _) = listener.accept().await.expect("应接受连接");
+            let mut ws = accept_async(stream).await.expect("握手应成功");
+            let _ = ws.next().await;
+            drop(ws);
+
+            // 第二个连接：应恢复订阅，然后推送数据
+            let (stream, _) = listener.accept().await.expect("应接受重连");
+            let mut ws = accept_async(stream).await.expect("握手应成功");
+            let msg = ws.next().await.expect("应收到订阅").expect("消息应为 Ok");
+            let Message::Text(text) = msg else {
+                panic!("预期 Text 订阅请求，实际为: {msg:?}");
+            };
+            assert!(text.contains("BTC-USDT"));
+            let _ = ws
+                .send(Message::Text(
+                    r#"{"arg":{"channel":"tickers","instId":"BTC-USDT"},"data":[]}"#.into(),
+                ))
+                .await;
+            ws
+        });
+
+        let cfg = Config::new(Credentials::new("k", "s", "p"))
+            .with_ws_public_url(format!("ws://{addr}/ws"));
+        let reconnect_config = ReconnectConfig::default()
+            .with_initial_delay(Duration::from_millis(1))
+            .with_max_attempts(3);
+        let mut client =
+            ReconnectingWsClient::connect(cfg, ConnectionType::Public, reconnect_config)
+                .await
+                .expect("连接应成功");
+        client
+            .subscribe(vec![Channel::Tickers {
+                inst_id: "BTC-USDT".to_string(),
+            }])
+            .await
+            .expect("订阅应成功");
+
+        let mut received = Vec::new();
+        while received.len() < 4 {
+            let msg = timeout(Duration::from_secs(5), client.next())
+                .await
+                .expect("等待消息超时")
+                .expect("流不应结束")
+                .expect("连接错误应转为重连事件");
+            received.push(msg);
+        }
+
+        assert!(matches!(
+            received[0],
+            WsMessage::Reconnecting { attempt: 1 }
+        ));
+        assert!(matches!(received[1], WsMessage::Reconnected));
+        assert!(matches!(
+            received[2],
+            WsMessage::SubscriptionsRestored { count: 1 }
+        ));
+        assert!(matches!(received[3], WsMessage::Data { .. }));
+        assert!(client.is_connected());
+
+        let _ws = server.await.expect("服务端任务不应 panic");
+    }
+
     #[test]
     fn test_set_and_clear_timestamp_provider() {
         use okx_core::LocalTimeProvider;
@@ -892,6 +1297,9 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         // Initially no provider
@@ -930,6 +1338,9 @@ mod tests {
             attempt_count: 0,
             current_delay: Duration::from_millis(1),
             timestamp_provider: None,
+            reconnecting: None,
+            events: VecDeque::new(),
+            switching: None,
         };
 
         // Windows TCP 连接超时较长（约 21 秒），需要足够的超时时间
@@ -0,0 +1,1142 @@
+//! In-process mock of the OKX WebSocket server for offline tests.
+//!
+//! Enabled with the `testing` feature. [`MockWsServer`] listens on
+//! `127.0.0.1` and speaks enough of the OKX protocol to drive [`WsClient`],
+//! [`ReconnectingWsClient`] and [`WsManager`] without network access:
+//!
+//! - `login` is verified with [`Signer`] against the server credentials
+//!   (API key, passphrase, signature, timestamp within 30 seconds)
+//! - `subscribe` / `unsubscribe` are acknowledged per channel; unknown
+//!   channels, channels of another endpoint, private channels before login and
+//!   channels registered with [`MockWsServer::reject`] get an `error` event
+//! - trading ops get the response set with [`MockWsServer::respond_to`], or an
+//!   echo of their args with `sCode = "0"`
+//! - `ping` is answered with `pong`
+//!
+//! Tests script pushes with [`MockWsServer::push`], drop every connection with
+//! [`MockWsServer::drop_connections`] and replay a recorded session (see
+//! [`MockWsServer::transcript`]) with [`MockWsServer::replay`].
+//!
+//! [`WsClient`]: crate::WsClient
+//! [`ReconnectingWsClient`]: crate::ReconnectingWsClient
+//! [`WsManager`]: crate::WsManager
+//!
+//! Source: OKX API v5 WebSocket API
+//! - <https://www.okx.com/docs-v5/en/#overview-websocket>
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Instant};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{Request, Response},
+        Message,
+    },
+};
+use tracing::debug;
+
+use okx_core::{Config, Credentials, OkxError, Result, Signer};
+
+use crate::channel::Channel;
+use crate::reconnect::ConnectionType;
+
+/// Default tolerance between the login timestamp and the server clock.
+const DEFAULT_LOGIN_WINDOW: Duration = Duration::from_secs(30);
+
+/// Direction of a recorded frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Sent by the client
+    Inbound,
+    /// Sent by the server
+    Outbound,
+}
+
+/// A text frame exchanged with the mock server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Connection number, in accept order starting at 1
+    pub conn: u64,
+    /// Who sent the frame
+    pub direction: Direction,
+    /// Frame text
+    pub text: String,
+}
+
+/// In-process OKX WebSocket server.
+///
+/// Each endpoint is served under its OKX path (`/ws/v5/public`,
+/// `/ws/v5/private`, `/ws/v5/business`); [`MockWsServer::config`] points all
+/// three URLs of a [`Config`] at the server. Dropping the server closes every
+/// connection.
+///
+/// ```rust,no_run
+/// use okx_ws::testing::MockWsServer;
+/// use okx_ws::{Channel, Credentials, WsClient};
+/// use serde_json::json;
+///
+/// # async fn run() -> okx_ws::Result<()> {
+/// let server = MockWsServer::start(Credentials::new("key", "secret", "pass")).await?;
+/// let mut client = WsClient::connect_public(&server.config()).await?;
+/// client
+///     .subscribe_confirmed(vec![Channel::Tickers { inst_id: "BTC-USDT".into() }])
+///     .await?;
+/// server.push(
+///     &json!({"channel": "tickers", "instId": "BTC-USDT"}),
+///     vec![json!({"instId": "BTC-USDT", "last": "42000"})],
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct MockWsServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    accept_task: JoinHandle<()>,
+}
+
+/// Instruction for a connection task.
+enum Command {
+    Send(String),
+    Drop,
+}
+
+struct Connection {
+    endpoint: Option<ConnectionType>,
+    logged_in: bool,
+    subscriptions: Vec<Value>,
+    tx: mpsc::UnboundedSender<Command>,
+}
+
+struct Rejection {
+    arg: Value,
+    code: String,
+    msg: String,
+}
+
+struct OpResponse {
+    code: String,
+    msg: String,
+    data: Vec<Value>,
+}
+
+struct State {
+    credentials: Credentials,
+    login_window: Duration,
+    rejections: Vec<Rejection>,
+    op_responses: HashMap<String, OpResponse>,
+    connections: HashMap<u64, Connection>,
+    last_conn: u64,
+    last_ord_id: u64,
+    transcript: Vec<RecordedFrame>,
+}
+
+impl MockWsServer {
+    /// Bind `127.0.0.1` on a free port and start accepting connections.
+    ///
+    /// `credentials` are the ones a client must log in with.
+    pub async fn start(credentials: Credentials) -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| OkxError::WebSocket(e.to_string()))?;
+        let addr = listener
+            .local_addr()
+            .map_err(|e| OkxError::WebSocket(e.to_string()))?;
+
+        let state = Arc::new(Mutex::new(State {
+            credentials,
+            login_window: DEFAULT_LOGIN_WINDOW,
+            rejections: Vec::new(),
+            op_responses: HashMap::new(),
+            connections: HashMap::new(),
+            last_conn: 0,
+            last_ord_id: 0,
+            transcript: Vec::new(),
+        }));
+
+        let accept_state = Arc::clone(&state);
+        let accept_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(serve(stream, Arc::clone(&accept_state)));
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            accept_task,
+        })
+    }
+
+    /// URL of the given endpoint.
+    #[must_use]
+    pub fn url(&self, conn_type: ConnectionType) -> String {
+        let path = match conn_type {
+            ConnectionType::Public => "public",
+            ConnectionType::Private => "private",
+            ConnectionType::Business => "business",
+        };
+        format!("ws://{}/ws/v5/{path}", self.addr)
+    }
+
+    /// A [`Config`] with the server credentials and all WebSocket URLs pointed
+    /// at this server.
+    #[must_use]
+    pub fn config(&self) -> Config {
+        Config::new(self.lock().credentials.clone())
+            .with_ws_public_url(self.url(ConnectionType::Public))
+            .with_ws_private_url(self.url(ConnectionType::Private))
+            .with_ws_business_url(self.url(ConnectionType::Business))
+    }
+
+    /// Set the accepted distance between the login timestamp and the server
+    /// clock (default 30 seconds).
+    pub fn set_login_window(&self, window: Duration) {
+        self.lock().login_window = window;
+    }
+
+    /// Reject subscriptions whose arg contains every field of `arg`.
+    ///
+    /// `json!({"channel": "tickers"})` rejects all tickers subscriptions;
+    /// the client receives an `error` event with `code` and `msg`.
+    pub fn reject(&self, arg: Value, code: &str, msg: &str) {
+        self.lock().rejections.push(Rejection {
+            arg,
+            code: code.to_string(),
+            msg: msg.to_string(),
+        });
+    }
+
+    /// Answer every later `op` request with this code, message and data.
+    pub fn respond_to(&self, op: &str, code: &str, msg: &str, data: Vec<Value>) {
+        self.lock().op_responses.insert(
+            op.to_string(),
+            OpResponse {
+                code: code.to_string(),
+                msg: msg.to_string(),
+                data,
+            },
+        );
+    }
+
+    /// Number of open connections.
+    #[must_use]
+    pub fn connection_count(&self) -> usize {
+        self.lock().connections.len()
+    }
+
+    /// Subscription args of every open connection.
+    #[must_use]
+    pub fn subscriptions(&self) -> Vec<Value> {
+        self.lock()
+            .connections
+            .values()
+            .flat_map(|conn| conn.subscriptions.iter().cloned())
+            .collect()
+    }
+
+    /// Wait until the open connections hold at least `count` subscriptions.
+    ///
+    /// Returns `false` if that does not happen within `wait`.
+    pub async fn wait_for_subscriptions(&self, count: usize, wait: Duration) -> bool {
+        let deadline = Instant::now() + wait;
+        loop {
+            if self.subscriptions().len() >= count {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// Push `data` to every connection subscribed to `arg`.
+    ///
+    /// A subscription matches when `arg` contains all of its fields, so a push
+    /// may carry extra fields such as `uid`. Returns the number of connections
+    /// reached.
+    pub fn push(&self, arg: &Value, data: Vec<Value>) -> usize {
+        let text = json!({"arg": arg, "data": data}).to_string();
+        let state = self.lock();
+        state
+            .connections
+            .values()
+            .filter(|conn| conn.subscriptions.iter().any(|sub| arg_contains(arg, sub)))
+            .filter(|conn| conn.tx.send(Command::Send(text.clone())).is_ok())
+            .count()
+    }
+
+    /// Send a raw text frame to every open connection.
+    ///
+    /// Returns the number of connections reached.
+    pub fn push_raw(&self, text: &str) -> usize {
+        self.broadcast(|| Command::Send(text.to_string()))
+    }
+
+    /// Drop every open connection without a close frame, as a network failure
+    /// would. Returns the number of dropped connections.
+    pub fn drop_connections(&self) -> usize {
+        self.broadcast(|| Command::Drop)
+    }
+
+    /// Every text frame exchanged so far, in order.
+    ///
+    /// Frames serialize with serde, so a session can be stored (for example as
+    /// JSON lines) and replayed later.
+    #[must_use]
+    pub fn transcript(&self) -> Vec<RecordedFrame> {
+        self.lock().transcript.clone()
+    }
+
+    /// Replay the server side of a recorded session.
+    ///
+    /// Sends the text of every [`Direction::Outbound`] frame to each open
+    /// connection, in order; inbound frames are skipped. Returns the number
+    /// of frames replayed per connection.
+    pub fn replay(&self, frames: &[RecordedFrame]) -> usize {
+        let outbound: Vec<&str> = frames
+            .iter()
+            .filter(|frame| frame.direction == Direction::Outbound)
+            .map(|frame| frame.text.as_str())
+            .collect();
+        let state = self.lock();
+        for conn in state.connections.values() {
+            for text in &outbound {
+                let _ = conn.tx.send(Command::Send((*text).to_string()));
+            }
+        }
+        outbound.len()
+    }
+
+    fn broadcast(&self, command: impl Fn() -> Command) -> usize {
+        self.lock()
+            .connections
+            .values()
+            .filter(|conn| conn.tx.send(command()).is_ok())
+            .count()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        lock(&self.state)
+    }
+}
+
+impl Drop for MockWsServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        self.drop_connections();
+    }
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Serve one connection until the client leaves or the test drops it.
+async fn serve(stream: TcpStream, state: Arc<Mutex<State>>) {
+    let mut path = String::new();
+    // 错误类型由 tungstenite 的回调签名决定
+    #[allow(clippy::result_large_err)]
+    let callback = |request: &Request, response: Response| {
+        path = request.uri().path().to_string();
+        Ok(response)
+    };
+    let Ok(ws) = accept_hdr_async(stream, callback).await else {
+        return;
+    };
+
+    let endpoint = match path.as_str() {
+        "/ws/v5/public" => Some(ConnectionType::Public),
+        "/ws/v5/private" => Some(ConnectionType::Private),
+        "/ws/v5/business" => Some(ConnectionType::Business),
+        _ => None,
+    };
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let id = lock(&state).register(endpoint, tx);
+    debug!("Mock WebSocket connection {id} opened on {path}");
+
+    let (mut sink, mut stream) = ws.split();
+    loop {
+        let replies = tokio::select! {
+            command = rx.recv() => match command {
+                Some(Command::Send(text)) => vec![text],
+                Some(Command::Drop) | None => break,
+            },
+            frame = stream.next() => match frame {
+                Some(Ok(Message::Text(text))) => lock(&state).handle(id, text.as_str()),
+                Some(Ok(Message::Ping(data))) => {
+                    if sink.send(Message::Pong(data)).await.is_err() {
+                        break;
+                    }
+                    continue;
+                }
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        let mut closed = false;
+        for text in replies {
+            lock(&state).record(id, Direction::Outbound, &text);
+            if sink.send(Message::Text(text.into())).await.is_err() {
+                closed = true;
+                break;
+            }
+        }
+        if closed {
+            break;
+        }
+    }
+
+    lock(&state).connections.remove(&id);
+    debug!("Mock WebSocket connection {id} closed");
+}
+
+impl State {
+    fn register(
+        &mut self,
+        endpoint: Option<ConnectionType>,
+        tx: mpsc::UnboundedSender<Command>,
+    ) -> u64 {
+        self.last_conn += 1;
+        self.connections.insert(
+            self.last_conn,
+            Connection {
+                endpoint,
+                logged_in: false,
+                subscriptions: Vec::new(),
+                tx,
+            },
+        );
+        self.last_conn
+    }
+
+    fn record(&mut self, conn: u64, direction: Direction, text: &str) {
+        self.transcript.push(RecordedFrame {
+            conn,
+            direction,
+            text: text.to_string(),
+        });
+    }
+
+    /// Answer one client frame; returns the frames to send back.
+    fn handle(&mut self, id: u64, text: &str) -> Vec<String> {
+        self.record(id, Direction::Inbound, text);
+        if text == "ping" {
+            return vec!["pong".to_string()];
+        }
+
+        let conn_id = conn_id(id);
+        let Ok(request) = serde_json::from_str::<Value>(text) else {
+            return vec![error_event(None, "60012", "Invalid request", &conn_id)];
+        };
+        let args = request["args"].as_array().cloned().unwrap_or_default();
+        match request["op"].as_str() {
+            Some("login") => vec![self.login(id, args.first())],
+            Some(op @ ("subscribe" | "unsubscribe")) => args
+                .iter()
+                .map(|arg| self.subscription(id, op, arg))
+                .collect(),
+            Some(op) => match request["id"].as_str() {
+                Some(request_id) => vec![self.op(id, request_id, op, args)],
+                None => vec![error_event(None, "60012", "Invalid request", &conn_id)],
+            },
+            None => vec![error_event(None, "60012", "Invalid request", &conn_id)],
+        }
+    }
+
+    fn login(&mut self, id: u64, arg: Option<&Value>) -> String {
+        let conn_id = conn_id(id);
+        match self.verify_login(arg.unwrap_or(&Value::Null)) {
+            Ok(()) => {
+                if let Some(conn) = self.connections.get_mut(&id) {
+                    conn.logged_in = true;
+                }
+                json!({"event": "login", "code": "0", "msg": "", "connId": conn_id}).to_string()
+            }
+            Err((code, msg)) => error_event(None, code, msg, &conn_id),
+        }
+    }
+
+    /// 按 OKX 的校验顺序检查登录参数，签名通过 `Signer` 重新计算后比对。
+    fn verify_login(&self, arg: &Value) -> std::result::Result<(), (&'static str, &'static str)> {
+        let field = |key: &str| arg.get(key).and_then(Value::as_str).unwrap_or_default();
+        if field("apiKey") != self.credentials.api_key() {
+            return Err(("60005", "Invalid apiKey"));
+        }
+        if field("passphrase") != self.credentials.passphrase() {
+            return Err(("60024", "Wrong passphrase"));
+        }
+        let timestamp = field("timestamp");
+        let Ok(seconds) = timestamp.parse::<u64>() else {
+            return Err(("60004", "Invalid timestamp"));
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        if now.abs_diff(seconds) > self.login_window.as_secs() {
+            return Err(("60006", "Timestamp request expired"));
+        }
+        let (_, _, _, sign) = Signer::new(self.credentials.clone())
+            .generate_ws_login_params_with_timestamp(timestamp);
+        if field("sign") != sign {
+            return Err(("60007", "Invalid sign"));
+        }
+        Ok(())
+    }
+
+    fn subscription(&mut self, id: u64, op: &str, arg: &Value) -> String {
+        let conn_id = conn_id(id);
+        if op == "subscribe" {
+            if let Some((code, msg)) = self.check_subscription(id, arg) {
+                return error_event(Some(arg), &code, &msg, &conn_id);
+            }
+        }
+
+        if let Some(conn) = self.connections.get_mut(&id) {
+            conn.subscriptions.retain(|sub| sub != arg);
+            if op == "subscribe" {
+                conn.subscriptions.push(arg.clone());
+            }
+        }
+        json!({"event": op, "arg": arg, "connId": conn_id}).to_string()
+    }
+
+    /// 返回订阅应被拒绝时的错误码与信息。
+    fn check_subscription(&self, id: u64, arg: &Value) -> Option<(String, String)> {
+        if let Some(rejection) = self.rejections.iter().find(|r| arg_contains(arg, &r.arg)) {
+            return Some((rejection.code.clone(), rejection.msg.clone()));
+        }
+
+        let conn = self.connections.get(&id)?;
+        let unknown = || {
+            Some((
+                "60018".to_string(),
+                format!("Wrong URL or channel:{arg} doesn't exist"),
+            ))
+        };
+        let Ok(channel) = serde_json::from_value::<Channel>(arg.clone()) else {
+            return unknown();
+        };
+        if conn
+            .endpoint
+            .is_some_and(|endpoint| endpoint != channel.endpoint())
+        {
+            return unknown();
+        }
+        if channel.is_private() && !conn.logged_in {
+            return Some(("60011".to_string(), "Please log in".to_string()));
+        }
+        None
+    }
+
+    fn op(&mut self, id: u64, request_id: &str, op: &str, args: Vec<Value>) -> String {
+        let logged_in = self.connections.get(&id).is_some_and(|conn| conn.logged_in);
+        let (code, msg, data) = if !logged_in {
+            ("60011".to_string(), "Please log in".to_string(), Vec::new())
+        } else if let Some(response) = self.op_responses.get(op) {
+            (
+                response.code.clone(),
+                response.msg.clone(),
+                response.data.clone(),
+            )
+        } else if op.ends_with("mass-cancel") {
+            (String::new(), String::new(), vec![json!({"result": true})])
+        } else {
+            let data = args.into_iter().map(|arg| self.echo_item(arg)).collect();
+            (String::new(), String::new(), data)
+        };
+        let code = if code.is_empty() {
+            "0".to_string()
+        } else {
+            code
+        };
+        json!({"id": request_id, "op": op, "code": code, "msg": msg, "data": data}).to_string()
+    }
+
+    /// 默认响应：回显请求参数并补齐 `ordId`、`clOrdId`、`sCode`、`sMsg`。
+    fn echo_item(&mut self, arg: Value) -> Value {
+        let mut item = match arg {
+            Value::Object(map) => map,
+            _ => Map::new(),
+        };
+        if !item.contains_key("ordId") {
+            self.last_ord_id += 1;
+            item.insert("ordId".to_string(), self.last_ord_id.to_string().into());
+        }
+        item.entry("clOrdId").or_insert_with(|| "".into());
+        item.insert("sCode".to_string(), "0".into());
+        item.insert("sMsg".to_string(), "".into());
+        Value::Object(item)
+    }
+}
+
+fn conn_id(id: u64) -> String {
+    format!("{id:08x}")
+}
+
+fn error_event(arg: Option<&Value>, code: &str, msg: &str, conn_id: &str) -> String {
+    let mut event = json!({"event": "error", "code": code, "msg": msg, "connId": conn_id});
+    if let Some(arg) = arg {
+        event["arg"] = arg.clone();
+    }
+    event.to_string()
+}
+
+/// `actual` 需包含 `expected` 的全部字段。
+fn arg_contains(actual: &Value, expected: &Value) -> bool {
+    match (actual.as_object(), expected.as_object()) {
+        (Some(actual), Some(expected)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key) == Some(value)),
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use futures_util::Stream;
+    use okx_core::types::{Bar, PlaceOrderRequest};
+    use tokio::time::timeout;
+
+    use crate::message::WsMessage;
+    use crate::reconnect::{ConnectionState, ReconnectConfig, ReconnectingWsClient};
+    use crate::trade::WsTradeApi;
+    use crate::WsClient;
+
+    fn credentials() -> Credentials {
+        Credentials::new("key", "secret", "pass")
+    }
+
+    fn tickers(inst_id: &str) -> Channel {
+        Channel::Tickers {
+            inst_id: inst_id.to_string(),
+        }
+    }
+
+    async fn start() -> Option<MockWsServer> {
+        match MockWsServer::start(credentials()).await {
+            Ok(server) => Some(server),
+            Err(e) => {
+                eprintln!("无法启动本地模拟服务端，跳过用例: {e}");
+                None
+            }
+        }
+    }
+
+    async fn next_data(client: &mut (impl Stream<Item = Result<WsMessage>> + Unpin)) -> WsMessage {
+        loop {
+            let msg = timeout(Duration::from_secs(5), client.next())
+                .await
+                .expect("等待消息超时")
+                .expect("流不应结束")
+                .expect("消息应为 Ok");
+            if matches!(msg, WsMessage::Data { .. }) {
+                return msg;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn login_is_verified_with_signer() {
+        let Some(server) = start().await else {
+            return;
+        };
+
+        let mut client = WsClient::connect_private(&server.config())
+            .await
+            .expect("连接应成功");
+        client.login().await.expect("正确凭证应登录成功");
+
+        let wrong = Config::new(Credentials::new("key", "other", "pass"))
+            .with_ws_private_url(server.url(ConnectionType::Private));
+        let mut client = WsClient::connect_private(&wrong).await.expect("连接应成功");
+        let err = client.login().await.expect_err("错误签名应被拒绝");
+        assert!(err.to_string().contains("60007"), "{err}");
+
+        let mut client = WsClient::connect_private(&server.config())
+            .await
+            .expect("连接应成功");
+        let err = client
+            .login_with_timestamp("1000")
+            .await
+            .expect_err("过期时间戳应被拒绝");
+        assert!(err.to_string().contains("60006"), "{err}");
+
+        // 未登录的私有连接订阅私有频道会收到 60011
+        let mut state = lock(&server.state);
+        let conn = state
+            .connections
+            .iter()
+            .find(|(_, conn)| !conn.logged_in && conn.endpoint == Some(ConnectionType::Private))
+            .map(|(id, _)| *id)
+            .expect("应有未登录的私有连接");
+        let reply = state.subscription(conn, "subscribe", &json!({"channel": "account"}));
+        assert!(reply.contains("60011"), "{reply}");
+    }
+
+    #[tokio::test]
+    async fn subscriptions_are_acked_rejected_and_pushed() {
+        let Some(server) = start().await else {
+            return;
+        };
+        server.reject(
+            json!({"channel": "tickers", "instId": "ETH-USDT"}),
+            "60018",
+            "Wrong URL or channel doesn't exist",
+        );
+
+        let mut client = WsClient::connect_public(&server.config())
+            .await
+            .expect("连接应成功");
+        let results = client
+            .subscribe_confirmed(vec![
+                tickers("BTC-USDT"),
+                tickers("ETH-USDT"),
+                Channel::Candle {
+                    bar: Bar::M1,
+                    inst_id: "BTC-USDT".to_string(),
+                },
+            ])
+            .await;
+        // K 线属于业务端点，客户端在发送前即拒绝
+        assert!(matches!(results, Err(OkxError::InvalidParameter(_))));
+
+        let results = client
+            .subscribe_confirmed(vec![tickers("BTC-USDT"), tickers("ETH-USDT")])
+            .await
+            .expect("应收到确认");
+        assert!(results[0].1.is_ok());
+        match &results[1].1 {
+            Err(OkxError::Api { code, .. }) => assert_eq!(code, "60018"),
+            other => panic!("应被拒绝，实际为 {other:?}"),
+        }
+        assert_eq!(server.subscriptions().len(), 1);
+
+        let arg = json!({"channel": "tickers", "instId": "BTC-USDT"});
+        assert_eq!(server.push(&arg, vec![json!({"last": "42000"})]), 1);
+        assert_eq!(
+            server.push(&json!({"channel": "tickers", "instId": "ETH-USDT"}), vec![]),
+            0
+        );
+        let WsMessage::Data { channel, data, .. } = next_data(&mut client).await else {
+            unreachable!();
+        };
+        assert_eq!(channel, "tickers");
+        assert_eq!(data[0]["last"], "42000");
+
+        // 私有频道不属于公有端点
+        let mut state = lock(&server.state);
+        let conn = *state.connections.keys().next().expect("应有连接");
+        let reply = state.subscription(conn, "subscribe", &json!({"channel": "account"}));
+        assert!(reply.contains("60018"), "{reply}");
+    }
+
+    #[tokio::test]
+    async fn ops_are_answered_after_login() {
+        let Some(server) = start().await else {
+            return;
+        };
+        let mut client = WsClient::connect_private(&server.config())
+            .await
+            .expect("连接应成功");
+
+        let request: PlaceOrderRequest = serde_json::from_value(json!({
+            "instId": "BTC-USDT",
+            "tdMode": "cash",
+            "side": "buy",
+            "ordType": "market",
+            "sz": "1",
+            "clOrdId": "c1",
+        }))
+        .expect("下单请求应可反序列化");
+        let placed = client
+            .place_order(request.clone())
+            .await
+            .expect("默认回显应成功");
+        assert_eq!(placed.cl_ord_id, "c1");
+        assert_eq!(placed.ord_id, "1");
+
+        server.respond_to(
+            "order",
+            "1",
+            "",
+            vec![json!({"ordId": "", "clOrdId": "c1", "sCode": "51008", "sMsg": "Insufficient balance"})],
+        );
+        match client.place_order(request).await {
+            Err(OkxError::Api { code, .. }) => assert_eq!(code, "51008"),
+            other => panic!("应返回脚本错误，实际为 {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn dropped_connections_reconnect_and_sessions_replay() {
+        let Some(server) = start().await else {
+            return;
+        };
+        let reconnect = ReconnectConfig::default()
+            .with_initial_delay(Duration::from_millis(10))
+            .with_max_attempts(5);
+        let mut client =
+            ReconnectingWsClient::connect(server.config(), ConnectionType::Public, reconnect)
+                .await
+                .expect("连接应成功");
+        client
+            .subscribe(vec![tickers("BTC-USDT")])
+            .await
+            .expect("订阅应成功");
+        assert!(
+            server
+                .wait_for_subscriptions(1, Duration::from_secs(5))
+                .await
+        );
+
+        let arg = json!({"channel": "tickers", "instId": "BTC-USDT"});
+        server.push(&arg, vec![json!({"last": "1"})]);
+        next_data(&mut client).await;
+        let session = server.transcript();
+        assert!(
+            session
+                .iter()
+                .any(|frame| frame.direction == Direction::Inbound
+                    && frame.text.contains("subscribe"))
+        );
+
+        assert_eq!(server.drop_connections(), 1);
+        let restored = timeout(Duration::from_secs(5), async {
+            while let Some(msg) = client.next().await {
+                if let Ok(WsMessage::SubscriptionsRestored { count }) = msg {
+                    return count;
+                }
+            }
+            0
+        })
+        .await
+        .expect("应在超时前恢复订阅");
+        assert_eq!(restored, 1);
+        assert!(
+            server
+                .wait_for_subscriptions(1, Duration::from_secs(5))
+                .await
+        );
+
+        // 回放录制的会话：新连接应再次收到同一条推送
+        assert!(server.replay(&session) >= 2);
+        let WsMessage::Data { data, .. } = next_data(&mut client).await else {
+            unreachable!();
+        };
+        assert_eq!(data[0]["last"], "1");
+    }
+
+    #[tokio::test]
+    async fn channels_unsubscribed_during_reconnect_are_not_restored() {
+        let Some(server) = start().await else {
+            return;
+        };
+        let reconnect = ReconnectConfig::default()
+            .with_initial_delay(Duration::from_millis(10))
+            .with_max_attempts(5);
+        let mut client =
+            ReconnectingWsClient::connect(server.config(), ConnectionType::Public, reconnect)
+                .await
+                .expect("连接应成功");
+        client
+            .subscribe(vec![tickers("BTC-USDT"), tickers("ETH-USDT")])
+            .await
+            .expect("订阅应成功");
+        assert!(
+            server
+                .wait_for_subscriptions(2, Duration::from_secs(5))
+                .await
+        );
+
+        // 重连已开始（已捕获两个频道）后再取消其中一个
+        assert_eq!(server.drop_connections(), 1);
+        timeout(Duration::from_secs(5), async {
+            loop {
+                let msg = client
+                    .next()
+                    .await
+                    .expect("流不应结束")
+                    .expect("消息应为 Ok");
+                if matches!(msg, WsMessage::Reconnecting { .. }) {
+                    break;
+                }
+            }
+        })
+        .await
+        .expect("应开始重连");
+        client
+            .unsubscribe(vec![tickers("ETH-USDT")])
+            .await
+            .expect("取消订阅应成功");
+
+        let restored = timeout(Duration::from_secs(5), async {
+            loop {
+                let msg = client
+                    .next()
+                    .await
+                    .expect("流不应结束")
+                    .expect("消息应为 Ok");
+                if let WsMessage::SubscriptionsRestored { count } = msg {
+                    return count;
+                }
+            }
+        })
+        .await
+        .expect("应在超时前恢复订阅");
+        assert_eq!(restored, 1);
+        // 新连接先恢复两个频道，再取消重连期间退订的那个
+        let expected = vec![json!({"channel": "tickers", "instId": "BTC-USDT"})];
+        timeout(Duration::from_secs(5), async {
+            while !server.transcript().iter().any(|frame| {
+                frame.direction == Direction::Inbound && frame.text.contains("unsubscribe")
+            }) || server.subscriptions() != expected
+            {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("新连接上只应保留 BTC-USDT");
+    }
+
+    #[tokio::test]
+    async fn service_upgrade_notice_switches_connection_without_gap() {
+        let Some(server) = start().await else {
+            return;
+        };
+        let mut client = ReconnectingWsClient::connect(
+            server.config(),
+            ConnectionType::Public,
+            ReconnectConfig::default(),
+        )
+        .await
+        .expect("连接应成功");
+        client
+            .subscribe(vec![tickers("BTC-USDT")])
+            .await
+            .expect("订阅应成功");
+        assert!(
+            server
+                .wait_for_subscriptions(1, Duration::from_secs(5))
+                .await
+        );
+
+        let notice = json!({
+            "event": "notice",
+            "code": "64008",
+            "msg": "The connection will soon be closed for a service upgrade. Please reconnect.",
+            "connId": "a4d3ae55"
+        });
+        assert_eq!(server.push_raw(&notice.to_string()), 1);
+        timeout(Duration::from_secs(5), async {
+            loop {
+                let msg = client
+                    .next()
+                    .await
+                    .expect("流不应结束")
+                    .expect("消息应为 Ok");
+                if msg.is_service_upgrade_notice() {
+                    break;
+                }
+            }
+        })
+        .await
+        .expect("等待通知超时");
+
+        // 替换连接就绪前旧连接继续推送
+        let arg = json!({"channel": "tickers", "instId": "BTC-USDT"});
+        assert!(server.push(&arg, vec![json!({"last": "1"})]) >= 1);
+        let (mut data, mut restored) = (false, None);
+        timeout(Duration::from_secs(5), async {
+            while !data || restored.is_none() {
+                match client
+                    .next()
+                    .await
+                    .expect("流不应结束")
+                    .expect("消息应为 Ok")
+                {
+                    WsMessage::Data { .. } => data = true,
+                    WsMessage::SubscriptionsRestored { count } => restored = Some(count),
+                    WsMessage::Reconnecting { .. } => panic!("通知切换不应进入断线重连"),
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .expect("应在超时前完成切换");
+        assert_eq!(restored, Some(1));
+        assert_eq!(client.state(), ConnectionState::Connected);
+
+        // 旧连接被丢弃，推送改由新连接送达
+        timeout(Duration::from_secs(5), async {
+            while server.connection_count() > 1 {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("旧连接应被关闭");
+        assert_eq!(server.push(&arg, vec![json!({"last": "2"})]), 1);
+        let WsMessage::Data { data, .. } = next_data(&mut client).await else {
+            unreachable!();
+        };
+        assert_eq!(data[0]["last"], "2");
+    }
+
+    #[tokio::test]
+    async fn subscription_changes_during_switch_reach_new_connection() {
+        let Some(server) = start().await else {
+            return;
+        };
+        let mut client = ReconnectingWsClient::connect(
+            server.config(),
+            ConnectionType::Public,
+            ReconnectConfig::default(),
+        )
+        .await
+        .expect("连接应成功");
+        client
+            .subscribe(vec![tickers("BTC-USDT"), tickers("SOL-USDT")])
+            .await
+            .expect("订阅应成功");
+        assert!(
+            server
+                .wait_for_subscriptions(2, Duration::from_secs(5))
+                .await
+        );
+
+        server.push_raw(&json!({"event": "notice", "code": "64008", "msg": ""}).to_string());
+        timeout(Duration::from_secs(5), async {
+            loop {
+                let msg = client
+                    .next()
+                    .await
+                    .expect("流不应结束")
+                    .expect("消息应为 Ok");
+                if msg.is_service_upgrade_notice() {
+                    break;
+                }
+            }
+        })
+        .await
+        .expect("等待通知超时");
+
+        // 替换连接已捕获 BTC/SOL，切换完成前再调整订阅
+        client
+            .unsubscribe(vec![tickers("SOL-USDT")])
+            .await
+            .expect("取消订阅应成功");
+        client
+            .subscribe(vec![tickers("ETH-USDT")])
+            .await
+            .expect("订阅应成功");
+
+        let restored = timeout(Duration::from_secs(5), async {
+            loop {
+                match client
+                    .next()
+                    .await
+                    .expect("流不应结束")
+                    .expect("消息应为 Ok")
+                {
+                    WsMessage::SubscriptionsRestored { count } => return count,
+                    WsMessage::Reconnecting { .. } => panic!("通知切换不应进入断线重连"),
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .expect("应在超时前完成切换");
+        assert_eq!(restored, 2);
+
+        timeout(Duration::from_secs(5), async {
+            loop {
+                let mut inst_ids: Vec<String> = server
+                    .subscriptions()
+                    .iter()
+                    .filter_map(|arg| arg["instId"].as_str().map(String::from))
+                    .collect();
+                inst_ids.sort();
+                if server.connection_count() == 1 && inst_ids == ["BTC-USDT", "ETH-USDT"] {
+                    break;
+                }
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("新连接上应只有 BTC-USDT 与 ETH-USDT");
+    }
+
+    #[tokio::test]
+    async fn manual_reconnect_restores_subscriptions_and_queues_events() {
+        let Some(server) = start().await else {
+            return;
+        };
+        let reconnect = ReconnectConfig::default().with_initial_delay(Duration::from_millis(10));
+        let mut client =
+            ReconnectingWsClient::connect(server.config(), ConnectionType::Public, reconnect)
+                .await
+                .expect("连接应成功");
+        client
+            .subscribe(vec![tickers("BTC-USDT")])
+            .await
+            .expect("订阅应成功");
+        assert!(
+            server
+                .wait_for_subscriptions(1, Duration::from_secs(5))
+                .await
+        );
+
+        timeout(Duration::from_secs(5), client.reconnect())
+            .await
+            .expect("手动重连超时")
+            .expect("手动重连应成功");
+        assert_eq!(client.state(), ConnectionState::Connected);
+
+        // 生命周期事件与流驱动的重连一致
+        let mut events = Vec::new();
+        while events.len() < 3 {
+            let msg = timeout(Duration::from_secs(5), client.next())
+                .await
+                .expect("等待事件超时")
+                .expect("流不应结束")
+                .expect("消息应为 Ok");
+            if !matches!(msg, WsMessage::Event { .. }) {
+                events.push(msg);
+            }
+        }
+        assert!(matches!(events[0], WsMessage::Reconnecting { attempt: 1 }));
+        assert!(matches!(events[1], WsMessage::Reconnected));
+        assert!(matches!(
+            events[2],
+            WsMessage::SubscriptionsRestored { count: 1 }
+        ));
+
+        let arg = json!({"channel": "tickers", "instId": "BTC-USDT"});
+        timeout(Duration::from_secs(5), async {
+            while server.connection_count() != 1 || server.subscriptions().len() != 1 {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("旧连接应关闭且新连接应恢复订阅");
+        assert_eq!(server.push(&arg, vec![json!({"last": "3"})]), 1);
+        let WsMessage::Data { data, .. } = next_data(&mut client).await else {
+            unreachable!();
+        };
+        assert_eq!(data[0]["last"], "3");
+    }
+}
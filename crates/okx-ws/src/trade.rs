@@ -0,0 +1,332 @@
+//! Order placement, amend and cancel over the private WebSocket.
+//!
+//! Every op carries a request `id`; the matching response is awaited with the
+//! config timeout while unrelated pushes stay queued on the stream. Spread
+//! ops (`sprd-*`) are sent on the business WebSocket instead.
+//!
+//! Source: OKX API v5 WebSocket Trade API
+//! - <https://www.okx.com/docs-v5/en/#order-book-trading-trade-ws-place-order>
+//! - <https://www.okx.com/docs-v5/en/#spread-trading-websocket-trade-api>
+
+use std::future::Future;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use okx_core::types::{
+    AmendOrderRequest, AmendOrderResponse, ApiResponse, BatchItem, BatchResult, CancelOrderRequest,
+    CancelOrderResponse, MassCancelRequest, MassCancelResponse, PlaceOrderRequest,
+    PlaceOrderResponse,
+};
+use okx_core::{OkxError, Result};
+
+use crate::client::WsClient;
+use crate::reconnect::ReconnectingWsClient;
+
+/// Trade API over the private WebSocket connection.
+///
+/// The `spread_*` ops must be sent on a business connection instead; sending
+/// an op on the wrong endpoint fails with [`OkxError::InvalidParameter`].
+/// Logs in automatically before the first op. Each call resolves to the
+/// response with the same request `id`, or [`OkxError::Timeout`] after
+/// `Config::timeout_secs`.
+pub trait WsTradeApi: Send {
+    /// Send a raw op with the given args and wait for its response.
+    fn send_op(
+        &mut self,
+        op: &str,
+        args: Vec<Value>,
+    ) -> impl Future<Output = Result<ApiResponse<Value>>> + Send;
+
+    /// Place a new order.
+    ///
+    /// - Op: `order`
+    /// - Rate limit: 60 requests per 2 seconds
+    fn place_order(
+        &mut self,
+        request: PlaceOrderRequest,
+    ) -> impl Future<Output = Result<PlaceOrderResponse>> + Send {
+        async move { single(self.send_op("order", vec![to_arg(&request)?]).await?) }
+    }
+
+    /// Place multiple orders (up to 20).
+    ///
+    /// Partial failures are returned as a [`BatchResult`] instead of an error.
+    ///
+    /// - Op: `batch-orders`
+    /// - Rate limit: 300 orders per 2 seconds
+    fn place_batch_orders(
+        &mut self,
+        requests: Vec<PlaceOrderRequest>,
+    ) -> impl Future<Output = Result<BatchResult<PlaceOrderResponse>>> + Send {
+        async move { batch(self.send_op("batch-orders", to_args(&requests)?).await?) }
+    }
+
+    /// Cancel an order.
+    ///
+    /// - Op: `cancel-order`
+    /// - Rate limit: 60 requests per 2 seconds
+    fn cancel_order(
+        &mut self,
+        request: CancelOrderRequest,
+    ) -> impl Future<Output = Result<CancelOrderResponse>> + Send {
+        async move {
+            single(
+                self.send_op("cancel-order", vec![to_arg(&request)?])
+                    .await?,
+            )
+        }
+    }
+
+    /// Cancel multiple orders (up to 20).
+    ///
+    /// - Op: `batch-cancel-orders`
+    /// - Rate limit: 300 orders per 2 seconds
+    fn cancel_batch_orders(
+        &mut self,
+        requests: Vec<CancelOrderRequest>,
+    ) -> impl Future<Output = Result<BatchResult<CancelOrderResponse>>> + Send {
+        async move {
+            batch(
+                self.send_op("batch-cancel-orders", to_args(&requests)?)
+                    .await?,
+            )
+        }
+    }
+
+    /// Amend an order.
+    ///
+    /// - Op: `amend-order`
+    /// - Rate limit: 60 requests per 2 seconds
+    fn amend_order(
+        &mut self,
+        request: AmendOrderRequest,
+    ) -> impl Future<Output = Result<AmendOrderResponse>> + Send {
+        async move { single(self.send_op("amend-order", vec![to_arg(&request)?]).await?) }
+    }
+
+    /// Amend multiple orders (up to 20).
+    ///
+    /// - Op: `batch-amend-orders`
+    /// - Rate limit: 300 orders per 2 seconds
+    fn amend_batch_orders(
+        &mut self,
+        requests: Vec<AmendOrderRequest>,
+    ) -> impl Future<Output = Result<BatchResult<AmendOrderResponse>>> + Send {
+        async move {
+            batch(
+                self.send_op("batch-amend-orders", to_args(&requests)?)
+                    .await?,
+            )
+        }
+    }
+
+    /// Cancel all pending orders of an instrument family (options only).
+    ///
+    /// - Op: `mass-cancel`
+    /// - Rate limit: 5 requests per 2 seconds
+    fn mass_cancel(
+        &mut self,
+        request: MassCancelRequest,
+    ) -> impl Future<Output = Result<MassCancelResponse>> + Send {
+        async move {
+            let response = self.send_op("mass-cancel", vec![to_arg(&request)?]).await?;
+            if !response.is_success() {
+                return Err(OkxError::api(response.code, response.msg));
+            }
+            first(response.data)
+        }
+    }
+
+    /// Place a spread order (business connection).
+    ///
+    /// Takes the same JSON body as `SpreadApi::spread_place_order` over REST.
+    ///
+    /// - Op: `sprd-order`
+    /// - Rate limit: 20 requests per 2 seconds
+    fn spread_place_order(&mut self, request: Value) -> impl Future<Output = Result<Value>> + Send {
+        async move { spread(self.send_op("sprd-order", vec![request]).await?) }
+    }
+
+    /// Amend a spread order (business connection).
+    ///
+    /// - Op: `sprd-amend-order`
+    /// - Rate limit: 20 requests per 2 seconds
+    fn spread_amend_order(&mut self, request: Value) -> impl Future<Output = Result<Value>> + Send {
+        async move { spread(self.send_op("sprd-amend-order", vec![request]).await?) }
+    }
+
+    /// Cancel a spread order (business connection).
+    ///
+    /// - Op: `sprd-cancel-order`
+    /// - Rate limit: 20 requests per 2 seconds
+    fn spread_cancel_order(
+        &mut self,
+        request: Value,
+    ) -> impl Future<Output = Result<Value>> + Send {
+        async move { spread(self.send_op("sprd-cancel-order", vec![request]).await?) }
+    }
+
+    /// Cancel all pending spread orders, optionally of one `sprdId`
+    /// (business connection).
+    ///
+    /// - Op: `sprd-mass-cancel`
+    /// - Rate limit: 5 requests per 2 seconds
+    fn spread_mass_cancel(&mut self, request: Value) -> impl Future<Output = Result<Value>> + Send {
+        async move { spread(self.send_op("sprd-mass-cancel", vec![request]).await?) }
+    }
+}
+
+impl WsTradeApi for WsClient {
+    async fn send_op(&mut self, op: &str, args: Vec<Value>) -> Result<ApiResponse<Value>> {
+        WsClient::send_op(self, op, args).await
+    }
+}
+
+impl WsTradeApi for ReconnectingWsClient {
+    async fn send_op(&mut self, op: &str, args: Vec<Value>) -> Result<ApiResponse<Value>> {
+        ReconnectingWsClient::send_op(self, op, args).await
+    }
+}
+
+fn to_arg<T: Serialize>(request: &T) -> Result<Value> {
+    Ok(serde_json::to_value(request)?)
+}
+
+fn to_args<T: Serialize>(requests: &[T]) -> Result<Vec<Value>> {
+    requests.iter().map(to_arg).collect()
+}
+
+fn decode<T: DeserializeOwned>(response: ApiResponse<Value>) -> Result<ApiResponse<T>> {
+    let data = response
+        .data
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<std::result::Result<_, _>>()?;
+    Ok(ApiResponse {
+        code: response.code,
+        msg: response.msg,
+        data,
+    })
+}
+
+fn first<T: DeserializeOwned>(data: Vec<Value>) -> Result<T> {
+    let item = data
+        .into_iter()
+        .next()
+        .ok_or_else(|| OkxError::WebSocket("Empty op response data".to_string()))?;
+    Ok(serde_json::from_value(item)?)
+}
+
+fn batch<T: BatchItem + DeserializeOwned>(response: ApiResponse<Value>) -> Result<BatchResult<T>> {
+    BatchResult::from_response(decode(response)?)
+}
+
+/// 单笔操作：失败时返回条目自身的 `sCode`/`sMsg`，没有条目时返回顶层错误。
+fn single<T: BatchItem + DeserializeOwned>(response: ApiResponse<Value>) -> Result<T> {
+    let mut result = batch::<T>(response)?;
+    if result.items.is_empty() {
+        return Err(OkxError::WebSocket("Empty op response data".to_string()));
+    }
+    let item = result.items.swap_remove(0);
+    match item.error() {
+        Some(e) => Err(e),
+        None => Ok(item),
+    }
+}
+
+/// 价差操作：失败时优先返回条目的 `sCode`/`sMsg`，否则返回顶层错误。
+fn spread(response: ApiResponse<Value>) -> Result<Value> {
+    let item_error = response.data.first().and_then(|item| {
+        let code = item.get("sCode")?.as_str()?;
+        (code != "0").then(|| {
+            let msg = item.get("sMsg").and_then(Value::as_str).unwrap_or_default();
+            OkxError::api(code, msg)
+        })
+    });
+    if let Some(e) = item_error {
+        return Err(e);
+    }
+    if !response.is_success() {
+        return Err(OkxError::api(response.code, response.msg));
+    }
+    response
+        .data
+        .into_iter()
+        .next()
+        .ok_or_else(|| OkxError::WebSocket("Empty op response data".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(code: &str, data: Value) -> ApiResponse<Value> {
+        ApiResponse {
+            code: code.to_string(),
+            msg: String::new(),
+            data: serde_json::from_value(data).expect("data 应为数组"),
+        }
+    }
+
+    #[test]
+    fn single_returns_item_or_its_error() {
+        let ok = response(
+            "0",
+            json!([{"ordId": "1", "clOrdId": "a", "tag": "", "sCode": "0", "sMsg": ""}]),
+        );
+        let placed: PlaceOrderResponse = single(ok).expect("应成功");
+        assert_eq!(placed.ord_id, "1");
+
+        let failed = response(
+            "1",
+            json!([{"ordId": "", "clOrdId": "a", "tag": "", "sCode": "51008", "sMsg": "Insufficient balance"}]),
+        );
+        match single::<PlaceOrderResponse>(failed) {
+            Err(OkxError::Api { code, .. }) => assert_eq!(code, "51008"),
+            other => panic!("应返回条目错误，实际为 {other:?}"),
+        }
+
+        let rejected = response("60012", json!([]));
+        assert!(single::<CancelOrderResponse>(rejected).is_err());
+    }
+
+    #[test]
+    fn spread_returns_item_or_its_error() {
+        let ok = response(
+            "0",
+            json!([{"ordId": "1", "clOrdId": "a", "tag": "", "sCode": "0", "sMsg": ""}]),
+        );
+        assert_eq!(spread(ok).expect("应成功")["ordId"], "1");
+
+        let failed = response(
+            "1",
+            json!([{"ordId": "", "clOrdId": "a", "sCode": "51008", "sMsg": "Insufficient balance"}]),
+        );
+        match spread(failed) {
+            Err(OkxError::Api { code, .. }) => assert_eq!(code, "51008"),
+            other => panic!("应返回条目错误，实际为 {other:?}"),
+        }
+
+        // sprd-mass-cancel 的响应条目没有 sCode
+        let mass = response("0", json!([{"result": true}]));
+        assert_eq!(spread(mass).expect("应成功")["result"], true);
+        assert!(spread(response("60012", json!([]))).is_err());
+    }
+
+    #[test]
+    fn batch_keeps_partial_failures() {
+        let partial = response(
+            "2",
+            json!([
+                {"ordId": "1", "clOrdId": "a", "reqId": "", "sCode": "0", "sMsg": ""},
+                {"ordId": "", "clOrdId": "b", "reqId": "", "sCode": "51503", "sMsg": "Order does not exist"}
+            ]),
+        );
+        let result: BatchResult<AmendOrderResponse> = batch(partial).expect("部分成功不应报错");
+        assert!(result.is_partial());
+        assert_eq!(result.failed_cl_ord_ids(), vec!["b"]);
+    }
+}
@@ -1,4 +1,5 @@
-use okx_ws::Channel;
+use okx_core::types::Bar;
+use okx_ws::{Channel, ConnectionType};
 
 #[test]
 fn test_public_channel_serialization_and_flags() {
@@ -36,37 +37,43 @@ fn test_additional_channel_names() {
             "books-l2-tbt",
         ),
         (
-            Channel::Candle1m {
+            Channel::Candle {
+                bar: Bar::M1,
                 inst_id: "BTC-USDT".into(),
             },
             "candle1m",
         ),
         (
-            Channel::Candle5m {
+            Channel::Candle {
+                bar: Bar::M5,
                 inst_id: "BTC-USDT".into(),
             },
             "candle5m",
         ),
         (
-            Channel::Candle15m {
+            Channel::Candle {
+                bar: Bar::M15,
                 inst_id: "BTC-USDT".into(),
             },
             "candle15m",
         ),
         (
-            Channel::Candle1H {
+            Channel::Candle {
+                bar: Bar::H1,
                 inst_id: "BTC-USDT".into(),
             },
             "candle1H",
         ),
         (
-            Channel::Candle4H {
+            Channel::Candle {
+                bar: Bar::H4,
                 inst_id: "BTC-USDT".into(),
             },
             "candle4H",
         ),
         (
-            Channel::Candle1D {
+            Channel::Candle {
+                bar: Bar::D1,
                 inst_id: "BTC-USDT".into(),
             },
             "candle1D",
@@ -140,6 +147,80 @@ fn test_balance_and_position_minimal_serialization() {
     assert_eq!(json, "{\"channel\":\"balance_and_position\"}");
 }
 
+#[test]
+fn test_public_market_data_channels_serialization() {
+    let cases = vec![
+        (
+            Channel::OpenInterest {
+                inst_id: "BTC-USDT-SWAP".into(),
+            },
+            "open-interest",
+            serde_json::json!({"channel": "open-interest", "instId": "BTC-USDT-SWAP"}),
+        ),
+        (
+            Channel::PriceLimit {
+                inst_id: "BTC-USDT-SWAP".into(),
+            },
+            "price-limit",
+            serde_json::json!({"channel": "price-limit", "instId": "BTC-USDT-SWAP"}),
+        ),
+        (
+            Channel::LiquidationOrders {
+                inst_type: "SWAP".into(),
+            },
+            "liquidation-orders",
+            serde_json::json!({"channel": "liquidation-orders", "instType": "SWAP"}),
+        ),
+        (
+            Channel::OptSummary {
+                inst_family: "BTC-USD".into(),
+            },
+            "opt-summary",
+            serde_json::json!({"channel": "opt-summary", "instFamily": "BTC-USD"}),
+        ),
+        (
+            Channel::EstimatedPrice {
+                inst_type: "FUTURES".into(),
+                inst_family: Some("BTC-USD".into()),
+                inst_id: None,
+            },
+            "estimated-price",
+            serde_json::json!({"channel": "estimated-price", "instType": "FUTURES", "instFamily": "BTC-USD"}),
+        ),
+        (
+            Channel::Instruments {
+                inst_type: "SPOT".into(),
+            },
+            "instruments",
+            serde_json::json!({"channel": "instruments", "instType": "SPOT"}),
+        ),
+        (
+            Channel::Status,
+            "status",
+            serde_json::json!({"channel": "status"}),
+        ),
+    ];
+
+    for (channel, name, expected) in cases {
+        assert!(!channel.is_private(), "{name} 应为公共频道");
+        assert_eq!(channel.endpoint(), ConnectionType::Public);
+        assert_eq!(channel.name(), name);
+        let value = serde_json::to_value(&channel).expect("序列化应成功");
+        assert_eq!(value, expected);
+        let restored: Channel = serde_json::from_value(value).expect("反序列化应成功");
+        assert_eq!(restored, channel);
+    }
+
+    // 经济日历在业务端点推送，且需要登录
+    let calendar = Channel::EconomicCalendar;
+    assert!(calendar.is_private());
+    assert_eq!(calendar.endpoint(), ConnectionType::Business);
+    assert_eq!(
+        serde_json::to_string(&calendar).expect("序列化应成功"),
+        "{\"channel\":\"economic-calendar\"}"
+    );
+}
+
 #[test]
 fn test_fills_channel_serialization_and_flags() {
     let chan = Channel::Fills {
@@ -157,6 +238,65 @@ fn test_fills_channel_serialization_and_flags() {
     assert!(value.get("instId").is_none());
 }
 
+#[test]
+fn test_risk_and_funding_channels_serialization() {
+    let cases = vec![
+        (
+            Channel::LiquidationWarning {
+                inst_type: "SWAP".into(),
+                inst_family: None,
+                inst_id: Some("BTC-USDT-SWAP".into()),
+            },
+            "liquidation-warning",
+            ConnectionType::Private,
+            serde_json::json!({"channel": "liquidation-warning", "instType": "SWAP", "instId": "BTC-USDT-SWAP"}),
+        ),
+        (
+            Channel::AccountGreeks { ccy: None },
+            "account-greeks",
+            ConnectionType::Private,
+            serde_json::json!({"channel": "account-greeks"}),
+        ),
+        (
+            Channel::DepositInfo {
+                ccy: Some("USDT".into()),
+            },
+            "deposit-info",
+            ConnectionType::Business,
+            serde_json::json!({"channel": "deposit-info", "ccy": "USDT"}),
+        ),
+        (
+            Channel::WithdrawalInfo { ccy: None },
+            "withdrawal-info",
+            ConnectionType::Business,
+            serde_json::json!({"channel": "withdrawal-info"}),
+        ),
+    ];
+
+    for (channel, name, endpoint, expected) in cases {
+        assert!(channel.is_private(), "{name} 应为私有频道");
+        assert_eq!(channel.name(), name);
+        assert_eq!(channel.endpoint(), endpoint);
+        let value = serde_json::to_value(&channel).expect("序列化应成功");
+        assert_eq!(value, expected);
+        let restored: Channel = serde_json::from_value(value).expect("反序列化应成功");
+        assert_eq!(restored, channel);
+    }
+
+    // adl-warning 推送保险基金状态，由公共端点提供
+    let adl = Channel::AdlWarning {
+        inst_type: "FUTURES".into(),
+        inst_family: Some("BTC-USD".into()),
+    };
+    assert!(!adl.is_private());
+    assert_eq!(adl.name(), "adl-warning");
+    assert_eq!(adl.endpoint(), ConnectionType::Public);
+    assert_eq!(
+        serde_json::to_value(&adl).expect("序列化应成功"),
+        serde_json::json!({"channel": "adl-warning", "instType": "FUTURES", "instFamily": "BTC-USD"})
+    );
+}
+
 #[test]
 fn test_strategy_channels_serialization() {
     // grid-orders
@@ -220,6 +360,61 @@ fn test_block_channels_serialization() {
     assert_eq!(v["instFamily"], "BTC-USD");
 }
 
+#[test]
+fn test_spread_channels_serialization() {
+    let sprd = || "BTC-USDT_BTC-USDT-SWAP".to_string();
+    let cases = vec![
+        (Channel::SprdOrders { sprd_id: None }, "sprd-orders", true),
+        (
+            Channel::SprdTrades {
+                sprd_id: Some(sprd()),
+            },
+            "sprd-trades",
+            true,
+        ),
+        (
+            Channel::SprdPublicTrades { sprd_id: sprd() },
+            "sprd-public-trades",
+            false,
+        ),
+        (
+            Channel::SprdBooks5 { sprd_id: sprd() },
+            "sprd-books5",
+            false,
+        ),
+        (
+            Channel::SprdBboTbt { sprd_id: sprd() },
+            "sprd-bbo-tbt",
+            false,
+        ),
+        (
+            Channel::SprdBooksL2Tbt { sprd_id: sprd() },
+            "sprd-books-l2-tbt",
+            false,
+        ),
+        (
+            Channel::SprdTickers { sprd_id: sprd() },
+            "sprd-tickers",
+            false,
+        ),
+    ];
+
+    for (channel, name, private) in cases {
+        assert_eq!(channel.name(), name);
+        assert_eq!(channel.is_private(), private, "{name} 私有标记错误");
+        assert_eq!(channel.endpoint(), ConnectionType::Business, "{name}");
+        let value = serde_json::to_value(&channel).expect("序列化应成功");
+        assert_eq!(value["channel"], name);
+        if name != "sprd-orders" {
+            assert_eq!(value["sprdId"], "BTC-USDT_BTC-USDT-SWAP");
+        } else {
+            assert!(value.get("sprdId").is_none());
+        }
+        let restored: Channel = serde_json::from_value(value).expect("反序列化应成功");
+        assert_eq!(restored, channel);
+    }
+}
+
 #[test]
 fn test_advanced_algo_channel_names() {
     let advance = Channel::AlgoAdvance {
@@ -334,3 +529,55 @@ fn test_remaining_channel_variants_names_and_privacy() {
         assert_eq!(v["channel"], expected_name);
     }
 }
+
+#[test]
+fn test_channel_endpoints() {
+    let cases = vec![
+        (
+            Channel::Tickers {
+                inst_id: "BTC-USDT".into(),
+            },
+            ConnectionType::Public,
+        ),
+        (Channel::Account { ccy: None }, ConnectionType::Private),
+        (
+            Channel::Candle {
+                bar: Bar::M1,
+                inst_id: "BTC-USDT".into(),
+            },
+            ConnectionType::Business,
+        ),
+        (
+            Channel::OrdersAlgo {
+                inst_type: "ANY".into(),
+                inst_family: None,
+                inst_id: None,
+            },
+            ConnectionType::Business,
+        ),
+        (
+            Channel::GridOrdersSpot {
+                algo_id: None,
+                inst_id: None,
+            },
+            ConnectionType::Business,
+        ),
+        (
+            Channel::PublicBlockTrades { inst_family: None },
+            ConnectionType::Business,
+        ),
+    ];
+
+    for (channel, endpoint) in cases {
+        assert_eq!(channel.endpoint(), endpoint, "{}", channel.name());
+    }
+
+    // 业务连接仅在包含私有频道时需要登录
+    let candles = [Channel::Candle {
+        bar: Bar::H1,
+        inst_id: "BTC-USDT".into(),
+    }];
+    assert!(!ConnectionType::Business.needs_login(&candles));
+    assert!(ConnectionType::Private.needs_login(&[]));
+    assert!(ConnectionType::Business.needs_login(&[Channel::Rfqs { inst_family: None }]));
+}
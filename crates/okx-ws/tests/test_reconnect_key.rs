@@ -1,5 +1,6 @@
 #![allow(missing_docs)]
 
+use okx_core::types::Bar;
 use okx_ws::{channel_from_key, channel_key_from, Channel};
 
 #[test]
@@ -33,3 +34,42 @@ fn channel_key_roundtrip_for_advanced_channels() {
         assert_eq!(restored.name(), ch.name());
     }
 }
+
+#[test]
+fn candle_channels_roundtrip_for_every_bar() {
+    for bar in Bar::ALL {
+        let inst_id = "BTC-USDT".to_string();
+        let cases = [
+            (
+                Channel::Candle {
+                    bar,
+                    inst_id: inst_id.clone(),
+                },
+                format!("candle{}", bar.as_str()),
+            ),
+            (
+                Channel::MarkPriceCandle {
+                    bar,
+                    inst_id: inst_id.clone(),
+                },
+                format!("mark-price-candle{}", bar.as_str()),
+            ),
+            (
+                Channel::IndexCandle { bar, inst_id },
+                format!("index-candle{}", bar.as_str()),
+            ),
+        ];
+
+        for (channel, name) in cases {
+            assert_eq!(channel.name(), name);
+            let json = serde_json::to_value(&channel).expect("序列化应成功");
+            assert_eq!(json["channel"], name.as_str());
+            assert_eq!(json["instId"], "BTC-USDT");
+
+            let restored = channel_from_key(&channel_key_from(&channel)).expect("应解析回 Channel");
+            assert_eq!(restored, channel);
+        }
+    }
+
+    assert!(channel_from_key(r#"{"channel":"candle7m","instId":"BTC-USDT"}"#).is_none());
+}
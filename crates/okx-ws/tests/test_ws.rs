@@ -265,6 +265,7 @@ mod message_tests {
             (WsEvent::Unsubscribe, "unsubscribe"),
             (WsEvent::Login, "login"),
             (WsEvent::Error, "error"),
+            (WsEvent::Notice, "notice"),
         ];
         for (event, expected) in cases {
             assert_eq!(event.to_string(), expected);